@@ -0,0 +1,81 @@
+use solution::Solution;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        solution::parse_lines(input)
+    }
+
+    fn part1(module_masses: &Self::Input) -> u32 {
+        fuel_requirements(module_masses)
+    }
+
+    fn part2(module_masses: &Self::Input) -> u32 {
+        fuel_requirements_refined(module_masses)
+    }
+}
+
+fn calculate_fuel(mass: u32) -> Option<u32> {
+    (mass / 3).checked_sub(2)
+}
+
+fn calculate_fuel_refined(mass: u32) -> u32 {
+    std::iter::successors(Some(mass), |&mass| calculate_fuel(mass))
+        .skip(1)
+        .sum()
+}
+
+fn fuel_requirements(module_masses: &[u32]) -> u32 {
+    module_masses
+        .iter()
+        .map(|&mass| calculate_fuel(mass).unwrap())
+        .sum()
+}
+
+fn fuel_requirements_refined(module_masses: &[u32]) -> u32 {
+    module_masses
+        .iter()
+        .map(|&mass| calculate_fuel_refined(mass))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solution::{answers, Answer};
+    use std::fs;
+
+    #[test]
+    fn fuel_weight() {
+        assert_eq!(Some(2), calculate_fuel(12));
+        assert_eq!(Some(2), calculate_fuel(14));
+        assert_eq!(Some(654), calculate_fuel(1969));
+        assert_eq!(Some(33583), calculate_fuel(100756));
+    }
+
+    #[test]
+    fn fuel_weight_including_fuel() {
+        assert_eq!(2, calculate_fuel_refined(14));
+        assert_eq!(966, calculate_fuel_refined(1969));
+        assert_eq!(50346, calculate_fuel_refined(100756));
+    }
+
+    fn input() -> Vec<u32> {
+        Day01::parse(&fs::read_to_string("input.txt").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn part_1() {
+        assert_eq!(answers::expected(1, 1), Day01::part1(&input()).to_answer());
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(answers::expected(1, 2), Day01::part2(&input()).to_answer());
+    }
+}
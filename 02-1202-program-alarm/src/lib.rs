@@ -0,0 +1,144 @@
+use intcode::{symbolic, MemoryType};
+use solution::Solution;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = Option<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        solution::parse_separated(input, ',')
+    }
+
+    fn part1(input: &Self::Input) -> u32 {
+        run_program(input, 12, 2)
+    }
+
+    fn part2(input: &Self::Input) -> Option<u32> {
+        find_output(input, 19_690_720).map(|(noun, verb)| noun * 100 + verb)
+    }
+}
+
+fn run_program(input: &[u32], noun: u32, verb: u32) -> u32 {
+    let mut computer = Computer::new(input);
+    computer.run_program(noun, verb)
+}
+
+// Solves for noun and verb symbolically instead of trying all 10000 combinations.
+fn find_output(input: &[u32], output: u32) -> Option<(u32, u32)> {
+    let program: Vec<MemoryType> = input.iter().map(|&value| MemoryType::from(value)).collect();
+    let cells = [(1, 0..=99), (2, 0..=99)];
+    symbolic::find_cell_values(&program, &cells, 0, MemoryType::from(output))
+        .map(|values| (values[0] as u32, values[1] as u32))
+}
+
+const ADD: u32 = 1;
+const MULTIPLY: u32 = 2;
+const HALT: u32 = 99;
+
+enum Status {
+    Continue,
+    Terminate,
+}
+
+struct Computer {
+    tape: Vec<u32>,
+    ip: usize,
+}
+
+impl Computer {
+    fn new(tape: &[u32]) -> Self {
+        Self {
+            tape: tape.to_vec(),
+            ip: 0,
+        }
+    }
+
+    fn run_program(&mut self, noun: u32, verb: u32) -> u32 {
+        self.tape[1] = noun;
+        self.tape[2] = verb;
+        loop {
+            if let Status::Terminate = self.execute_instruction() {
+                break;
+            }
+            self.advance_program_counter();
+        }
+        self.tape[0]
+    }
+
+    fn advance_program_counter(&mut self) {
+        self.ip += 4;
+    }
+
+    fn execute_instruction(&mut self) -> Status {
+        match self.tape[self.ip] {
+            opcode @ ADD | opcode @ MULTIPLY => {
+                let a = self.tape[self.tape[self.ip + 1] as usize];
+                let b = self.tape[self.tape[self.ip + 2] as usize];
+                let output_pos = self.tape[self.ip + 3] as usize;
+                self.tape[output_pos] = if opcode == ADD { a + b } else { a * b };
+                Status::Continue
+            }
+            HALT => Status::Terminate,
+            _ => panic!(
+                "Invalid opcode ({}) at position {}!",
+                self.tape[self.ip], self.ip
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solution::{answers, Answer};
+    use std::fs;
+
+    #[test]
+    fn example_program_1() {
+        let input = vec![1, 0, 0, 0, 99];
+        let mut computer = Computer::new(&input);
+        computer.run_program(0, 0);
+        assert_eq!(vec![2, 0, 0, 0, 99], computer.tape);
+    }
+
+    #[test]
+    fn example_program_2() {
+        let input = vec![2, 3, 0, 3, 99];
+        let mut computer = Computer::new(&input);
+        computer.run_program(3, 0);
+        assert_eq!(vec![2, 3, 0, 6, 99], computer.tape);
+    }
+
+    #[test]
+    fn example_program_3() {
+        let input = vec![2, 4, 4, 5, 99, 0];
+        let mut computer = Computer::new(&input);
+        computer.run_program(4, 4);
+        assert_eq!(vec![2, 4, 4, 5, 99, 9801], computer.tape);
+    }
+
+    #[test]
+    fn example_program_4() {
+        let input = vec![1, 1, 1, 4, 99, 5, 6, 0, 99];
+        let mut computer = Computer::new(&input);
+        computer.run_program(1, 1);
+        assert_eq!(vec![30, 1, 1, 4, 2, 5, 6, 0, 99], computer.tape);
+    }
+
+    fn input() -> Vec<u32> {
+        Day02::parse(&fs::read_to_string("input.txt").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn part_1() {
+        assert_eq!(answers::expected(2, 1), Day02::part1(&input()).to_answer());
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(answers::expected(2, 2), Day02::part2(&input()).to_answer());
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use benchmark::Bencher;
+use solution::Solution;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<WirePath>;
+    type Answer1 = Option<i32>;
+    type Answer2 = Option<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        let wire_paths = input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| WirePath::parse_line(i + 1, line))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        if wire_paths.len() != 2 {
+            return Err(format!("Expected 2 wire paths, got {}", wire_paths.len()));
+        }
+        Ok(wire_paths)
+    }
+
+    fn part1(wire_paths: &Self::Input) -> Option<i32> {
+        let points_a = trace_path(&wire_paths[0]);
+        let points_b = trace_path(&wire_paths[1]);
+        let intersections = map_intersection(&points_a, &points_b);
+        find_closest_intersection(&intersections)
+    }
+
+    fn part2(wire_paths: &Self::Input) -> Option<u32> {
+        let points_a = trace_path(&wire_paths[0]);
+        let points_b = trace_path(&wire_paths[1]);
+        let intersections = map_intersection(&points_a, &points_b);
+        find_fewest_steps_to_intersection(&intersections, &points_a, &points_b)
+    }
+
+    // The steps that both parts repeat for both wires
+    fn benchmarks(wire_paths: &Self::Input, bencher: &mut Bencher) {
+        bencher.bench("trace_path", || trace_path(&wire_paths[0]));
+        let points_a = trace_path(&wire_paths[0]);
+        let points_b = trace_path(&wire_paths[1]);
+        bencher.bench("map_intersection", || {
+            map_intersection(&points_a, &points_b)
+        });
+    }
+}
+
+fn trace_path(path: &WirePath) -> HashMap<Point, u32> {
+    // Pre-allocate to gain performance
+    let mut map = HashMap::with_capacity(path.segments.len() * 1000);
+
+    let mut current = Point::new(0, 0);
+    let mut distance = 0;
+
+    for segment in &path.segments {
+        for _ in 0..segment.length {
+            current = match segment.direction {
+                Direction::Left => Point::new(current.x - 1, current.y),
+                Direction::Right => Point::new(current.x + 1, current.y),
+                Direction::Up => Point::new(current.x, current.y + 1),
+                Direction::Down => Point::new(current.x, current.y - 1),
+            };
+            distance += 1;
+            map.entry(current).or_insert(distance);
+        }
+    }
+
+    map
+}
+
+fn find_closest_intersection(intersections: &[Point]) -> Option<i32> {
+    intersections.iter().map(|p| p.x.abs() + p.y.abs()).min()
+}
+
+fn find_fewest_steps_to_intersection(
+    intersections: &[Point],
+    a: &HashMap<Point, u32>,
+    b: &HashMap<Point, u32>,
+) -> Option<u32> {
+    intersections
+        .iter()
+        .map(|p| a.get(p).unwrap() + b.get(p).unwrap())
+        .min()
+}
+
+fn map_intersection<K, V>(a: &HashMap<K, V>, b: &HashMap<K, V>) -> Vec<K>
+where
+    K: std::cmp::Eq + std::hash::Hash + Copy,
+{
+    a.iter()
+        .filter(|(k, _)| b.contains_key(k))
+        .map(|(&k, _)| k)
+        .collect()
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Point {
+    fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Direction {
+    Right,
+    Left,
+    Up,
+    Down,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Segment {
+    direction: Direction,
+    length: u32,
+}
+
+#[derive(Debug)]
+pub struct WirePath {
+    segments: Vec<Segment>,
+}
+
+impl WirePath {
+    // Parses the segments of the path on a line (1-based) of the input.
+    fn parse_line(line: usize, definition: &str) -> Result<Self, ParseWireError> {
+        let mut segments = Vec::new();
+        let mut column = 1;
+        for chunk in definition.split(',') {
+            let mut chars = chunk.chars();
+            let direction = match chars.next() {
+                Some('R') => Direction::Right,
+                Some('L') => Direction::Left,
+                Some('U') => Direction::Up,
+                Some('D') => Direction::Down,
+                _ => {
+                    return Err(ParseWireError::InvalidDirection {
+                        line,
+                        column,
+                        segment: chunk.to_string(),
+                    })
+                }
+            };
+            let length = chars
+                .as_str()
+                .parse()
+                .map_err(|_| ParseWireError::InvalidLength {
+                    line,
+                    column: column + 1,
+                    segment: chunk.to_string(),
+                })?;
+            segments.push(Segment { direction, length });
+            column += chunk.chars().count() + 1;
+        }
+
+        Ok(Self { segments })
+    }
+}
+
+// A wire path on its own is the first line of an input.
+impl FromStr for WirePath {
+    type Err = ParseWireError;
+
+    fn from_str(definition: &str) -> Result<Self, Self::Err> {
+        Self::parse_line(1, definition)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWireError {
+    // Line and column (1-based) of the segment's direction
+    InvalidDirection {
+        line: usize,
+        column: usize,
+        segment: String,
+    },
+    // Line and column (1-based) of the segment's length
+    InvalidLength {
+        line: usize,
+        column: usize,
+        segment: String,
+    },
+}
+
+impl fmt::Display for ParseWireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseWireError::InvalidDirection {
+                line,
+                column,
+                segment,
+            } => write!(
+                f,
+                "Invalid direction of segment {:?} at line {}, column {}",
+                segment, line, column
+            ),
+            ParseWireError::InvalidLength {
+                line,
+                column,
+                segment,
+            } => write!(
+                f,
+                "Invalid length of segment {:?} at line {}, column {}",
+                segment, line, column
+            ),
+        }
+    }
+}
+
+impl Error for ParseWireError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solution::{answers, Answer};
+    use std::fs;
+
+    #[test]
+    fn intersection() {
+        let mut a: HashMap<u32, u32> = HashMap::new();
+        a.insert(1, 1);
+        a.insert(2, 4);
+        a.insert(3, 9);
+
+        let mut b: HashMap<u32, u32> = HashMap::new();
+        b.insert(4, 32);
+        b.insert(2, 8);
+        b.insert(3, 18);
+
+        let mut intersection = map_intersection(&a, &b);
+        intersection.sort();
+
+        assert_eq!(vec![2, 3], intersection);
+    }
+
+    #[test]
+    fn closest_intersection_1() {
+        let definitions = [
+            "R75,D30,R83,U83,L12,D49,R71,U7,L72",
+            "U62,R66,U55,R34,D71,R55,D58,R83",
+        ];
+        let wire_paths: Vec<WirePath> = definitions
+            .iter()
+            .map(|definition| definition.parse().unwrap())
+            .collect();
+
+        let points_a = trace_path(&wire_paths[0]);
+        let points_b = trace_path(&wire_paths[1]);
+        let intersections = map_intersection(&points_a, &points_b);
+        assert_eq!(Some(159), find_closest_intersection(&intersections));
+    }
+
+    #[test]
+    fn closest_intersection_2() {
+        let definitions = [
+            "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51",
+            "U98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+        ];
+        let wire_paths: Vec<WirePath> = definitions
+            .iter()
+            .map(|definition| definition.parse().unwrap())
+            .collect();
+
+        let points_a = trace_path(&wire_paths[0]);
+        let points_b = trace_path(&wire_paths[1]);
+        let intersections = map_intersection(&points_a, &points_b);
+        assert_eq!(Some(135), find_closest_intersection(&intersections));
+    }
+
+    #[test]
+    fn fewest_steps_1() {
+        let definitions = [
+            "R75,D30,R83,U83,L12,D49,R71,U7,L72",
+            "U62,R66,U55,R34,D71,R55,D58,R83",
+        ];
+        let wire_paths: Vec<WirePath> = definitions
+            .iter()
+            .map(|definition| definition.parse().unwrap())
+            .collect();
+
+        let points_a = trace_path(&wire_paths[0]);
+        let points_b = trace_path(&wire_paths[1]);
+        let intersections = map_intersection(&points_a, &points_b);
+        assert_eq!(
+            Some(610),
+            find_fewest_steps_to_intersection(&intersections, &points_a, &points_b)
+        );
+    }
+
+    #[test]
+    fn fewest_steps_2() {
+        let definitions = [
+            "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51",
+            "U98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+        ];
+        let wire_paths: Vec<WirePath> = definitions
+            .iter()
+            .map(|definition| definition.parse().unwrap())
+            .collect();
+
+        let points_a = trace_path(&wire_paths[0]);
+        let points_b = trace_path(&wire_paths[1]);
+        let intersections = map_intersection(&points_a, &points_b);
+        assert_eq!(
+            Some(410),
+            find_fewest_steps_to_intersection(&intersections, &points_a, &points_b)
+        );
+    }
+
+    #[test]
+    fn invalid_paths() {
+        assert_eq!(
+            Err(ParseWireError::InvalidDirection {
+                line: 1,
+                column: 4,
+                segment: String::from("X5")
+            }),
+            "R8,X5,L5".parse::<WirePath>().map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseWireError::InvalidLength {
+                line: 1,
+                column: 6,
+                segment: String::from("U")
+            }),
+            "R12,U,L5".parse::<WirePath>().map(|_| ())
+        );
+
+        let error = Day03::parse("R8,U5,L5,D3\nU7,R6,,L4").err().unwrap();
+        assert_eq!(
+            "Invalid direction of segment \"\" at line 2, column 7",
+            error
+        );
+    }
+
+    fn input() -> Vec<WirePath> {
+        Day03::parse(&fs::read_to_string("input.txt").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn part_1() {
+        assert_eq!(answers::expected(3, 1), Day03::part1(&input()).to_answer());
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(answers::expected(3, 2), Day03::part2(&input()).to_answer());
+    }
+}
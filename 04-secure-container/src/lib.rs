@@ -0,0 +1,173 @@
+use std::io::Write;
+
+use solution::Solution;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = (u32, u32);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        match solution::parse_separated(input, '-')?.as_slice() {
+            &[min, max] => Ok((min, max)),
+            range => Err(format!("Expected range of 2 values, got {}", range.len())),
+        }
+    }
+
+    fn part1(&(min, max): &Self::Input) -> usize {
+        count_valid_passwords(min, max, true)
+    }
+
+    fn part2(&(min, max): &Self::Input) -> usize {
+        // No large groups
+        count_valid_passwords(min, max, false)
+    }
+}
+
+fn count_valid_passwords(min: u32, max: u32, allow_larger_group: bool) -> usize {
+    let validator = Validator::new(min, max, allow_larger_group);
+    (min..=max).filter(|&pwd| validator.validate(pwd)).count()
+}
+
+#[derive(Debug)]
+struct Validator {
+    min: u32,
+    max: u32,
+    allow_larger_group: bool,
+}
+
+impl Validator {
+    fn new(min: u32, max: u32, allow_larger_group: bool) -> Self {
+        Self {
+            min,
+            max,
+            allow_larger_group,
+        }
+    }
+
+    fn validate(&self, password: u32) -> bool {
+        // Check here to prevent buffer overflow when converting to digits
+        if !Self::six_digits(password) {
+            return false;
+        }
+
+        let mut digits = [0_u8; 6];
+        write!(&mut digits[..], "{}", password).unwrap();
+
+        self.in_range(password)
+            && self.check_adjacent_digits(&digits)
+            && Self::never_decrease(&digits)
+    }
+
+    fn six_digits(password: u32) -> bool {
+        password > 99999 && password < 1_000_000
+    }
+
+    fn in_range(&self, password: u32) -> bool {
+        password >= self.min && password <= self.max
+    }
+
+    fn check_adjacent_digits(&self, password: &[u8]) -> bool {
+        let mut previous = 0;
+        let mut has_adjacent = false;
+        let mut current_adjacent = 0;
+        for &c in password.iter() {
+            if c == previous {
+                current_adjacent += 1;
+                has_adjacent = true;
+            } else {
+                if current_adjacent == 1 {
+                    return true;
+                }
+                current_adjacent = 0;
+            }
+            previous = c;
+        }
+        self.allow_larger_group && has_adjacent || current_adjacent == 1
+    }
+
+    fn never_decrease(password: &[u8]) -> bool {
+        let mut previous = 0;
+        for &c in password.iter() {
+            if c < previous {
+                return false;
+            }
+            previous = c;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solution::{answers, Answer};
+    use std::fs;
+
+    #[test]
+    fn six_digit_number() {
+        let range = Validator::new(0, u32::MAX, true);
+        assert!(!range.validate(99999));
+        assert!(range.validate(599999));
+        assert!(range.validate(999999));
+        assert!(!range.validate(1222222));
+    }
+
+    #[test]
+    fn within_range() {
+        let range = Validator::new(234456, 456677, true);
+        assert!(!range.validate(234455));
+        assert!(range.validate(234456));
+        assert!(range.validate(444444));
+        assert!(range.validate(456677));
+        assert!(!range.validate(456678));
+    }
+
+    #[test]
+    fn adjacent_digits() {
+        let range = Validator::new(300000, 500000, true);
+        assert!(!range.validate(345678));
+        assert!(range.validate(344478));
+        assert!(range.validate(344567));
+    }
+
+    #[test]
+    fn never_decrease() {
+        let range = Validator::new(300000, 500000, true);
+        assert!(!range.validate(432100));
+        assert!(range.validate(444444));
+        assert!(range.validate(455677));
+    }
+
+    #[test]
+    fn examples() {
+        let range = Validator::new(0, u32::MAX, true);
+        assert!(range.validate(111111));
+        assert!(!range.validate(223450));
+        assert!(!range.validate(123789));
+    }
+
+    #[test]
+    fn larger_groups() {
+        let range = Validator::new(0, u32::MAX, false);
+        assert!(range.validate(112233));
+        assert!(!range.validate(123444));
+        assert!(range.validate(111122));
+    }
+
+    fn input() -> (u32, u32) {
+        Day04::parse(&fs::read_to_string("input.txt").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn part_1() {
+        assert_eq!(answers::expected(4, 1), Day04::part1(&input()).to_answer());
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(answers::expected(4, 2), Day04::part2(&input()).to_answer());
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use adhoc_derive::FromStr;
+use solution::Solution;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Graph;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        let orbits: Vec<Orbit> = solution::parse_lines(input)?;
+        Ok(Graph::construct_graph(&orbits))
+    }
+
+    fn part1(graph: &Self::Input) -> usize {
+        graph.count_orbits()
+    }
+
+    fn part2(graph: &Self::Input) -> usize {
+        // Transfers between the objects YOU and SAN are orbiting
+        graph.minimal_distance("YOU", "SAN") - 2
+    }
+}
+
+#[derive(Debug, FromStr)]
+#[adhoc(regex = r"^(?P<center>.+)\)(?P<object>.+)$")]
+struct Orbit {
+    object: String,
+    center: String,
+}
+
+#[derive(Debug)]
+struct Node {
+    object: String,
+    parent: Option<String>,
+    children: Vec<String>,
+}
+
+pub struct Graph {
+    root: String,
+    nodes: HashMap<String, Node>,
+}
+
+impl Graph {
+    fn construct_graph(orbits: &[Orbit]) -> Self {
+        let mut graph = Self {
+            root: String::from("COM"),
+            nodes: HashMap::new(),
+        };
+        for orbit in orbits {
+            let node = graph.nodes.entry(orbit.object.clone()).or_insert(Node {
+                object: orbit.object.clone(),
+                parent: None,
+                children: Vec::new(),
+            });
+            assert!(node.parent.is_none());
+            node.parent = Some(orbit.center.clone());
+
+            let parent = graph.nodes.entry(orbit.center.clone()).or_insert(Node {
+                object: orbit.center.clone(),
+                parent: None,
+                children: Vec::new(),
+            });
+            parent.children.push(orbit.object.clone());
+        }
+        graph
+    }
+
+    fn count_orbits(&self) -> usize {
+        let root = self.nodes.get(&self.root).unwrap();
+        self.count_depth(root, 0)
+    }
+
+    fn count_depth(&self, node: &Node, depth: usize) -> usize {
+        let mut sum = depth;
+        for child in &node.children {
+            let node = self.nodes.get(child).unwrap();
+            sum += self.count_depth(node, depth + 1);
+        }
+        sum
+    }
+
+    fn minimal_distance(&self, from: &str, to: &str) -> usize {
+        let mut visited: HashMap<String, usize> = HashMap::new();
+
+        // Start at from node and go up the parents
+        let mut distance = 0;
+        let mut current = self.nodes.get(from).unwrap();
+        visited.insert(current.object.clone(), distance);
+
+        while let Some(parent) = &current.parent {
+            current = self.nodes.get(parent).unwrap();
+
+            distance += 1;
+            visited.insert(current.object.clone(), distance);
+        }
+
+        // Start at to node and go up the parents
+        distance = 0;
+        current = self.nodes.get(to).unwrap();
+
+        loop {
+            if visited.contains_key(&current.object) {
+                return distance + visited.get(&current.object).unwrap();
+            }
+
+            distance += 1;
+            let parent = match &current.parent {
+                Some(parent) => parent,
+                None => break,
+            };
+            current = self.nodes.get(parent).unwrap();
+        }
+
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solution::{answers, Answer};
+    use std::fs;
+
+    fn input() -> Graph {
+        Day06::parse(&fs::read_to_string("input.txt").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn part_1() {
+        assert_eq!(answers::expected(6, 1), Day06::part1(&input()).to_answer());
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(answers::expected(6, 2), Day06::part2(&input()).to_answer());
+    }
+}
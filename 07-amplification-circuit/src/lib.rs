@@ -0,0 +1,204 @@
+use intcode::{MemoryType, RunState, Scheduler};
+use solution::Solution;
+
+const PHASE_SETTINGS: [u8; 5] = [0, 1, 2, 3, 4];
+const PHASE_SETTINGS_FEEDBACK: [u8; 5] = [5, 6, 7, 8, 9];
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<MemoryType>;
+    type Answer1 = MemoryType;
+    type Answer2 = MemoryType;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        intcode::parse_program(input).map_err(|e| e.to_string())
+    }
+
+    fn part1(program: &Self::Input) -> MemoryType {
+        find_best_phase_settings(program, 0, PHASE_SETTINGS, false)
+    }
+
+    fn part2(program: &Self::Input) -> MemoryType {
+        find_best_phase_settings(program, 0, PHASE_SETTINGS_FEEDBACK, true)
+    }
+}
+
+// Runs one amplifier per phase setting, each feeding the next one. With feedback, the last
+// amplifier feeds the first one and they run until all of them halted.
+fn run_amplifiers(
+    program: &[MemoryType],
+    phase_settings: [u8; 5],
+    initial_input: MemoryType,
+    feedback: bool,
+) -> MemoryType {
+    let mut scheduler = Scheduler::new();
+    for &phase_setting in &phase_settings {
+        let amplifier = scheduler.add(program);
+        scheduler.push_input(amplifier, MemoryType::from(phase_setting));
+    }
+    for amplifier in 0..4 {
+        scheduler.connect(amplifier, amplifier + 1);
+    }
+    if feedback {
+        scheduler.connect(4, 0);
+    }
+    scheduler.push_input(0, initial_input);
+
+    if let Err(e) = scheduler.run() {
+        panic!("Amplifiers didn't halt: {}", e);
+    }
+    match scheduler.state(4) {
+        RunState::Stopped(output) => output,
+        state => unreachable!("Unexpected state: {:?}", state),
+    }
+}
+
+fn find_best_phase_settings(
+    program: &[MemoryType],
+    initial_input: MemoryType,
+    mut phase_settings: [u8; 5],
+    feedback: bool,
+) -> MemoryType {
+    let mut highest_thruster_value = 0;
+    // Create iterator that generates all permutations
+    let permutations = permutohedron::Heap::new(&mut phase_settings);
+    for phase_setting in permutations {
+        let thruster_input = run_amplifiers(program, phase_setting, initial_input, feedback);
+        highest_thruster_value = MemoryType::max(thruster_input, highest_thruster_value);
+    }
+
+    highest_thruster_value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use intcode::Computer;
+    use solution::{answers, Answer};
+    use std::collections::VecDeque;
+    use std::fs;
+
+    fn input() -> Vec<MemoryType> {
+        Day07::parse(&fs::read_to_string("input.txt").unwrap()).unwrap()
+    }
+
+    fn run(program: &[MemoryType], input: &[MemoryType]) -> (Vec<MemoryType>, Vec<MemoryType>) {
+        let input = input.iter().copied().collect::<VecDeque<_>>();
+        let mut computer = Computer::new(0, program, input, Vec::new());
+        computer.run_program();
+        let memory = (0..program.len())
+            .map(|address| computer.peek(address))
+            .collect();
+        (memory, computer.get_output().clone())
+    }
+
+    #[test]
+    fn example_program_1() {
+        let program = vec![1, 0, 0, 0, 99];
+        assert_eq!(vec![2, 0, 0, 0, 99], run(&program, &[]).0);
+    }
+
+    #[test]
+    fn example_program_2() {
+        let program = vec![2, 3, 0, 3, 99];
+        assert_eq!(vec![2, 3, 0, 6, 99], run(&program, &[]).0);
+    }
+
+    #[test]
+    fn example_program_3() {
+        let program = vec![2, 4, 4, 5, 99, 0];
+        assert_eq!(vec![2, 4, 4, 5, 99, 9801], run(&program, &[]).0);
+    }
+
+    #[test]
+    fn example_program_4() {
+        let program = vec![1, 1, 1, 4, 99, 5, 6, 0, 99];
+        assert_eq!(vec![30, 1, 1, 4, 2, 5, 6, 0, 99], run(&program, &[]).0);
+    }
+
+    #[test]
+    fn input_output() {
+        let program = vec![3, 0, 4, 0, 99];
+        assert_eq!(vec![42], run(&program, &[42]).1);
+    }
+
+    #[test]
+    fn parameter_modes() {
+        let program = vec![1002, 4, 3, 4, 33];
+        assert_eq!(vec![1002, 4, 3, 4, 99], run(&program, &[]).0);
+    }
+
+    #[test]
+    fn example_program_5() {
+        let program = vec![
+            3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0,
+            0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
+            20, 1105, 1, 46, 98, 99,
+        ];
+        assert_eq!(vec![999], run(&program, &[7]).1);
+        assert_eq!(vec![1000], run(&program, &[8]).1);
+        assert_eq!(vec![1001], run(&program, &[9]).1);
+    }
+
+    #[test]
+    fn amplifier_example_1() {
+        let program = vec![
+            3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0,
+        ];
+        let thruster_input = find_best_phase_settings(&program, 0, PHASE_SETTINGS, false);
+        assert_eq!(43210, thruster_input);
+    }
+
+    #[test]
+    fn amplifier_example_2() {
+        let program = vec![
+            3, 23, 3, 24, 1002, 24, 10, 24, 1002, 23, -1, 23, 101, 5, 23, 23, 1, 24, 23, 23, 4, 23,
+            99, 0, 0,
+        ];
+        let thruster_input = find_best_phase_settings(&program, 0, PHASE_SETTINGS, false);
+        assert_eq!(54321, thruster_input);
+    }
+
+    #[test]
+    fn amplifier_example_3() {
+        let program = vec![
+            3, 31, 3, 32, 1002, 32, 10, 32, 1001, 31, -2, 31, 1007, 31, 0, 33, 1002, 33, 7, 33, 1,
+            33, 31, 31, 1, 32, 31, 31, 4, 31, 99, 0, 0, 0,
+        ];
+        let thruster_input = find_best_phase_settings(&program, 0, PHASE_SETTINGS, false);
+        assert_eq!(65210, thruster_input);
+    }
+
+    #[test]
+    fn amplifier_feedback_example_1() {
+        let program = vec![
+            3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1,
+            28, 1005, 28, 6, 99, 0, 0, 5,
+        ];
+        let thruster_input = find_best_phase_settings(&program, 0, PHASE_SETTINGS_FEEDBACK, true);
+        assert_eq!(139629729, thruster_input);
+    }
+
+    #[test]
+    fn amplifier_feedback_example_2() {
+        let program = vec![
+            3, 52, 1001, 52, -5, 52, 3, 53, 1, 52, 56, 54, 1007, 54, 5, 55, 1005, 55, 26, 1001, 54,
+            -5, 54, 1105, 1, 12, 1, 53, 54, 53, 1008, 54, 0, 55, 1001, 55, 1, 55, 2, 53, 55, 53, 4,
+            53, 1001, 56, -1, 56, 1005, 56, 6, 99, 0, 0, 0, 0, 10,
+        ];
+        let thruster_input = find_best_phase_settings(&program, 0, PHASE_SETTINGS_FEEDBACK, true);
+        assert_eq!(18216, thruster_input);
+    }
+
+    #[test]
+    fn part_1() {
+        assert_eq!(answers::expected(7, 1), Day07::part1(&input()).to_answer());
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(answers::expected(7, 2), Day07::part2(&input()).to_answer());
+    }
+}
@@ -0,0 +1,239 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use grid::{ocr, Grid, Point};
+use solution::Solution;
+
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = RawImage;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        RawImage::parse(input.trim(), WIDTH, HEIGHT).map_err(|e| e.to_string())
+    }
+
+    fn part1(image: &Self::Input) -> usize {
+        let counts = image.count_colors_in_layers();
+        product_of_layer_with_minimum_number_of_zeros(&counts)
+    }
+
+    // Message shown by the decoded image, or the image itself if it can't be read
+    fn part2(image: &Self::Input) -> String {
+        let image = image.stack_layers();
+        image.text().unwrap_or_else(|_| image.render())
+    }
+
+    fn statistics(image: &Self::Input) -> Option<String> {
+        Some(image.stack_layers().render())
+    }
+}
+
+fn product_of_layer_with_minimum_number_of_zeros(counts: &[(usize, usize, usize)]) -> usize {
+    counts
+        .iter()
+        .map(|(zeros, ones, twos)| (zeros, ones * twos))
+        .min_by_key(|(&zeros, _)| zeros)
+        .map(|(_, product)| product)
+        .unwrap()
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Color {
+    Black,
+    White,
+    Transparent,
+}
+
+// The error is the character that isn't a color.
+impl TryFrom<char> for Color {
+    type Error = char;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        match ch {
+            '0' => Ok(Color::Black),
+            '1' => Ok(Color::White),
+            '2' => Ok(Color::Transparent),
+            ch => Err(ch),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseImageError {
+    // Line and column are 1-based
+    InvalidPixel {
+        line: usize,
+        column: usize,
+        character: char,
+    },
+    // The number of pixels isn't a (non-zero) multiple of the size of a layer
+    IncompleteLayer {
+        pixels: usize,
+        layer_size: usize,
+    },
+}
+
+impl fmt::Display for ParseImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseImageError::InvalidPixel {
+                line,
+                column,
+                character,
+            } => write!(
+                f,
+                "Invalid pixel {:?} at line {}, column {}",
+                character, line, column
+            ),
+            ParseImageError::IncompleteLayer { pixels, layer_size } => write!(
+                f,
+                "The image has {} pixels, expected a multiple of {}",
+                pixels, layer_size
+            ),
+        }
+    }
+}
+
+impl Error for ParseImageError {}
+
+pub struct RawImage {
+    layers: Vec<Vec<Color>>,
+    width: usize,
+    height: usize,
+}
+
+impl RawImage {
+    fn parse(data: &str, width: usize, height: usize) -> Result<Self, ParseImageError> {
+        let mut pixels = Vec::with_capacity(data.len());
+        for (i, line) in data.lines().enumerate() {
+            for (column, ch) in line.chars().enumerate() {
+                let pixel =
+                    Color::try_from(ch).map_err(|character| ParseImageError::InvalidPixel {
+                        line: i + 1,
+                        column: column + 1,
+                        character,
+                    })?;
+                pixels.push(pixel);
+            }
+        }
+
+        let layer_size = width * height;
+        if pixels.is_empty() || pixels.len() % layer_size != 0 {
+            return Err(ParseImageError::IncompleteLayer {
+                pixels: pixels.len(),
+                layer_size,
+            });
+        }
+
+        Ok(Self {
+            layers: pixels.chunks(layer_size).map(<[Color]>::to_vec).collect(),
+            width,
+            height,
+        })
+    }
+
+    fn count_colors_in_layers(&self) -> Vec<(usize, usize, usize)> {
+        let mut counts = Vec::new();
+
+        for layer in &self.layers {
+            let mut count = (0, 0, 0);
+            for &digit in layer {
+                match digit {
+                    Color::Black => count.0 += 1,
+                    Color::White => count.1 += 1,
+                    Color::Transparent => count.2 += 1,
+                }
+            }
+            counts.push(count);
+        }
+
+        counts
+    }
+
+    fn stack_layers(&self) -> StackedImage {
+        let mut stacked = Grid::new(self.width, self.height, Color::Transparent);
+
+        for layer in &self.layers {
+            for (i, &digit) in layer.iter().enumerate() {
+                let point = Point::new((i % self.width) as isize, (i / self.width) as isize);
+                if stacked[point] == Color::Transparent {
+                    stacked[point] = digit;
+                }
+            }
+        }
+
+        StackedImage { image: stacked }
+    }
+}
+
+struct StackedImage {
+    image: Grid<Color>,
+}
+
+impl StackedImage {
+    fn render(&self) -> String {
+        self.image.render(|_, &pixel| match pixel {
+            Color::Black => '\u{2588}',
+            Color::White => '\u{2591}',
+            Color::Transparent => '.',
+        })
+    }
+
+    // The message is written in white.
+    fn text(&self) -> Result<String, ocr::OcrError> {
+        ocr::recognize(&self.image.map(|&pixel| pixel == Color::White))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solution::{answers, Answer};
+    use std::fs;
+
+    fn input() -> RawImage {
+        Day08::parse(&fs::read_to_string("input.txt").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn invalid_images() {
+        assert_eq!(
+            Err(ParseImageError::InvalidPixel {
+                line: 1,
+                column: 5,
+                character: '3'
+            }),
+            RawImage::parse("0122301", 3, 2).map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseImageError::IncompleteLayer {
+                pixels: 7,
+                layer_size: 6
+            }),
+            RawImage::parse("0122101", 3, 2).map(|_| ())
+        );
+        assert_eq!(
+            Err(String::from(
+                "The image has 0 pixels, expected a multiple of 150"
+            )),
+            Day08::parse("\n").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn part_1() {
+        assert_eq!(answers::expected(8, 1), Day08::part1(&input()).to_answer());
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(answers::expected(8, 2), Day08::part2(&input()).to_answer());
+    }
+}
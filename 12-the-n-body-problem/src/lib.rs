@@ -0,0 +1,206 @@
+use adhoc_derive::FromStr;
+use benchmark::Bencher;
+use solution::Solution;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Moon>;
+    type Answer1 = u64;
+    type Answer2 = Option<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        solution::parse_lines(input)
+    }
+
+    fn part1(moons: &Self::Input) -> u64 {
+        let mut simulator = OrbitSimulator::new(moons);
+        simulator.simulate(1000);
+        simulator.calculate_total_energy()
+    }
+
+    fn part2(moons: &Self::Input) -> Option<u64> {
+        let mut simulator = OrbitSimulator::new(moons);
+        simulator.find_periodicity()
+    }
+
+    // Both parts repeat this step, part 2 a few hundred thousand times per dimension.
+    fn benchmarks(moons: &Self::Input, bencher: &mut Bencher) {
+        let mut simulator = OrbitSimulator::new(moons);
+        bencher.bench("simulate_step", || simulator.simulate_step());
+    }
+}
+
+#[derive(Copy, Clone, Debug, FromStr)]
+#[adhoc(regex = r"^<x=(?P<x>-?\d+), y=(?P<y>-?\d+), z=(?P<z>-?\d+)>$")]
+pub struct Moon {
+    x: isize,
+    y: isize,
+    z: isize,
+    #[adhoc(construct_with = "0")]
+    dx: isize,
+    #[adhoc(construct_with = "0")]
+    dy: isize,
+    #[adhoc(construct_with = "0")]
+    dz: isize,
+}
+
+struct OrbitSimulator {
+    moons: Vec<Moon>,
+}
+
+impl OrbitSimulator {
+    fn new(moons: &[Moon]) -> Self {
+        Self {
+            moons: moons.to_vec(),
+        }
+    }
+
+    fn simulate(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.simulate_step();
+        }
+    }
+
+    fn simulate_step(&mut self) {
+        self.apply_gravity();
+        self.apply_velocity();
+    }
+
+    fn apply_gravity(&mut self) {
+        for a in 0..self.moons.len() {
+            for b in 0..self.moons.len() {
+                let dx = (self.moons[b].x - self.moons[a].x).signum();
+                let dy = (self.moons[b].y - self.moons[a].y).signum();
+                let dz = (self.moons[b].z - self.moons[a].z).signum();
+                self.moons[a].dx += dx;
+                self.moons[a].dy += dy;
+                self.moons[a].dz += dz;
+            }
+        }
+    }
+
+    fn apply_velocity(&mut self) {
+        for moon in &mut self.moons {
+            moon.x += moon.dx;
+            moon.y += moon.dy;
+            moon.z += moon.dz;
+        }
+    }
+
+    fn calculate_total_energy(&self) -> u64 {
+        self.moons
+            .iter()
+            .map(|moon| {
+                let epot = (moon.x.abs() + moon.y.abs() + moon.z.abs()) as u64;
+                let ekin = (moon.dx.abs() + moon.dy.abs() + moon.dz.abs()) as u64;
+                epot * ekin
+            })
+            .sum()
+    }
+
+    // `None` if the period doesn't fit into a u64
+    fn find_periodicity(&mut self) -> Option<u64> {
+        // Key insights (thanks reddit...):
+        // - The position/velocity update for one dimension only depends
+        //   on position/velocity values of that dimension => the period in each dimension can be calculated
+        //   independently, and the period of the whole system must be the least common multiple of all periods.
+        // - The mapping from previous state to current state is invertible, meaning we could simulate backwards.
+        //   => The cycle must include the initial state
+
+        let initial_state = self.moons.clone();
+        let mut periods = (0, 0, 0);
+        for step in 1u64.. {
+            // Simulate one step
+            self.simulate_step();
+
+            // Check for cycle
+            let mut cycle = (true, true, true);
+            for (i, moon) in self.moons.iter().enumerate() {
+                if moon.dx != 0 || moon.x != initial_state[i].x {
+                    cycle.0 = false;
+                }
+
+                if moon.dy != 0 || moon.y != initial_state[i].y {
+                    cycle.1 = false;
+                }
+
+                if moon.dz != 0 || moon.z != initial_state[i].z {
+                    cycle.2 = false;
+                }
+            }
+
+            if cycle.0 && periods.0 == 0 {
+                periods.0 = step;
+            }
+
+            if cycle.1 && periods.1 == 0 {
+                periods.1 = step;
+            }
+
+            if cycle.2 && periods.2 == 0 {
+                periods.2 = step;
+            }
+
+            // Stop if all periods have been found
+            if periods.0 != 0 && periods.1 != 0 && periods.2 != 0 {
+                break;
+            }
+        }
+
+        numtheory::lcm(periods.0, periods.1).and_then(|lcm| numtheory::lcm(lcm, periods.2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solution::{answers, Answer};
+    use std::fs;
+
+    fn read_moons(file: &str) -> Vec<Moon> {
+        Day12::parse(&fs::read_to_string(file).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn energy_example_1() {
+        let moons = read_moons("example1.txt");
+        let mut simulator = OrbitSimulator::new(&moons);
+        simulator.simulate(10);
+        let total_energy = simulator.calculate_total_energy();
+        assert_eq!(179, total_energy);
+    }
+
+    #[test]
+    fn energy_example_2() {
+        let moons = read_moons("example2.txt");
+        let mut simulator = OrbitSimulator::new(&moons);
+        simulator.simulate(100);
+        let total_energy = simulator.calculate_total_energy();
+        assert_eq!(1940, total_energy);
+    }
+
+    #[test]
+    fn period_example_1() {
+        let moons = read_moons("example1.txt");
+        let mut simulator = OrbitSimulator::new(&moons);
+        let period = simulator.find_periodicity();
+        assert_eq!(Some(2772), period);
+    }
+
+    #[test]
+    fn part_1() {
+        assert_eq!(
+            answers::expected(12, 1),
+            Day12::part1(&read_moons("input.txt")).to_answer()
+        );
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(
+            answers::expected(12, 2),
+            Day12::part2(&read_moons("input.txt")).to_answer()
+        );
+    }
+}
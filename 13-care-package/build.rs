@@ -0,0 +1,23 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// Compiles the puzzle input to Rust, so that the tests can compare the compiled game with the
+// interpreter.
+fn main() {
+    println!("cargo:rerun-if-changed=input.txt");
+
+    let program = intcode::load_program("input.txt").expect("Failed to read input.txt");
+
+    let mut free_play = program.clone();
+    free_play[0] = 2; // Insert two quarters
+
+    let mut code = intcode::transpiler::transpile(&program, "care_package");
+    code.push_str(&intcode::transpiler::transpile(
+        &free_play,
+        "care_package_free_play",
+    ));
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("care_package.rs"), code).unwrap();
+}
@@ -0,0 +1,489 @@
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use animation::{Animation, Color, Frame};
+use intcode::{Computer, RunState};
+use solution::Solution;
+
+const WIDTH: usize = 43;
+const HEIGHT: usize = 21;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<i64>;
+    type Answer1 = Result<usize, ScreenError>;
+    type Answer2 = Result<usize, ScreenError>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        intcode::parse_program(input).map_err(|e| e.to_string())
+    }
+
+    // Number of blocks on the screen
+    fn part1(game: &Self::Input) -> Result<usize, ScreenError> {
+        play(game, false).map(|arcade| arcade.block_count)
+    }
+
+    // Final score after breaking all blocks
+    fn part2(game: &Self::Input) -> Result<usize, ScreenError> {
+        play(game, true).map(|arcade| arcade.score)
+    }
+
+    fn statistics(game: &Self::Input) -> Option<String> {
+        Some(format!(
+            "{}\n\n{}",
+            play(game, false).ok()?.game.report(),
+            play(game, true).ok()?.game.report()
+        ))
+    }
+
+    // The game played until all blocks are broken
+    fn animate(game: &Self::Input, animation: &mut Animation) -> Result<(), String> {
+        let mut game = game.to_vec();
+        game[0] = 2; // Insert two quarters
+        ArcadeCabinet::new(&game)
+            .play(Some(animation))
+            .map_err(|e| e.to_string())
+    }
+}
+
+fn play(game: &[i64], quarters: bool) -> Result<ArcadeCabinet, ScreenError> {
+    let mut game = game.to_vec();
+    if quarters {
+        game[0] = 2; // Insert two quarters
+    }
+    let mut arcade = ArcadeCabinet::new(&game);
+    arcade.play(None)?;
+    Ok(arcade)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum TileType {
+    Empty,
+    Wall,
+    Block,
+    Paddle,
+    Ball,
+}
+
+// The error is the value that isn't a tile type.
+impl TryFrom<i64> for TileType {
+    type Error = i64;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(TileType::Empty),
+            1 => Ok(TileType::Wall),
+            2 => Ok(TileType::Block),
+            3 => Ok(TileType::Paddle),
+            4 => Ok(TileType::Ball),
+            value => Err(value),
+        }
+    }
+}
+
+// Output of the game that can't be drawn on the screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScreenError {
+    InvalidTile { x: i64, y: i64, value: i64 },
+    OutOfBounds { x: i64, y: i64 },
+}
+
+impl fmt::Display for ScreenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScreenError::InvalidTile { x, y, value } => {
+                write!(f, "Invalid tile type {} at x={}, y={}", value, x, y)
+            }
+            ScreenError::OutOfBounds { x, y } => {
+                write!(f, "Tile at x={}, y={} is outside of the screen", x, y)
+            }
+        }
+    }
+}
+
+impl Error for ScreenError {}
+
+struct ArcadeCabinet {
+    game: Computer<VecDeque<i64>, Vec<i64>>,
+    screen_width: usize,
+    screen_height: usize,
+    screen: Vec<TileType>,
+    ball_position: (usize, usize),
+    paddle_position: (usize, usize),
+    block_count: usize,
+    score: usize,
+}
+
+impl ArcadeCabinet {
+    fn new(game: &[i64]) -> Self {
+        Self {
+            game: Computer::new(0, game, VecDeque::new(), Vec::new()),
+            screen_width: WIDTH,
+            screen_height: HEIGHT,
+            screen: vec![TileType::Empty; WIDTH * HEIGHT],
+            ball_position: (0, 0),
+            paddle_position: (0, 0),
+            block_count: 0,
+            score: 0,
+        }
+    }
+
+    fn play(&mut self, mut animation: Option<&mut Animation>) -> Result<(), ScreenError> {
+        let mut run_state = self.game.run_program();
+        loop {
+            match run_state {
+                RunState::NotYetStarted => unreachable!(),
+                RunState::NeedInput => {
+                    // Update state
+                    self.update_state()?;
+
+                    // Decide on input
+                    let input = match self.ball_position.0.cmp(&self.paddle_position.0) {
+                        Ordering::Greater => 1,
+                        Ordering::Less => -1,
+                        Ordering::Equal => 0,
+                    };
+
+                    self.game.get_input().push_back(input);
+
+                    // Draw screen
+                    if let Some(animation) = &mut animation {
+                        animation.show(self.frame());
+                    }
+                }
+                RunState::Breakpoint { .. }
+                | RunState::OutputFull { .. }
+                | RunState::InputFailed { .. }
+                | RunState::OutputFailed { .. }
+                | RunState::InstructionLimit { .. } => unreachable!(),
+                RunState::Stopped(_) => {
+                    self.update_state()?;
+                    if let Some(animation) = &mut animation {
+                        animation.show(self.frame());
+                    }
+                    return Ok(());
+                }
+            }
+
+            self.game.get_output().clear();
+            run_state = self.game.resume();
+        }
+    }
+
+    fn update_state(&mut self) -> Result<(), ScreenError> {
+        let output = self.game.get_output();
+        self.block_count = 0;
+        for pixel in output.chunks_exact(3) {
+            let (x, y) = (pixel[0], pixel[1]);
+
+            // Update score
+            if x == -1 && y == 0 {
+                self.score = pixel[2] as usize;
+                continue;
+            }
+
+            let tile = TileType::try_from(pixel[2]).map_err(|value| ScreenError::InvalidTile {
+                x,
+                y,
+                value,
+            })?;
+            if x < 0 || y < 0 || x as usize >= self.screen_width || y as usize >= self.screen_height
+            {
+                return Err(ScreenError::OutOfBounds { x, y });
+            }
+            let position = (x as usize, y as usize);
+
+            match tile {
+                // Count blocks
+                TileType::Block => self.block_count += 1,
+                // Update paddle position
+                TileType::Paddle => self.paddle_position = position,
+                // Update ball position
+                TileType::Ball => self.ball_position = position,
+                TileType::Empty | TileType::Wall => {}
+            }
+
+            // Update tiles
+            self.screen[position.1 * self.screen_width + position.0] = tile;
+        }
+        Ok(())
+    }
+
+    fn frame(&self) -> Frame {
+        let mut frame = Frame::new(&format!(
+            "+{:->42}\n|SCORE:{:>35}|\n+{:->42}",
+            "+", self.score, "+"
+        ));
+
+        let mut screen = String::with_capacity((self.screen_width + 1) * self.screen_height);
+        for row in self.screen.chunks(self.screen_width) {
+            for tile in row {
+                screen.push(match tile {
+                    TileType::Empty => ' ',
+                    TileType::Wall => '#',
+                    TileType::Block => '=',
+                    TileType::Paddle => '-',
+                    TileType::Ball => 'o',
+                });
+            }
+            screen.push('\n');
+        }
+        frame.extend(Frame::colored(&screen, |c| match c {
+            '#' => Some(Color::Gray),
+            '=' => Some(Color::Blue),
+            '-' => Some(Color::Cyan),
+            'o' => Some(Color::Yellow),
+            _ => None,
+        }));
+        frame
+    }
+}
+
+#[cfg(test)]
+include!(concat!(env!("OUT_DIR"), "/care_package.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use intcode::{Access, RegionKind};
+    use solution::{answers, Answer};
+    use std::fs;
+
+    fn input() -> Vec<i64> {
+        Day13::parse(&fs::read_to_string("input.txt").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn part_1() {
+        assert_eq!(answers::expected(13, 1), Day13::part1(&input()).to_answer());
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(answers::expected(13, 2), Day13::part2(&input()).to_answer());
+    }
+
+    // Draws a single tile and stops.
+    fn draw(x: i64, y: i64, tile: i64) -> Result<ArcadeCabinet, ScreenError> {
+        play(&[104, x, 104, y, 104, tile, 99], false)
+    }
+
+    #[test]
+    fn invalid_output() {
+        assert_eq!(Ok(1), draw(3, 4, 2).map(|arcade| arcade.block_count));
+        assert_eq!(
+            Err(ScreenError::InvalidTile {
+                x: 3,
+                y: 4,
+                value: 7
+            }),
+            draw(3, 4, 7).map(|_| ())
+        );
+        assert_eq!(
+            Err(ScreenError::OutOfBounds { x: 43, y: 0 }),
+            draw(43, 0, 1).map(|_| ())
+        );
+        assert_eq!(
+            "Error: Invalid tile type 7 at x=3, y=4",
+            Day13::part1(&vec![104, 3, 104, 4, 104, 7, 99]).to_answer()
+        );
+    }
+
+    #[test]
+    fn animation() {
+        let mut animation = Animation::headless();
+        Day13::animate(&input(), &mut animation).unwrap();
+
+        // The last frame shows the final score and no blocks
+        let last = animation.frames().last().unwrap().to_string();
+        assert!(last.contains("|SCORE:                              13581|"));
+        assert!(!last.contains('='));
+        assert_eq!(24, animation.frames()[0].height());
+    }
+
+    #[test]
+    fn coverage() {
+        let mut game = input();
+
+        let mut arcade = ArcadeCabinet::new(&game);
+        arcade.game.enable_coverage();
+        arcade.play(None).unwrap();
+        let demo_mode = arcade.game.take_coverage().unwrap();
+
+        game[0] = 2; // Insert two quarters
+        let mut arcade = ArcadeCabinet::new(&game);
+        arcade.game.enable_coverage();
+        arcade.play(None).unwrap();
+        let mut coverage = arcade.game.take_coverage().unwrap();
+
+        // Only playing the game reaches the code that reads the joystick and updates the score
+        assert!(demo_mode.executed_addresses().count() < coverage.executed_addresses().count());
+
+        coverage.merge(&demo_mode);
+        assert!(demo_mode
+            .executed_addresses()
+            .all(|address| coverage.is_executed(address)));
+    }
+
+    #[test]
+    fn self_modification() {
+        let mut game = input();
+        game[0] = 2; // Insert two quarters
+        let mut arcade = ArcadeCabinet::new(&game);
+        arcade.game.enable_self_modification_analysis();
+        arcade.play(None).unwrap();
+
+        // The game patches operands (to index into the screen), but never its instructions
+        let analysis = arcade.game.self_modification().unwrap();
+        assert!(!analysis.writes().is_empty());
+        assert!(!analysis.modifies_opcodes());
+    }
+
+    #[test]
+    fn paddle_position_written_after_joystick_input() {
+        const PADDLE_POSITION: usize = 392;
+
+        let mut game = input();
+        game[0] = 2; // Insert two quarters
+        let mut computer = Computer::new(0, &game, VecDeque::new(), Vec::new());
+        computer.add_watchpoint(PADDLE_POSITION, Access::Write);
+
+        // The game only updates the paddle position if the joystick was tilted
+        let mut joystick = None;
+        let mut written = false;
+        loop {
+            match computer.resume() {
+                RunState::Breakpoint { .. } => written = true,
+                RunState::NeedInput => {
+                    if let Some(joystick) = joystick {
+                        assert_eq!(joystick != 0, written);
+                    }
+                    written = false;
+
+                    let paddle = computer.peek(PADDLE_POSITION);
+                    let ball = computer
+                        .get_output()
+                        .chunks_exact(3)
+                        .rev()
+                        .find(|pixel| pixel[2] == 4)
+                        .map_or(paddle, |pixel| pixel[0]);
+                    joystick = Some((ball - paddle).signum());
+                    computer.get_output().clear();
+                    computer.get_input().push_back((ball - paddle).signum());
+                }
+                RunState::Stopped(_) => break,
+                RunState::NotYetStarted
+                | RunState::OutputFull { .. }
+                | RunState::InputFailed { .. }
+                | RunState::OutputFailed { .. }
+                | RunState::InstructionLimit { .. } => unreachable!(),
+            }
+        }
+        assert!(joystick.is_some());
+    }
+
+    #[test]
+    fn compiled_game() {
+        let game = input();
+        let mut interpreted = Computer::new(0, &game, VecDeque::new(), Vec::new());
+        let mut compiled = care_package::new(VecDeque::new(), Vec::new());
+
+        assert_eq!(interpreted.run_program(), compiled.run_program());
+        assert_eq!(interpreted.get_output(), compiled.get_output());
+        assert!(compiled.is_compiled());
+    }
+
+    #[test]
+    fn compiled_game_with_quarters() {
+        let mut game = input();
+        game[0] = 2; // Insert two quarters
+        let mut interpreted = Computer::new(0, &game, VecDeque::new(), Vec::new());
+        let mut compiled = care_package_free_play::new(VecDeque::new(), Vec::new());
+
+        let mut ball = 0;
+        let mut paddle = 0;
+        loop {
+            let run_state = interpreted.resume();
+            assert_eq!(run_state, compiled.resume());
+            assert_eq!(interpreted.get_output(), compiled.get_output());
+
+            if let RunState::Stopped(_) = run_state {
+                break;
+            }
+
+            for pixel in interpreted.get_output().chunks_exact(3) {
+                match pixel[2] {
+                    3 => paddle = pixel[0],
+                    4 => ball = pixel[0],
+                    _ => {}
+                }
+            }
+            let input = (ball - paddle).signum();
+            interpreted.get_input().push_back(input);
+            compiled.get_input().push_back(input);
+            interpreted.get_output().clear();
+            compiled.get_output().clear();
+        }
+        assert!(compiled.is_compiled());
+    }
+
+    #[test]
+    fn decompiled_functions() {
+        let game = input();
+
+        // (entry, frame size, parameters) of main and the functions it calls
+        let functions: Vec<_> = intcode::decompiler::find_functions(&game)
+            .iter()
+            .map(|function| (function.entry, function.frame_size, function.parameters))
+            .collect();
+        assert_eq!(
+            vec![
+                (0, 0, 0),
+                (393, 3, 2),
+                (456, 8, 4),
+                (549, 4, 3),
+                (578, 3, 2),
+                (601, 3, 2)
+            ],
+            functions
+        );
+
+        // When the ball hits a block, the block is cleared first
+        let code = intcode::decompiler::decompile(&game);
+        assert!(code.contains("fn f393(arg0, arg1) {\n    call f549(arg0, arg1, 0)\n"));
+    }
+
+    #[test]
+    fn region_map() {
+        let mut game = input();
+        game[0] = 2; // Insert two quarters
+        let mut map = intcode::RegionMap::new(&game);
+
+        // The game's variables sit between main and the functions, the screen and score
+        // tables behind the code are only indexed with computed addresses.
+        let regions: Vec<_> = map
+            .regions()
+            .iter()
+            .map(|region| (region.range.clone(), region.kind))
+            .collect();
+        let expected = vec![
+            (0..379, RegionKind::Code),
+            (379..393, RegionKind::Data),
+            (393..639, RegionKind::Code),
+            (639..2445, RegionKind::Unknown),
+            (2445..2446, RegionKind::Data),
+        ];
+        assert_eq!(expected, regions);
+
+        // Playing the game doesn't execute any code that wasn't found statically
+        let mut arcade = ArcadeCabinet::new(&game);
+        arcade.game.enable_coverage();
+        arcade.play(None).unwrap();
+        map.refine(arcade.game.coverage().unwrap());
+        assert_eq!(expected.len(), map.regions().len());
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use animation::{Animation, Color, Frame};
+use grid::{Direction, Point, SparseGrid};
+use intcode::{Computer, RunState};
+use solution::Solution;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<i64>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        intcode::parse_program(input).map_err(|e| e.to_string())
+    }
+
+    // Distance between starting position and oxygen system
+    fn part1(program: &Self::Input) -> usize {
+        explore(program).terrain.distance(Point::ORIGIN)
+    }
+
+    // Minutes to fill the area with oxygen
+    fn part2(program: &Self::Input) -> usize {
+        explore(program).terrain.max_distance()
+    }
+
+    fn statistics(program: &Self::Input) -> Option<String> {
+        Some(explore(program).computer.report().to_string())
+    }
+
+    // The droid mapping the area
+    fn animate(program: &Self::Input, animation: &mut Animation) -> Result<(), String> {
+        RepairDroid::new(program, Some(animation)).map_terrain();
+        Ok(())
+    }
+}
+
+// Maps the area with the repair droid and calculates the distances from the oxygen system.
+fn explore(program: &[i64]) -> RepairDroid<'static> {
+    let mut repair_droid = RepairDroid::new(program, None);
+    repair_droid.map_terrain();
+    repair_droid
+        .terrain
+        .calculate_distances_from_oxygen_system();
+    repair_droid
+}
+
+struct RepairDroid<'a> {
+    terrain: Terrain,
+    computer: Computer<Option<i64>, Option<i64>>,
+    animation: Option<&'a mut Animation>,
+}
+
+impl<'a> RepairDroid<'a> {
+    fn new(program: &[i64], animation: Option<&'a mut Animation>) -> Self {
+        Self {
+            terrain: Terrain::new(),
+            computer: Computer::new(0, program, None, None),
+            animation,
+        }
+    }
+
+    fn map_terrain(&mut self) {
+        let run_state = self.computer.run_program();
+        if run_state != RunState::NeedInput {
+            panic!("Run state was {:?}", run_state);
+        }
+
+        self.terrain.tiles.insert(Point::ORIGIN, Tile::Floor);
+        self.explore(Point::ORIGIN);
+    }
+
+    fn explore(&mut self, droid_position: Point) {
+        self.explore_direction(Direction::Up, droid_position);
+        self.explore_direction(Direction::Down, droid_position);
+        self.explore_direction(Direction::Left, droid_position);
+        self.explore_direction(Direction::Right, droid_position);
+    }
+
+    fn explore_direction(&mut self, direction: Direction, droid_position: Point) {
+        // Check that we haven't mapped that direction already
+        if self.terrain.tiles.contains(droid_position + direction) {
+            return;
+        }
+
+        // Command direction to explore
+        *self.computer.get_input() = Some(movement_command(direction));
+
+        // Explore direction
+        let run_state = self.computer.resume();
+        if run_state != RunState::NeedInput {
+            panic!("Run state was {:?}", run_state);
+        }
+
+        // Check status
+        let mut obstacle = false;
+        let status = self
+            .computer
+            .get_output()
+            .take()
+            .expect("Expected status report!");
+        match status {
+            0 => {
+                self.terrain
+                    .tiles
+                    .insert(droid_position + direction, Tile::Wall);
+                obstacle = true;
+            }
+            1 => {
+                self.terrain
+                    .tiles
+                    .insert(droid_position + direction, Tile::Floor);
+            }
+            2 => {
+                self.terrain
+                    .tiles
+                    .insert(droid_position + direction, Tile::OxygenSystem);
+                self.terrain.oxygen_system = Some(droid_position + direction);
+            }
+            _ => panic!("Unexpected status: {}", status),
+        }
+
+        // Continue exploring and then backtrack
+        if !obstacle {
+            self.show(droid_position + direction);
+            self.explore(droid_position + direction);
+
+            // Backtrack
+            *self.computer.get_input() = Some(movement_command(direction.reverse()));
+
+            let run_state = self.computer.resume();
+            if run_state != RunState::NeedInput {
+                panic!("Run state was {:?}", run_state);
+            }
+
+            // Consume status
+            self.computer.get_output().take();
+        }
+
+        self.show(droid_position);
+    }
+
+    fn show(&mut self, droid_position: Point) {
+        if let Some(animation) = &mut self.animation {
+            animation.show(self.terrain.frame(droid_position));
+        }
+    }
+}
+
+// Movement command understood by the droid
+fn movement_command(direction: Direction) -> i64 {
+    match direction {
+        Direction::Up => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+        Direction::Right => 4,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Tile {
+    Wall,
+    Floor,
+    OxygenSystem,
+}
+
+struct Terrain {
+    tiles: SparseGrid<Tile>,
+    oxygen_system: Option<Point>,
+    distances: HashMap<Point, usize>,
+}
+
+impl Terrain {
+    fn new() -> Self {
+        Self {
+            tiles: SparseGrid::new(),
+            oxygen_system: None,
+            distances: HashMap::new(),
+        }
+    }
+
+    fn calculate_distances_from_oxygen_system(&mut self) {
+        let oxygen_system = self
+            .oxygen_system
+            .expect("Location of oxygen system unknown.");
+        self.distances = self
+            .tiles
+            .distances(oxygen_system, |&tile| tile != Tile::Wall);
+    }
+
+    fn distance(&self, position: Point) -> usize {
+        self.distances[&position]
+    }
+
+    fn max_distance(&self) -> usize {
+        self.distances.values().copied().max().unwrap_or(0)
+    }
+
+    fn frame(&self, droid_position: Point) -> Frame {
+        let mut frame = Frame::new(&format!("Droid at {}", droid_position));
+        let rendered = self.tiles.render(|point, tile| {
+            if point == droid_position {
+                return 'D';
+            }
+            match tile {
+                Some(Tile::Wall) => '#',
+                Some(Tile::Floor) => '.',
+                Some(Tile::OxygenSystem) => 'O',
+                None => ' ',
+            }
+        });
+        frame.extend(Frame::colored(&rendered, |c| match c {
+            '#' => Some(Color::Gray),
+            'D' => Some(Color::Green),
+            'O' => Some(Color::Cyan),
+            _ => None,
+        }));
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solution::{answers, Answer};
+    use std::fs;
+
+    fn input() -> Vec<i64> {
+        Day15::parse(&fs::read_to_string("input.txt").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn part_1() {
+        assert_eq!(answers::expected(15, 1), Day15::part1(&input()).to_answer());
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(answers::expected(15, 2), Day15::part2(&input()).to_answer());
+    }
+
+    #[test]
+    fn animation() {
+        let mut animation = Animation::headless();
+        Day15::animate(&input(), &mut animation).unwrap();
+
+        // The droid returns to where it started after mapping the whole area
+        let last = animation.frames().last().unwrap().to_string();
+        assert!(last.starts_with("Droid at (0,0)\n"));
+        assert!(last.contains('O'));
+    }
+}
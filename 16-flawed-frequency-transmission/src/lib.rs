@@ -0,0 +1,271 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use benchmark::Bencher;
+use solution::Solution;
+
+pub struct Day16;
+
+// Both parts read a message of 8 digits, part 2 at the offset given by the first 7 digits.
+const MESSAGE_LENGTH: usize = 8;
+const OFFSET_LENGTH: usize = 7;
+
+impl Solution for Day16 {
+    type Input = Signal;
+    type Answer1 = String;
+    type Answer2 = Result<String, String>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        input.parse().map_err(|e: ParseSignalError| e.to_string())
+    }
+
+    fn part1(signal: &Self::Input) -> String {
+        let numbers = run_n_phases(signal.digits.clone(), 100);
+        digits(&numbers[..MESSAGE_LENGTH])
+    }
+
+    fn part2(signal: &Self::Input) -> Result<String, String> {
+        let offset = signal.digits[..OFFSET_LENGTH]
+            .iter()
+            .fold(0, |offset, &digit| offset * 10 + digit as usize);
+        let real_input = signal.digits.repeat(10000);
+        if offset + MESSAGE_LENGTH > real_input.len() {
+            return Err(format!(
+                "Message offset {} is beyond the end of the real signal ({} digits)",
+                offset,
+                real_input.len()
+            ));
+        }
+
+        let numbers = run_n_phases(real_input, 100);
+        Ok(digits(&numbers[offset..offset + MESSAGE_LENGTH]))
+    }
+
+    // A single phase, of which both parts calculate 100. Includes copying the signal.
+    fn benchmarks(signal: &Self::Input, bencher: &mut Bencher) {
+        bencher.bench("phase", || calculate_next_phase(signal.digits.clone()));
+        if bencher.is_selected("phase_repeated") {
+            let real_input = signal.digits.repeat(10000);
+            bencher.bench("phase_repeated", || {
+                calculate_next_phase(real_input.clone())
+            });
+        }
+    }
+}
+
+pub struct Signal {
+    digits: Vec<u8>,
+}
+
+// The signal is a single line of at least 8 digits.
+impl FromStr for Signal {
+    type Err = ParseSignalError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut digits = Vec::with_capacity(input.len());
+        for (i, line) in input.trim_end().lines().enumerate() {
+            if i > 0 {
+                return Err(ParseSignalError::ExtraLine(i + 1));
+            }
+            for (column, character) in line.chars().enumerate() {
+                match character.to_digit(10) {
+                    Some(digit) => digits.push(digit as u8),
+                    None => {
+                        return Err(ParseSignalError::InvalidDigit {
+                            line: i + 1,
+                            column: column + 1,
+                            character,
+                        })
+                    }
+                }
+            }
+        }
+
+        if digits.is_empty() {
+            return Err(ParseSignalError::Empty);
+        }
+        if digits.len() < MESSAGE_LENGTH {
+            return Err(ParseSignalError::TooShort {
+                line: 1,
+                column: digits.len() + 1,
+            });
+        }
+        Ok(Self { digits })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseSignalError {
+    Empty,
+    // A line (1-based) after the signal
+    ExtraLine(usize),
+    // Line and column are 1-based
+    InvalidDigit {
+        line: usize,
+        column: usize,
+        character: char,
+    },
+    // The signal ends before the 8th digit, at the given line and column (1-based)
+    TooShort {
+        line: usize,
+        column: usize,
+    },
+}
+
+impl fmt::Display for ParseSignalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseSignalError::Empty => write!(f, "Empty signal"),
+            ParseSignalError::ExtraLine(line) => {
+                write!(f, "Unexpected line {}, the signal is a single line", line)
+            }
+            ParseSignalError::InvalidDigit {
+                line,
+                column,
+                character,
+            } => write!(
+                f,
+                "Invalid digit {:?} at line {}, column {}",
+                character, line, column
+            ),
+            ParseSignalError::TooShort { line, column } => write!(
+                f,
+                "Signal ends at line {}, column {}, expected at least {} digits",
+                line, column, MESSAGE_LENGTH
+            ),
+        }
+    }
+}
+
+impl Error for ParseSignalError {}
+
+fn digits(numbers: &[u8]) -> String {
+    numbers.iter().map(|number| number.to_string()).collect()
+}
+
+fn run_n_phases(input_list: Vec<u8>, n: usize) -> Vec<u8> {
+    let mut numbers = input_list;
+    for _ in 0..n {
+        numbers = calculate_next_phase(numbers);
+    }
+    numbers
+}
+
+fn calculate_next_phase(input_list: Vec<u8>) -> Vec<u8> {
+    let mut output_list = Vec::with_capacity(input_list.len());
+    let mut partial_sums: Vec<i32> = Vec::with_capacity(input_list.len() + 1);
+
+    // Calculate partial sums of input list
+    partial_sums.push(0);
+    for (i, &number) in input_list.iter().enumerate() {
+        let previous = partial_sums[i];
+        partial_sums.push(previous + number as i32);
+    }
+
+    // Calculate next phase
+    for i in 0..input_list.len() {
+        let mut sign = 1i32;
+        let mut sum = 0;
+        for j in (i..input_list.len()).step_by((i + 1) * 2) {
+            sum += sign
+                * (partial_sums[usize::min(j + i + 1, partial_sums.len() - 1)] - partial_sums[j]);
+            sign *= -1;
+        }
+        output_list.push((sum % 10).unsigned_abs() as u8);
+    }
+
+    output_list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solution::{answers, Answer};
+    use std::fs;
+
+    #[test]
+    fn small_example() {
+        let numbers = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        let numbers = calculate_next_phase(numbers);
+        assert_eq!(vec![4, 8, 2, 2, 6, 1, 5, 8], numbers);
+
+        let numbers = calculate_next_phase(numbers);
+        assert_eq!(vec![3, 4, 0, 4, 0, 4, 3, 8], numbers);
+
+        let numbers = calculate_next_phase(numbers);
+        assert_eq!(vec![0, 3, 4, 1, 5, 5, 1, 8], numbers);
+
+        let numbers = calculate_next_phase(numbers);
+        assert_eq!(vec![0, 1, 0, 2, 9, 4, 9, 8], numbers);
+    }
+
+    #[test]
+    fn example_1() {
+        let signal: Signal = "80871224585914546619083218645595".parse().unwrap();
+
+        let numbers = run_n_phases(signal.digits, 100);
+        assert_eq!(&[2, 4, 1, 7, 6, 1, 7, 6], &numbers[..8]);
+    }
+
+    #[test]
+    fn example_2() {
+        let signal: Signal = "19617804207202209144916044189917".parse().unwrap();
+
+        let numbers = run_n_phases(signal.digits, 100);
+        assert_eq!(&[7, 3, 7, 4, 5, 4, 1, 8], &numbers[..8]);
+    }
+
+    #[test]
+    fn example_3() {
+        let signal: Signal = "69317163492948606335995924319873".parse().unwrap();
+
+        let numbers = run_n_phases(signal.digits, 100);
+        assert_eq!(&[5, 2, 4, 3, 2, 1, 3, 3], &numbers[..8]);
+    }
+
+    #[test]
+    fn invalid_signals() {
+        assert_eq!(
+            Ok(vec![1, 2, 3, 4, 5, 6, 7, 8]),
+            "12345678\n".parse().map(|s: Signal| s.digits)
+        );
+        assert_eq!(
+            Err(ParseSignalError::TooShort { line: 1, column: 4 }),
+            "123\n".parse::<Signal>().map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseSignalError::InvalidDigit {
+                line: 1,
+                column: 3,
+                character: 'x'
+            }),
+            "12x4".parse::<Signal>().map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseSignalError::ExtraLine(2)),
+            "12345678\n5678".parse::<Signal>().map(|_| ())
+        );
+        assert_eq!(
+            Err(String::from("Empty signal")),
+            Day16::parse(" \n").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn message_offset_beyond_signal() {
+        let signal = "12345678".parse().unwrap();
+        assert_eq!(
+            Err(String::from(
+                "Message offset 1234567 is beyond the end of the real signal (80000 digits)"
+            )),
+            Day16::part2(&signal)
+        );
+    }
+
+    #[test]
+    fn part_1() {
+        let input = Day16::parse(&fs::read_to_string("input.txt").unwrap()).unwrap();
+        assert_eq!(answers::expected(16, 1), Day16::part1(&input).to_answer());
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::VecDeque;
+
+use grid::{Direction, Grid, Point};
+use intcode::{Computer, RunState};
+use solution::Solution;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Vec<i64>;
+    type Answer1 = usize;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        intcode::parse_program(input).map_err(|e| e.to_string())
+    }
+
+    fn part1(program: &Self::Input) -> usize {
+        let mut robot = VacuumRobot::new(program);
+        robot.dry_run();
+        robot.sum_of_alignment_parameters()
+    }
+
+    // Dust collected by the robot after visiting every part of the scaffold
+    fn part2(program: &Self::Input) -> i64 {
+        let mut robot = VacuumRobot::new(program);
+        robot.reset_program(&wake_up(program));
+        robot.run()
+    }
+
+    // Camera view, the path along the scaffold the movement routine was crafted from, and the
+    // statistics of both runs.
+    fn statistics(program: &Self::Input) -> Option<String> {
+        let mut robot = VacuumRobot::new(program);
+        robot.dry_run();
+        let path: Vec<String> = robot.find_path().iter().map(ToString::to_string).collect();
+        let mut statistics = format!(
+            "{}\nPath: {}\n\n{}\n\n",
+            robot.render_scaffolding(),
+            path.join(","),
+            robot.computer.report()
+        );
+
+        robot.reset_program(&wake_up(program));
+        robot.run();
+        statistics.push_str(&robot.computer.report().to_string());
+        Some(statistics)
+    }
+}
+
+fn wake_up(program: &[i64]) -> Vec<i64> {
+    let mut program = program.to_vec();
+    program[0] = 2;
+    program
+}
+
+/* Movement routine (hand-crafted):
+    A,B,A,B,C,C,B,A,B,C
+
+    A: L,4,R,8,L,6,L,10
+    B: L,6,R,8,R,10,L,6,L,6
+    C: L,4,L,4,L,10
+*/
+
+struct VacuumRobot {
+    computer: Computer<VecDeque<i64>, VecDeque<i64>>,
+    scaffolding: Grid<Tile>,
+    intersections: Vec<Point>,
+    position: Point,
+    direction: Direction,
+}
+
+impl VacuumRobot {
+    fn new(program: &[i64]) -> Self {
+        Self {
+            computer: Computer::new(0, program, VecDeque::new(), VecDeque::new()),
+            scaffolding: Grid::new(0, 0, Tile::OpenSpace),
+            intersections: Vec::new(),
+            position: Point::new(-1, -1),
+            direction: Direction::Up,
+        }
+    }
+
+    fn reset_program(&mut self, program: &[i64]) {
+        self.computer = Computer::new(0, program, VecDeque::new(), VecDeque::new());
+    }
+
+    // Runs the movement routine and returns the amount of dust collected.
+    fn run(&mut self) -> i64 {
+        let input = self.computer.get_input();
+        let routine =
+            "A,B,A,B,C,C,B,A,B,C\nL,4,R,8,L,6,L,10\nL,6,R,8,R,10,L,6,L,6\nL,4,L,4,L,10\nn\n";
+        for c in routine.chars() {
+            input.push_back(c as i64);
+        }
+
+        match self.computer.run_program() {
+            RunState::NotYetStarted
+            | RunState::Breakpoint { .. }
+            | RunState::OutputFull { .. }
+            | RunState::InputFailed { .. }
+            | RunState::OutputFailed { .. }
+            | RunState::InstructionLimit { .. } => unreachable!(),
+            RunState::NeedInput => panic!("Movement routine is incomplete"),
+            // The last output is the amount of dust
+            RunState::Stopped(dust) => dust,
+        }
+    }
+
+    fn dry_run(&mut self) {
+        let run_state = self.computer.run_program();
+
+        loop {
+            match run_state {
+                RunState::NotYetStarted
+                | RunState::Breakpoint { .. }
+                | RunState::OutputFull { .. }
+                | RunState::InputFailed { .. }
+                | RunState::OutputFailed { .. }
+                | RunState::InstructionLimit { .. } => unreachable!(),
+                RunState::NeedInput => println!("NEED INPUT"),
+                RunState::Stopped(_) => break,
+            }
+        }
+
+        let camera: String = self
+            .computer
+            .get_output()
+            .iter()
+            .map(|&output| {
+                assert!((0..256).contains(&output));
+                output as u8 as char
+            })
+            .collect();
+        self.scaffolding = Grid::parse(&camera, |c| match c {
+            '.' => Some(Tile::OpenSpace),
+            '#' => Some(Tile::Scaffold),
+            '^' => Some(Tile::Robot(Direction::Up)),
+            '<' => Some(Tile::Robot(Direction::Left)),
+            '>' => Some(Tile::Robot(Direction::Right)),
+            'v' => Some(Tile::Robot(Direction::Down)),
+            _ => None,
+        })
+        .unwrap_or_else(|e| panic!("Unexpected output: {}", e));
+
+        self.position = self
+            .scaffolding
+            .find(|tile| matches!(tile, Tile::Robot(_)))
+            .expect("Robot not found");
+        if let Tile::Robot(direction) = self.scaffolding[self.position] {
+            self.direction = direction;
+        }
+
+        self.find_intersections();
+    }
+
+    fn find_intersections(&mut self) {
+        self.intersections = self
+            .scaffolding
+            .points()
+            .filter(|&point| {
+                self.is_scaffold(point)
+                    && point
+                        .neighbors4()
+                        .iter()
+                        .all(|&neighbor| self.is_scaffold(neighbor))
+            })
+            .collect();
+    }
+
+    fn is_scaffold(&self, position: Point) -> bool {
+        match self.scaffolding.get(position) {
+            None | Some(Tile::OpenSpace) => false,
+            Some(Tile::Scaffold) | Some(Tile::Robot(_)) => true,
+        }
+    }
+
+    fn sum_of_alignment_parameters(&self) -> usize {
+        self.intersections
+            .iter()
+            .map(|pos| (pos.x * pos.y) as usize)
+            .sum()
+    }
+
+    fn find_path(&self) -> Vec<PathSegment> {
+        let mut path = Vec::new();
+
+        let mut current_position = self.position;
+        let mut current_direction = self.direction;
+        let mut forward_length = 0;
+
+        loop {
+            if self.is_scaffold(current_position + current_direction) {
+                // Try to move forward
+                forward_length += 1;
+                current_position = current_position + current_direction;
+            } else if self.is_scaffold(current_position + current_direction.left()) {
+                // Try left
+                if forward_length > 0 {
+                    path.push(PathSegment::Forward(forward_length));
+                    forward_length = 0;
+                }
+                path.push(PathSegment::Left);
+                current_direction = current_direction.left();
+            } else if self.is_scaffold(current_position + current_direction.right()) {
+                // Try right
+                if forward_length > 0 {
+                    path.push(PathSegment::Forward(forward_length));
+                    forward_length = 0;
+                }
+                path.push(PathSegment::Right);
+                current_direction = current_direction.right();
+            } else {
+                if forward_length > 0 {
+                    path.push(PathSegment::Forward(forward_length));
+                }
+                break;
+            }
+        }
+
+        path
+    }
+
+    fn render_scaffolding(&self) -> String {
+        self.scaffolding.to_string()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Tile {
+    OpenSpace,
+    Scaffold,
+    Robot(Direction),
+}
+
+impl std::fmt::Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Tile::OpenSpace => write!(f, "."),
+            Tile::Scaffold => write!(f, "#"),
+            Tile::Robot(Direction::Up) => write!(f, "^"),
+            Tile::Robot(Direction::Left) => write!(f, "<"),
+            Tile::Robot(Direction::Right) => write!(f, ">"),
+            Tile::Robot(Direction::Down) => write!(f, "v"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum PathSegment {
+    Left,
+    Right,
+    Forward(usize),
+}
+
+impl std::fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            PathSegment::Left => write!(f, "L"),
+            PathSegment::Right => write!(f, "R"),
+            PathSegment::Forward(steps) => write!(f, "{}", steps),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solution::{answers, Answer};
+    use std::fs;
+
+    fn input() -> Vec<i64> {
+        Day17::parse(&fs::read_to_string("input.txt").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn part_1() {
+        assert_eq!(answers::expected(17, 1), Day17::part1(&input()).to_answer());
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(answers::expected(17, 2), Day17::part2(&input()).to_answer());
+    }
+}
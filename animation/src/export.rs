@@ -0,0 +1,363 @@
+// Export of frames to image files: the last frame as PNG, or all of them as animated GIF. Each
+// character is drawn as a square cell filled with its color, spaces are left empty.
+
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::{Color, Frame};
+
+pub type Rgb = [u8; 3];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    background: Rgb,
+    // Color of characters without a color
+    foreground: Rgb,
+    colors: Vec<(Color, Rgb)>,
+    // Colors of specific characters, which take precedence over the color of a cell
+    characters: Vec<(char, Rgb)>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            background: [0, 0, 0],
+            foreground: [204, 204, 204],
+            colors: vec![
+                (Color::Black, [0, 0, 0]),
+                (Color::Red, [205, 49, 49]),
+                (Color::Green, [13, 188, 121]),
+                (Color::Yellow, [229, 229, 16]),
+                (Color::Blue, [36, 114, 200]),
+                (Color::Magenta, [188, 63, 188]),
+                (Color::Cyan, [17, 168, 205]),
+                (Color::White, [229, 229, 229]),
+                (Color::Gray, [102, 102, 102]),
+            ],
+            characters: Vec::new(),
+        }
+    }
+}
+
+impl Palette {
+    pub fn background(mut self, rgb: Rgb) -> Self {
+        self.background = rgb;
+        self
+    }
+
+    pub fn foreground(mut self, rgb: Rgb) -> Self {
+        self.foreground = rgb;
+        self
+    }
+
+    pub fn color(mut self, color: Color, rgb: Rgb) -> Self {
+        self.colors.retain(|&(c, _)| c != color);
+        self.colors.push((color, rgb));
+        self
+    }
+
+    pub fn character(mut self, character: char, rgb: Rgb) -> Self {
+        self.characters.retain(|&(c, _)| c != character);
+        self.characters.push((character, rgb));
+        self
+    }
+
+    fn rgb(&self, character: char, color: Option<Color>) -> Rgb {
+        if let Some(&(_, rgb)) = self.characters.iter().find(|&&(c, _)| c == character) {
+            return rgb;
+        }
+        if character == ' ' {
+            return self.background;
+        }
+        color
+            .and_then(|color| self.colors.iter().find(|&&(c, _)| c == color))
+            .map_or(self.foreground, |&(_, rgb)| rgb)
+    }
+
+    // All colors of the palette, starting with the background
+    fn entries(&self) -> Vec<Rgb> {
+        let mut entries = vec![self.background, self.foreground];
+        entries.extend(self.colors.iter().map(|&(_, rgb)| rgb));
+        entries.extend(self.characters.iter().map(|&(_, rgb)| rgb));
+        entries
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    NoFrames,
+    // Images are limited to 65535 pixels in each direction
+    TooLarge { width: usize, height: usize },
+    UnknownFormat(String),
+    Io(io::Error),
+    Png(png::EncodingError),
+    Gif(gif::EncodingError),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::NoFrames => write!(f, "No frames to export"),
+            ExportError::TooLarge { width, height } => {
+                write!(f, "Image of {}x{} pixels is too large", width, height)
+            }
+            ExportError::UnknownFormat(path) => {
+                write!(f, "Unknown image format of {}, expected .png or .gif", path)
+            }
+            ExportError::Io(e) => write!(f, "{}", e),
+            ExportError::Png(e) => write!(f, "PNG: {}", e),
+            ExportError::Gif(e) => write!(f, "GIF: {}", e),
+        }
+    }
+}
+
+impl Error for ExportError {}
+
+impl From<io::Error> for ExportError {
+    fn from(e: io::Error) -> Self {
+        ExportError::Io(e)
+    }
+}
+
+impl From<png::EncodingError> for ExportError {
+    fn from(e: png::EncodingError) -> Self {
+        ExportError::Png(e)
+    }
+}
+
+impl From<gif::EncodingError> for ExportError {
+    fn from(e: gif::EncodingError) -> Self {
+        ExportError::Gif(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Export {
+    palette: Palette,
+    // Width and height of the square drawn for each character, in pixels
+    cell_size: usize,
+    // Time each frame of a GIF is shown
+    delay: Duration,
+}
+
+impl Default for Export {
+    fn default() -> Self {
+        Self {
+            palette: Palette::default(),
+            cell_size: 4,
+            delay: Duration::from_millis(50),
+        }
+    }
+}
+
+impl Export {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    pub fn cell_size(mut self, cell_size: usize) -> Self {
+        self.cell_size = cell_size.max(1);
+        self
+    }
+
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    // Writes the frames to a file whose format is given by the extension. A PNG only shows the
+    // last frame.
+    pub fn save(&self, frames: &[Frame], path: &Path) -> Result<(), ExportError> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        let gif = match extension.as_deref() {
+            Some("png") => false,
+            Some("gif") => true,
+            _ => return Err(ExportError::UnknownFormat(path.display().to_string())),
+        };
+        let last = frames.last().ok_or(ExportError::NoFrames)?;
+
+        let mut out = BufWriter::new(File::create(path)?);
+        if gif {
+            self.gif(frames, &mut out)?;
+        } else {
+            self.png(last, &mut out)?;
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    pub fn png<W: Write>(&self, frame: &Frame, out: W) -> Result<(), ExportError> {
+        let (width, height) = self.size(std::slice::from_ref(frame))?;
+        let entries = self.palette.entries();
+        let pixels: Vec<u8> = self
+            .pixels(frame, width, height)
+            .into_iter()
+            .flat_map(|index| entries[index as usize].iter().copied())
+            .collect();
+
+        let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+        encoder.set_color(png::ColorType::RGB);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&pixels)?;
+        Ok(())
+    }
+
+    // All frames are drawn at the top left corner of an image as large as the largest frame.
+    pub fn gif<W: Write>(&self, frames: &[Frame], out: W) -> Result<(), ExportError> {
+        let (width, height) = self.size(frames)?;
+        let palette: Vec<u8> = self.palette.entries().into_iter().flatten().collect();
+        let mut encoder = gif::Encoder::new(out, width, height, &palette)?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+
+        // The delay is given in hundredths of a second
+        let delay = (self.delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+        for frame in frames {
+            let pixels = self.pixels(frame, width, height);
+            let mut frame = gif::Frame::from_indexed_pixels(width, height, &pixels, None);
+            frame.delay = delay;
+            encoder.write_frame(&frame)?;
+        }
+        encoder.into_inner()?;
+        Ok(())
+    }
+
+    fn size(&self, frames: &[Frame]) -> Result<(u16, u16), ExportError> {
+        if frames.is_empty() {
+            return Err(ExportError::NoFrames);
+        }
+        let width = frames.iter().map(Frame::width).max().unwrap_or(0) * self.cell_size;
+        let height = frames.iter().map(Frame::height).max().unwrap_or(0) * self.cell_size;
+        if width > u16::MAX as usize || height > u16::MAX as usize {
+            return Err(ExportError::TooLarge { width, height });
+        }
+        Ok((width as u16, height as u16))
+    }
+
+    // Index into the palette entries of each pixel, row by row
+    fn pixels(&self, frame: &Frame, width: u16, height: u16) -> Vec<u8> {
+        let entries = self.palette.entries();
+        let (width, height) = (width as usize, height as usize);
+        let mut pixels = vec![0; width * height];
+        for (y, line) in frame.lines.iter().enumerate() {
+            for (x, cell) in line.iter().enumerate() {
+                let rgb = self.palette.rgb(cell.character, cell.color);
+                let index = entries.iter().position(|&entry| entry == rgb).unwrap_or(0) as u8;
+                for row in y * self.cell_size..(y + 1) * self.cell_size {
+                    let start = row * width + x * self.cell_size;
+                    pixels[start..start + self.cell_size].fill(index);
+                }
+            }
+        }
+        debug_assert_eq!(width * height, pixels.len());
+        pixels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    fn frames() -> Vec<Frame> {
+        let color = |c| match c {
+            '#' => Some(Color::Gray),
+            'o' => Some(Color::Yellow),
+            _ => None,
+        };
+        vec![
+            Frame::colored("#o\n .", color),
+            Frame::colored("# o\n.", color),
+        ]
+    }
+
+    fn temporary_file(name: &str) -> std::path::PathBuf {
+        env::temp_dir().join(format!("animation-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn png() {
+        let path = temporary_file("frame.png");
+        let palette = Palette::default().character('.', [0, 0, 255]);
+        Export::new()
+            .palette(palette)
+            .cell_size(2)
+            .save(&frames()[..1], &path)
+            .unwrap();
+
+        let decoder = png::Decoder::new(File::open(&path).unwrap());
+        let (info, mut reader) = decoder.read_info().unwrap();
+        let mut pixels = vec![0; info.buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!((4, 4), (info.width, info.height));
+        assert_eq!(png::ColorType::RGB, info.color_type);
+        let pixel = |x: usize, y: usize| &pixels[(y * 4 + x) * 3..(y * 4 + x + 1) * 3];
+        assert_eq!(&[102, 102, 102], pixel(0, 0));
+        assert_eq!(&[102, 102, 102], pixel(1, 1));
+        assert_eq!(&[229, 229, 16], pixel(3, 0));
+        assert_eq!(&[0, 0, 0], pixel(1, 3));
+        assert_eq!(&[0, 0, 255], pixel(3, 3));
+    }
+
+    #[test]
+    fn gif() {
+        let path = temporary_file("frames.gif");
+        Export::new()
+            .cell_size(1)
+            .delay(Duration::from_millis(200))
+            .save(&frames(), &path)
+            .unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(File::open(&path).unwrap()).unwrap();
+        let mut decoded = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((3, 2), (frame.width, frame.height));
+            assert_eq!(20, frame.delay);
+            decoded.push(frame.buffer.to_vec());
+        }
+        fs::remove_file(&path).unwrap();
+
+        // The first frame is narrower than the second one
+        assert_eq!(2, decoded.len());
+        let pixel = |frame: usize, x: usize, y: usize| &decoded[frame][(y * 3 + x) * 4..][..3];
+        assert_eq!(&[229, 229, 16], pixel(0, 1, 0));
+        assert_eq!(&[0, 0, 0], pixel(0, 2, 0));
+        assert_eq!(&[204, 204, 204], pixel(0, 1, 1));
+        assert_eq!(&[0, 0, 0], pixel(1, 1, 0));
+        assert_eq!(&[229, 229, 16], pixel(1, 2, 0));
+    }
+
+    #[test]
+    fn errors() {
+        let mut out = Vec::new();
+        assert!(matches!(
+            Export::new().gif(&[], &mut out),
+            Err(ExportError::NoFrames)
+        ));
+        assert!(matches!(
+            Export::new().save(&frames(), Path::new("frames.bmp")),
+            Err(ExportError::UnknownFormat(_))
+        ));
+        let wide = Frame::new(&"#".repeat(20_000));
+        assert!(matches!(
+            Export::new().png(&wide, &mut out),
+            Err(ExportError::TooLarge { .. })
+        ));
+    }
+}
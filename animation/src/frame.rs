@@ -0,0 +1,156 @@
+// Frames of an animation: lines of characters, each of which can have a color.
+
+use std::fmt;
+use std::io::{self, Write};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl Color {
+    // SGR code of the foreground color
+    fn code(self) -> u8 {
+        match self {
+            Color::Black => 30,
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+            Color::Gray => 90,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct Cell {
+    pub(crate) character: char,
+    pub(crate) color: Option<Color>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frame {
+    pub(crate) lines: Vec<Vec<Cell>>,
+}
+
+impl Frame {
+    pub fn new(text: &str) -> Self {
+        Self::colored(text, |_| None)
+    }
+
+    // Colors each character of the text with the color returned by `color`.
+    pub fn colored<F>(text: &str, mut color: F) -> Self
+    where
+        F: FnMut(char) -> Option<Color>,
+    {
+        Self {
+            lines: text
+                .lines()
+                .map(|line| {
+                    line.chars()
+                        .map(|character| Cell {
+                            character,
+                            color: color(character),
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    // Adds the lines of another frame below the lines of this one.
+    pub fn extend(&mut self, frame: Frame) {
+        self.lines.extend(frame.lines);
+    }
+
+    // Length of the longest line
+    pub fn width(&self) -> usize {
+        self.lines.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.lines.len()
+    }
+
+    // Writes the lines with escape sequences for the colors. Each line is terminated with
+    // `end_of_line`.
+    pub(crate) fn write_ansi<W: Write>(
+        &self,
+        out: &mut W,
+        colors: bool,
+        end_of_line: &str,
+    ) -> io::Result<()> {
+        for line in &self.lines {
+            let mut current = None;
+            for cell in line {
+                let color = if colors { cell.color } else { None };
+                if color != current {
+                    match color {
+                        Some(color) => write!(out, "\x1b[{}m", color.code())?,
+                        None => write!(out, "\x1b[0m")?,
+                    }
+                    current = color;
+                }
+                write!(out, "{}", cell.character)?;
+            }
+            if current.is_some() {
+                write!(out, "\x1b[0m")?;
+            }
+            write!(out, "{}", end_of_line)?;
+        }
+        Ok(())
+    }
+}
+
+// The text of the frame, without colors
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            for cell in line {
+                write!(f, "{}", cell.character)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ansi(frame: &Frame, colors: bool) -> String {
+        let mut out = Vec::new();
+        frame.write_ansi(&mut out, colors, "\n").unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn colors() {
+        let mut frame = Frame::colored("#o.\n..", |c| match c {
+            '#' => Some(Color::Gray),
+            'o' => Some(Color::Yellow),
+            _ => None,
+        });
+        frame.extend(Frame::new("SCORE: 1"));
+
+        assert_eq!((8, 3), (frame.width(), frame.height()));
+        assert_eq!("#o.\n..\nSCORE: 1\n", frame.to_string());
+        assert_eq!(
+            "\x1b[90m#\x1b[33mo\x1b[0m.\n..\nSCORE: 1\n",
+            ansi(&frame, true)
+        );
+        assert_eq!(frame.to_string(), ansi(&frame, false));
+    }
+}
@@ -0,0 +1,257 @@
+// Animations in the terminal. Each frame is drawn in place of the previous one with ANSI escape
+// sequences, and the animation can be paused, stepped through and sped up with the keyboard.
+// Headless animations only capture the frames, so that tests can look at them.
+
+#[cfg(feature = "export")]
+mod export;
+mod frame;
+mod playback;
+
+#[cfg(feature = "export")]
+pub use export::{Export, ExportError, Palette, Rgb};
+pub use frame::{Color, Frame};
+
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::terminal;
+use crossterm::tty::IsTty;
+
+use playback::{Key, Playback};
+
+pub const DEFAULT_FPS: u32 = 30;
+
+// How long to wait for a key at a time while paused
+const PAUSED_POLL: Duration = Duration::from_millis(250);
+
+enum Output {
+    Terminal {
+        // Number of lines drawn last, which are overwritten by the next frame
+        height: usize,
+        // Whether the terminal is in raw mode to read single key presses
+        keys: bool,
+    },
+    Headless(Vec<Frame>),
+}
+
+pub struct Animation {
+    output: Output,
+    playback: Playback,
+    colors: bool,
+    current: Option<Frame>,
+}
+
+impl Animation {
+    // Draws the frames to stdout. The keyboard is only read if stdin is a terminal, otherwise
+    // the animation just plays at the given frame rate.
+    pub fn terminal(fps: u32) -> Self {
+        let keys = io::stdin().is_tty() && terminal::enable_raw_mode().is_ok();
+        Self {
+            output: Output::Terminal { height: 0, keys },
+            playback: Playback::new(fps),
+            colors: true,
+            current: None,
+        }
+    }
+
+    pub fn headless() -> Self {
+        Self {
+            output: Output::Headless(Vec::new()),
+            playback: Playback::new(DEFAULT_FPS),
+            colors: true,
+            current: None,
+        }
+    }
+
+    pub fn with_colors(mut self, colors: bool) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    // Whether the user quit the animation. Further frames aren't shown.
+    pub fn is_stopped(&self) -> bool {
+        self.playback.stopped
+    }
+
+    // Frames captured by a headless animation
+    pub fn frames(&self) -> &[Frame] {
+        match &self.output {
+            Output::Terminal { .. } => &[],
+            Output::Headless(frames) => frames,
+        }
+    }
+
+    // Shows the next frame and waits until it's time for the one after it.
+    pub fn show(&mut self, frame: Frame) {
+        if self.playback.stopped {
+            return;
+        }
+
+        match &mut self.output {
+            Output::Terminal { .. } => {
+                self.current = Some(frame);
+                self.draw();
+                self.wait();
+            }
+            Output::Headless(frames) => frames.push(frame),
+        }
+    }
+
+    fn draw(&mut self) {
+        if let (Output::Terminal { height, .. }, Some(frame)) = (&mut self.output, &self.current) {
+            let status = Frame::colored(&self.playback.status(), |_| Some(Color::Gray));
+            let result = draw(
+                &mut io::stdout().lock(),
+                frame,
+                &status,
+                *height,
+                self.colors,
+            );
+            *height = frame.height() + status.height();
+
+            // Nobody is watching anymore if stdout is closed
+            if result.is_err() {
+                self.playback.stopped = true;
+            }
+        }
+    }
+
+    fn wait(&mut self) {
+        let keys = matches!(self.output, Output::Terminal { keys: true, .. });
+        let start = Instant::now();
+        loop {
+            let timeout = if self.playback.paused {
+                PAUSED_POLL
+            } else {
+                match self.playback.delay.checked_sub(start.elapsed()) {
+                    Some(remaining) if remaining > Duration::ZERO => remaining,
+                    _ => return,
+                }
+            };
+
+            if !keys {
+                thread::sleep(timeout);
+                continue;
+            }
+
+            match event::poll(timeout) {
+                Ok(true) => {
+                    if let Ok(Event::Key(event)) = event::read() {
+                        if let Some(key) = key(event) {
+                            let next = self.playback.press(key);
+                            self.draw();
+                            if next {
+                                return;
+                            }
+                        }
+                    }
+                }
+                Ok(false) => {}
+                Err(_) => return,
+            }
+        }
+    }
+}
+
+impl Drop for Animation {
+    fn drop(&mut self) {
+        if let Output::Terminal { height, keys } = self.output {
+            if keys {
+                let _ = terminal::disable_raw_mode();
+            }
+            if height > 0 {
+                // Show the cursor again
+                print!("\x1b[?25h");
+                let _ = io::stdout().flush();
+            }
+        }
+    }
+}
+
+fn key(event: KeyEvent) -> Option<Key> {
+    match event.code {
+        KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => Some(Key::Quit),
+        KeyCode::Char(' ') | KeyCode::Char('p') => Some(Key::Pause),
+        KeyCode::Char('n') | KeyCode::Right => Some(Key::Step),
+        KeyCode::Char('+') | KeyCode::Up => Some(Key::Faster),
+        KeyCode::Char('-') | KeyCode::Down => Some(Key::Slower),
+        KeyCode::Char('q') | KeyCode::Esc => Some(Key::Quit),
+        _ => None,
+    }
+}
+
+// Draws the frame and the status line below it over the lines that were drawn before. Lines
+// are ended with "\r\n", as the terminal doesn't return the carriage by itself in raw mode.
+fn draw<W: Write>(
+    out: &mut W,
+    frame: &Frame,
+    status: &Frame,
+    previous_height: usize,
+    colors: bool,
+) -> io::Result<()> {
+    if previous_height == 0 {
+        // Hide the cursor
+        write!(out, "\x1b[?25l")?;
+    } else {
+        // Move back to the first line drawn before
+        write!(out, "\r\x1b[{}A", previous_height)?;
+    }
+
+    // Clear the rest of each line and everything below a frame that is smaller than the
+    // previous one.
+    frame.write_ansi(out, colors, "\x1b[K\r\n")?;
+    status.write_ansi(out, colors, "\x1b[K\r\n")?;
+    write!(out, "\x1b[J")?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headless() {
+        let mut animation = Animation::headless();
+        animation.show(Frame::new("#.\n.#"));
+        animation.show(Frame::new(".#\n#."));
+        assert!(!animation.is_stopped());
+
+        let frames: Vec<String> = animation.frames().iter().map(Frame::to_string).collect();
+        assert_eq!(vec!["#.\n.#\n", ".#\n#.\n"], frames);
+    }
+
+    #[test]
+    fn draw_in_place() {
+        let frame = Frame::colored("ab\nc", |c| if c == 'c' { Some(Color::Red) } else { None });
+        let status = Frame::new("[paused]");
+
+        let mut out = Vec::new();
+        draw(&mut out, &frame, &status, 0, true).unwrap();
+        assert_eq!(
+            "\x1b[?25lab\x1b[K\r\n\x1b[31mc\x1b[0m\x1b[K\r\n[paused]\x1b[K\r\n\x1b[J",
+            String::from_utf8(out).unwrap()
+        );
+
+        let mut out = Vec::new();
+        draw(&mut out, &frame, &status, 3, false).unwrap();
+        assert_eq!(
+            "\r\x1b[3Aab\x1b[K\r\nc\x1b[K\r\n[paused]\x1b[K\r\n\x1b[J",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn keys() {
+        let press = |code| key(KeyEvent::new(code, KeyModifiers::NONE));
+        assert_eq!(Some(Key::Pause), press(KeyCode::Char(' ')));
+        assert_eq!(Some(Key::Step), press(KeyCode::Right));
+        assert_eq!(Some(Key::Quit), press(KeyCode::Esc));
+        assert_eq!(None, press(KeyCode::Char('c')));
+        assert_eq!(
+            Some(Key::Quit),
+            key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL))
+        );
+    }
+}
@@ -0,0 +1,107 @@
+// Reaction of a running animation to the keys pressed by the user
+
+use std::time::Duration;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Key {
+    // Pause or resume
+    Pause,
+    // Show the next frame and pause
+    Step,
+    Faster,
+    Slower,
+    // Stop showing frames
+    Quit,
+}
+
+const MIN_DELAY: Duration = Duration::from_millis(1);
+const MAX_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Playback {
+    pub(crate) delay: Duration,
+    pub(crate) paused: bool,
+    pub(crate) stopped: bool,
+}
+
+impl Playback {
+    pub(crate) fn new(fps: u32) -> Self {
+        Self {
+            delay: Duration::from_secs(1) / fps.max(1),
+            paused: false,
+            stopped: false,
+        }
+    }
+
+    // Returns whether the next frame should be shown right away.
+    pub(crate) fn press(&mut self, key: Key) -> bool {
+        match key {
+            Key::Pause => {
+                self.paused = !self.paused;
+                false
+            }
+            Key::Step => {
+                self.paused = true;
+                true
+            }
+            Key::Faster => {
+                self.delay = (self.delay / 2).max(MIN_DELAY);
+                false
+            }
+            Key::Slower => {
+                self.delay = (self.delay * 2).min(MAX_DELAY);
+                false
+            }
+            Key::Quit => {
+                self.stopped = true;
+                true
+            }
+        }
+    }
+
+    pub(crate) fn status(&self) -> String {
+        let state = if self.stopped {
+            String::from("stopped")
+        } else if self.paused {
+            String::from("paused")
+        } else {
+            format!("{:.0} fps", 1.0 / self.delay.as_secs_f64())
+        };
+        format!("[{}]  space: pause  n: step  +/-: speed  q: quit", state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys() {
+        let mut playback = Playback::new(20);
+        assert_eq!(Duration::from_millis(50), playback.delay);
+        assert!(playback.status().starts_with("[20 fps]"));
+
+        assert!(!playback.press(Key::Pause));
+        assert!(playback.paused);
+        assert!(playback.status().starts_with("[paused]"));
+        assert!(!playback.press(Key::Pause));
+        assert!(!playback.paused);
+
+        // Stepping pauses the animation
+        assert!(playback.press(Key::Step));
+        assert!(playback.paused);
+        assert!(playback.press(Key::Step));
+        assert!(playback.paused);
+
+        assert!(!playback.press(Key::Faster));
+        assert_eq!(Duration::from_millis(25), playback.delay);
+        for _ in 0..10 {
+            playback.press(Key::Slower);
+        }
+        assert_eq!(MAX_DELAY, playback.delay);
+
+        assert!(playback.press(Key::Quit));
+        assert!(playback.stopped);
+        assert!(playback.status().starts_with("[stopped]"));
+    }
+}
@@ -0,0 +1,61 @@
+// Registry of the solved days. The input of each day defaults to the `input.txt` in its crate.
+
+use animation::Animation;
+use benchmark::Bencher;
+use solution::Report;
+
+pub struct Day {
+    pub number: u32,
+    pub name: &'static str,
+    // Directory of the day's crate, which holds its input files
+    pub directory: &'static str,
+    pub run: fn(&str, bool) -> Result<Report, String>,
+    pub animate: fn(&str, &mut Animation) -> Result<(), String>,
+    pub bench: fn(&str, &mut Bencher) -> Result<(), String>,
+}
+
+impl Day {
+    // Path of the input file with the given name, e.g. `input` or `example1`
+    pub fn input_file(&self, name: &str) -> String {
+        format!("{}/{}.txt", self.directory, name)
+    }
+}
+
+macro_rules! days {
+    ($($number:literal => $name:literal, $solution:ty;)*) => {
+        pub const DAYS: &[Day] = &[
+            $(Day {
+                number: $number,
+                name: $name,
+                directory: concat!(env!("CARGO_MANIFEST_DIR"), "/../", $name),
+                run: solution::run::<$solution>,
+                animate: solution::animate::<$solution>,
+                bench: solution::bench::<$solution>,
+            },)*
+        ];
+    };
+}
+
+days! {
+    1 => "01-tyranny-of-the-rocket-equation", day01::Day01;
+    2 => "02-1202-program-alarm", day02::Day02;
+    3 => "03-crossed-wires", day03::Day03;
+    4 => "04-secure-container", day04::Day04;
+    5 => "05-sunny-with-a-chance-of-asteroids", day05::Day05;
+    6 => "06-universal-orbit-map", day06::Day06;
+    7 => "07-amplification-circuit", day07::Day07;
+    8 => "08-space-image-format", day08::Day08;
+    9 => "09-sensor-boost", day09::Day09;
+    10 => "10-monitoring-station", day10::Day10;
+    11 => "11-space-police", day11::Day11;
+    12 => "12-the-n-body-problem", day12::Day12;
+    13 => "13-care-package", day13::Day13;
+    14 => "14-space-stoichiometry", day14::Day14;
+    15 => "15-oxygen-system", day15::Day15;
+    16 => "16-flawed-frequency-transmission", day16::Day16;
+    17 => "17-set-and-forget", day17::Day17;
+}
+
+pub fn find(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
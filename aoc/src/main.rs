@@ -0,0 +1,679 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::Duration;
+
+use animation::{Animation, Export};
+use benchmark::{Baseline, Bencher};
+use solution::{answers, format_duration};
+
+mod days;
+mod verify;
+
+use days::Day;
+
+const USAGE: &str = "Usage: aoc <command>
+
+Commands:
+    run <day> [options]  Solve the puzzle of a day
+    run all [--stats]    Solve the puzzles of all days
+    animate <day> [...]  Watch the solution of a day (space: pause, n: step, +/-: speed, q: quit)
+    verify [<day>]       Check the answers of all days, or of one day, against the registry
+    bench [<day>]        Measure parsing, the parts and the hot functions of all days, or of one
+                         day, on their inputs
+    list                 List the solved days
+
+Options:
+    --input FILE         Read the puzzle input from FILE instead of the day's input.txt
+    --stats              Show additional statistics of the solution
+    --fps N              Frames per second of the animation (default: 30)
+    --no-color           Animate without colors
+    --export FILE        Write the animation to FILE instead of the terminal: an animated .gif,
+                         or a .png of the last frame
+    --cell-size N        Pixels per character in exported images (default: 4)
+    --answers FILE       Read the expected answers from FILE instead of answers.toml
+    --filter TEXT        Only measure the benchmarks whose name contains TEXT, e.g. day16/part2
+    --save FILE          Save the measurements to FILE, as the baseline of a later run
+    --baseline FILE      Compare the measurements with those saved in FILE";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Selection {
+    All,
+    Day(u32),
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run {
+        selection: Selection,
+        input: Option<String>,
+        statistics: bool,
+    },
+    Animate {
+        day: u32,
+        input: Option<String>,
+        fps: u32,
+        colors: bool,
+        export: Option<String>,
+        cell_size: usize,
+    },
+    Verify {
+        selection: Selection,
+        answers: Option<String>,
+    },
+    Bench {
+        selection: Selection,
+        filter: Option<String>,
+        save: Option<String>,
+        baseline: Option<String>,
+    },
+    List,
+}
+
+fn main() {
+    let command = match parse_command(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            println!("{}\n\n{}", e, USAGE);
+            std::process::exit(1);
+        }
+    };
+
+    match command {
+        Command::List => {
+            for day in days::DAYS {
+                println!("{:>2}: {}", day.number, day.name);
+            }
+        }
+        Command::Run {
+            selection,
+            input,
+            statistics,
+        } => {
+            let selected: Vec<&Day> = match selection {
+                Selection::All => days::DAYS.iter().collect(),
+                Selection::Day(number) => vec![find_day(number)],
+            };
+
+            let mut total = Duration::default();
+            let mut failed = false;
+            for day in &selected {
+                println!("Day {}: {}", day.number, day.name);
+                match run_day(day, input.as_deref(), statistics) {
+                    Ok(time) => total += time,
+                    Err(e) => {
+                        println!("{}", e);
+                        failed = true;
+                    }
+                }
+                println!();
+            }
+            if selected.len() > 1 {
+                println!("Total: {}", format_duration(total));
+            }
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Command::Animate {
+            day,
+            input,
+            fps,
+            colors,
+            export: None,
+            ..
+        } => {
+            let day = find_day(day);
+            let mut animation = Animation::terminal(fps).with_colors(colors);
+            let result = read_input(day, input.as_deref())
+                .and_then(|input| (day.animate)(&input, &mut animation));
+            // Restore the terminal before printing anything else
+            drop(animation);
+            if let Err(e) = result {
+                println!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        Command::Animate {
+            day,
+            input,
+            fps,
+            export: Some(file),
+            cell_size,
+            ..
+        } => {
+            let export = Export::new()
+                .cell_size(cell_size)
+                .delay(Duration::from_secs(1) / fps);
+            match export_day(find_day(day), input.as_deref(), &export, &file) {
+                Ok(frames) => println!("Wrote {} frames to {}", frames, file),
+                Err(e) => {
+                    println!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::Verify { selection, answers } => {
+            let selected: Vec<&Day> = match selection {
+                Selection::All => days::DAYS.iter().collect(),
+                Selection::Day(number) => vec![find_day(number)],
+            };
+            let file = answers.as_deref().unwrap_or(answers::FILE);
+            match verify_days(&selected, file) {
+                Ok(true) => {}
+                Ok(false) => std::process::exit(1),
+                Err(e) => {
+                    println!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::Bench {
+            selection,
+            filter,
+            save,
+            baseline,
+        } => {
+            let selected: Vec<&Day> = match selection {
+                Selection::All => days::DAYS.iter().collect(),
+                Selection::Day(number) => vec![find_day(number)],
+            };
+            let bencher = match filter {
+                Some(filter) => Bencher::new().with_filter(&filter),
+                None => Bencher::new(),
+            };
+            match bench_days(&selected, bencher, baseline.as_deref(), save.as_deref()) {
+                Ok(true) => {}
+                Ok(false) => std::process::exit(1),
+                Err(e) => {
+                    println!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+}
+
+fn find_day(number: u32) -> &'static Day {
+    match days::find(number) {
+        Some(day) => day,
+        None => {
+            println!("Day {} is not solved yet", number);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn read_input(day: &Day, input: Option<&str>) -> Result<String, String> {
+    let default = day.input_file("input");
+    let input_file = input.unwrap_or(&default);
+    fs::read_to_string(input_file).map_err(|e| format!("Error reading input {}: {}", input_file, e))
+}
+
+// Captures the animation of a day and writes it to an image file. Returns the number of frames.
+fn export_day(
+    day: &Day,
+    input: Option<&str>,
+    export: &Export,
+    file: &str,
+) -> Result<usize, String> {
+    let input = read_input(day, input)?;
+    let mut animation = Animation::headless();
+    (day.animate)(&input, &mut animation)?;
+    export
+        .save(animation.frames(), Path::new(file))
+        .map_err(|e| format!("Error exporting {}: {}", file, e))?;
+    Ok(animation.frames().len())
+}
+
+// Checks the days against the expected answers in the registry file and prints a table of the
+// results. Returns whether all answers are correct.
+fn verify_days(selected: &[&Day], file: &str) -> Result<bool, String> {
+    let entries = fs::read_to_string(file)
+        .map_err(|e| format!("Error reading answers {}: {}", file, e))
+        .and_then(|text| answers::parse(&text).map_err(|e| format!("{}: {}", file, e)))?;
+
+    verify::print_header();
+    let mut outcomes = Vec::new();
+    let mut unchecked = Vec::new();
+    for day in selected {
+        let mut expected = entries
+            .iter()
+            .filter(|entry| entry.day == day.number)
+            .peekable();
+        if expected.peek().is_none() {
+            unchecked.push(day.number.to_string());
+        }
+        for expected in expected {
+            let outcome = verify::check(day, expected);
+            verify::print_row(&outcome);
+            outcomes.push(outcome);
+        }
+    }
+
+    let failures: Vec<String> = outcomes
+        .iter()
+        .flat_map(verify::Outcome::failures)
+        .collect();
+    if !failures.is_empty() {
+        println!("\nFailures:");
+        for failure in &failures {
+            println!("{}", failure);
+        }
+    }
+    if !unchecked.is_empty() {
+        println!("\nNo expected answers for day {}", unchecked.join(", "));
+    }
+    let passed = outcomes.iter().filter(|outcome| outcome.passed()).count();
+    println!("\n{} of {} inputs passed", passed, outcomes.len());
+    Ok(passed == outcomes.len())
+}
+
+// Measures the days on their inputs and prints a table of the results, compared with the baseline
+// file if one is given. Returns whether all days could be measured.
+fn bench_days(
+    selected: &[&Day],
+    bencher: Bencher,
+    baseline: Option<&str>,
+    save: Option<&str>,
+) -> Result<bool, String> {
+    let baseline = match baseline {
+        Some(file) => Some(Rc::new(Baseline::load(file)?)),
+        None => None,
+    };
+    let compared = baseline.clone();
+    let mut bencher = bencher.with_observer(move |measurement| {
+        println!("{}", benchmark::row(measurement, compared.as_deref()))
+    });
+
+    println!("{}", benchmark::HEADER);
+    let mut failed = false;
+    for day in selected {
+        bencher.set_group(&format!("day{:02}", day.number));
+        let result = read_input(day, None).and_then(|input| (day.bench)(&input, &mut bencher));
+        if let Err(e) = result {
+            println!("Day {}: {}", day.number, e);
+            failed = true;
+        }
+    }
+
+    let measurements = bencher.measurements();
+    if let Some(baseline) = &baseline {
+        println!("\n{}", baseline.summary(measurements));
+    }
+    if let Some(file) = save {
+        Baseline::save(measurements, file)?;
+        println!("\nSaved {} measurements to {}", measurements.len(), file);
+    }
+    Ok(!failed)
+}
+
+// Solves the puzzle of a day, prints the report and returns the total time.
+fn run_day(day: &Day, input: Option<&str>, statistics: bool) -> Result<Duration, String> {
+    let input = read_input(day, input)?;
+    let report =
+        (day.run)(&input, statistics).map_err(|e| format!("Error parsing input: {}", e))?;
+    print!("{}", report);
+    Ok(report.total_time())
+}
+
+fn parse_command<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    match args.next().as_deref() {
+        Some("run") => {}
+        Some("animate") => return parse_animate(args),
+        Some("verify") => return parse_verify(args),
+        Some("bench") => return parse_bench(args),
+        Some("list") => {
+            return match args.next() {
+                Some(arg) => Err(format!("Unexpected argument: {}", arg)),
+                None => Ok(Command::List),
+            }
+        }
+        Some(command) => return Err(format!("Unknown command: {}", command)),
+        None => return Err(String::from("Please supply a command!")),
+    }
+
+    let mut selection = None;
+    let mut input = None;
+    let mut statistics = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => input = Some(value(&arg, args.next())?),
+            "--stats" => statistics = true,
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {}", arg)),
+            "all" if selection.is_none() => selection = Some(Selection::All),
+            _ if selection.is_none() => match arg.parse() {
+                Ok(number) => selection = Some(Selection::Day(number)),
+                Err(_) => return Err(format!("Invalid day: {}", arg)),
+            },
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+
+    match selection {
+        Some(Selection::All) if input.is_some() => Err(String::from(
+            "An input file can only be given for a single day",
+        )),
+        Some(selection) => Ok(Command::Run {
+            selection,
+            input,
+            statistics,
+        }),
+        None => Err(String::from("Please supply a day!")),
+    }
+}
+
+fn parse_animate<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut day = None;
+    let mut input = None;
+    let mut fps = animation::DEFAULT_FPS;
+    let mut colors = true;
+    let mut export = None;
+    let mut cell_size = 4;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => input = Some(value(&arg, args.next())?),
+            "--fps" => {
+                let value = value(&arg, args.next())?;
+                fps = match value.parse() {
+                    Ok(fps) if fps > 0 => fps,
+                    _ => return Err(format!("Invalid frame rate: {}", value)),
+                }
+            }
+            "--no-color" => colors = false,
+            "--export" => export = Some(value(&arg, args.next())?),
+            "--cell-size" => {
+                let value = value(&arg, args.next())?;
+                cell_size = match value.parse() {
+                    Ok(cell_size) if cell_size > 0 => cell_size,
+                    _ => return Err(format!("Invalid cell size: {}", value)),
+                }
+            }
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {}", arg)),
+            _ if day.is_none() => match arg.parse() {
+                Ok(number) => day = Some(number),
+                Err(_) => return Err(format!("Invalid day: {}", arg)),
+            },
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+
+    match day {
+        Some(day) => Ok(Command::Animate {
+            day,
+            input,
+            fps,
+            colors,
+            export,
+            cell_size,
+        }),
+        None => Err(String::from("Please supply a day!")),
+    }
+}
+
+fn parse_verify<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut selection = Selection::All;
+    let mut answers = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => answers = Some(value(&arg, args.next())?),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {}", arg)),
+            _ if selection == Selection::All => match arg.parse() {
+                Ok(number) => selection = Selection::Day(number),
+                Err(_) => return Err(format!("Invalid day: {}", arg)),
+            },
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+    Ok(Command::Verify { selection, answers })
+}
+
+fn parse_bench<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut selection = Selection::All;
+    let mut filter = None;
+    let mut save = None;
+    let mut baseline = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--filter" => filter = Some(value(&arg, args.next())?),
+            "--save" => save = Some(value(&arg, args.next())?),
+            "--baseline" => baseline = Some(value(&arg, args.next())?),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {}", arg)),
+            _ if selection == Selection::All => match arg.parse() {
+                Ok(number) => selection = Selection::Day(number),
+                Err(_) => return Err(format!("Invalid day: {}", arg)),
+            },
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+    Ok(Command::Bench {
+        selection,
+        filter,
+        save,
+        baseline,
+    })
+}
+
+fn value(option: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("Missing value for {}", option))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Command, String> {
+        parse_command(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn parse_arguments() {
+        assert_eq!(Ok(Command::List), parse("list"));
+        assert_eq!(
+            Ok(Command::Run {
+                selection: Selection::Day(7),
+                input: None,
+                statistics: false
+            }),
+            parse("run 7")
+        );
+        assert_eq!(
+            Ok(Command::Run {
+                selection: Selection::Day(13),
+                input: Some(String::from("other.txt")),
+                statistics: true
+            }),
+            parse("run --stats 13 --input other.txt")
+        );
+        assert_eq!(
+            Ok(Command::Run {
+                selection: Selection::All,
+                input: None,
+                statistics: true
+            }),
+            parse("run all --stats")
+        );
+
+        assert_eq!(Err(String::from("Please supply a command!")), parse(""));
+        assert_eq!(Err(String::from("Please supply a day!")), parse("run"));
+        assert_eq!(Err(String::from("Invalid day: x")), parse("run x"));
+        assert_eq!(
+            Err(String::from("Unexpected argument: 2")),
+            parse("run 1 2")
+        );
+        assert_eq!(
+            Err(String::from("Missing value for --input")),
+            parse("run 1 --input")
+        );
+        assert!(parse("run all --input other.txt").is_err());
+
+        assert_eq!(
+            Ok(Command::Verify {
+                selection: Selection::All,
+                answers: None
+            }),
+            parse("verify")
+        );
+        assert_eq!(
+            Ok(Command::Verify {
+                selection: Selection::Day(3),
+                answers: Some(String::from("other.toml"))
+            }),
+            parse("verify 3 --answers other.toml")
+        );
+        assert_eq!(
+            Err(String::from("Unexpected argument: 4")),
+            parse("verify 3 4")
+        );
+
+        assert_eq!(
+            Ok(Command::Bench {
+                selection: Selection::All,
+                filter: None,
+                save: None,
+                baseline: None
+            }),
+            parse("bench")
+        );
+        assert_eq!(
+            Ok(Command::Bench {
+                selection: Selection::Day(16),
+                filter: Some(String::from("phase")),
+                save: Some(String::from("after.txt")),
+                baseline: Some(String::from("before.txt"))
+            }),
+            parse("bench 16 --filter phase --baseline before.txt --save after.txt")
+        );
+        assert_eq!(
+            Err(String::from("Missing value for --save")),
+            parse("bench --save")
+        );
+
+        assert_eq!(
+            Ok(Command::Animate {
+                day: 13,
+                input: None,
+                fps: animation::DEFAULT_FPS,
+                colors: true,
+                export: None,
+                cell_size: 4
+            }),
+            parse("animate 13")
+        );
+        assert_eq!(
+            Ok(Command::Animate {
+                day: 15,
+                input: Some(String::from("other.txt")),
+                fps: 5,
+                colors: false,
+                export: None,
+                cell_size: 4
+            }),
+            parse("animate --fps 5 15 --no-color --input other.txt")
+        );
+        assert_eq!(
+            Ok(Command::Animate {
+                day: 11,
+                input: None,
+                fps: animation::DEFAULT_FPS,
+                colors: true,
+                export: Some(String::from("hull.gif")),
+                cell_size: 8
+            }),
+            parse("animate 11 --export hull.gif --cell-size 8")
+        );
+        assert_eq!(
+            Err(String::from("Invalid cell size: 0")),
+            parse("animate 11 --cell-size 0")
+        );
+        assert_eq!(
+            Err(String::from("Invalid frame rate: 0")),
+            parse("animate 11 --fps 0")
+        );
+        assert_eq!(
+            Err(String::from("Missing value for --fps")),
+            parse("animate 11 --fps")
+        );
+        assert_eq!(Err(String::from("Please supply a day!")), parse("animate"));
+    }
+
+    #[test]
+    fn registry() {
+        for (i, day) in days::DAYS.iter().enumerate() {
+            assert_eq!(i as u32 + 1, day.number);
+            assert!(day.name.starts_with(&format!("{:02}-", day.number)));
+        }
+        assert_eq!(Some(5), days::find(5).map(|day| day.number));
+        assert!(days::find(26).is_none());
+    }
+
+    #[test]
+    fn animate_day() {
+        let mut animation = Animation::headless();
+        let input = read_input(days::find(11).unwrap(), None).unwrap();
+        (days::find(11).unwrap().animate)(&input, &mut animation).unwrap();
+        assert!(!animation.frames().is_empty());
+
+        let input = read_input(days::find(1).unwrap(), None).unwrap();
+        assert!((days::find(1).unwrap().animate)(&input, &mut animation).is_err());
+    }
+
+    #[test]
+    fn export_animation() {
+        let day = days::find(11).unwrap();
+        let file = std::env::temp_dir().join(format!("aoc-{}-hull.png", std::process::id()));
+        let file = file.to_str().unwrap();
+
+        let frames = export_day(day, None, &Export::new(), file).unwrap();
+        assert!(frames > 0);
+        assert_eq!(b"\x89PNG", &fs::read(file).unwrap()[..4]);
+        fs::remove_file(file).unwrap();
+
+        assert!(export_day(day, None, &Export::new(), "hull.txt")
+            .unwrap_err()
+            .starts_with("Error exporting hull.txt: Unknown image format"));
+    }
+
+    #[test]
+    fn verify_single_day() {
+        let day = days::find(1).unwrap();
+        assert_eq!(Ok(true), verify_days(&[day], answers::FILE));
+
+        let file = std::env::temp_dir().join(format!("aoc-{}-answers.toml", std::process::id()));
+        fs::write(&file, "[day01]\ninput = { part1 = 1 }").unwrap();
+        assert_eq!(Ok(false), verify_days(&[day], file.to_str().unwrap()));
+        fs::remove_file(&file).unwrap();
+
+        assert!(verify_days(&[day], "missing.toml")
+            .unwrap_err()
+            .starts_with("Error reading answers missing.toml: "));
+    }
+
+    #[test]
+    fn bench_single_day() {
+        let day = days::find(1).unwrap();
+        let file = std::env::temp_dir().join(format!("aoc-{}-baseline.txt", std::process::id()));
+        let file = file.to_str().unwrap();
+        let bencher = || Bencher::new().with_target(Duration::from_millis(1));
+
+        assert_eq!(Ok(true), bench_days(&[day], bencher(), None, Some(file)));
+        let saved = fs::read_to_string(file).unwrap();
+        assert!(saved.contains("\nday01/part1 "));
+        assert_eq!(Ok(true), bench_days(&[day], bencher(), Some(file), None));
+        fs::remove_file(file).unwrap();
+
+        assert!(bench_days(&[day], bencher(), Some("missing.txt"), None)
+            .unwrap_err()
+            .starts_with("Couldn't read baseline missing.txt: "));
+    }
+
+    #[test]
+    fn run_single_day() {
+        let day = days::find(1).unwrap();
+        assert!(run_day(day, None, false).is_ok());
+        assert!(run_day(day, Some("missing.txt"), false)
+            .unwrap_err()
+            .starts_with("Error reading input missing.txt: "));
+    }
+}
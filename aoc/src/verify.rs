@@ -0,0 +1,174 @@
+// Checks the answers of the days against the expected answers of the registry.
+
+use std::fs;
+use std::panic;
+use std::time::Duration;
+
+use solution::answers::Expected;
+use solution::format_duration;
+
+use crate::days::Day;
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub day: u32,
+    pub input: String,
+    pub expected: [Option<String>; 2],
+    // The answers and the time to solve the input, or why it couldn't be solved
+    pub result: Result<([String; 2], Duration), String>,
+}
+
+impl Outcome {
+    // Parts without an expected answer neither pass nor fail.
+    fn part_passed(&self, part: usize) -> Option<bool> {
+        let expected = self.expected[part].as_ref()?;
+        Some(match &self.result {
+            Ok((answers, _)) => answers[part].trim_end() == expected,
+            Err(_) => false,
+        })
+    }
+
+    pub fn passed(&self) -> bool {
+        (0..2).all(|part| self.part_passed(part) != Some(false))
+    }
+
+    // Describes what went wrong, one line per failure.
+    pub fn failures(&self) -> Vec<String> {
+        let name = format!("Day {} ({})", self.day, self.input);
+        match &self.result {
+            Err(e) => vec![format!("{}: {}", name, e)],
+            Ok((answers, _)) => (0..2)
+                .filter(|&part| self.part_passed(part) == Some(false))
+                .map(|part| {
+                    format!(
+                        "{} part {}: expected {}, got {}",
+                        name,
+                        part + 1,
+                        self.expected[part].as_ref().unwrap(),
+                        answers[part].trim_end()
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    // Row of the table printed by `print_row`
+    fn row(&self) -> String {
+        let status = |part| match self.part_passed(part) {
+            Some(true) => "ok",
+            Some(false) => "FAIL",
+            None => "-",
+        };
+        let time = match &self.result {
+            Ok((_, time)) => format_duration(*time),
+            Err(_) => String::from("-"),
+        };
+        format!(
+            "{:>3}  {:<12} {:<7} {:<7} {:>10}",
+            self.day,
+            self.input,
+            status(0),
+            status(1),
+            time
+        )
+    }
+}
+
+pub fn print_header() {
+    println!("Day  Input        Part 1  Part 2        Time");
+}
+
+pub fn print_row(outcome: &Outcome) {
+    println!("{}", outcome.row());
+}
+
+// Solves the input of a day named by the registry entry.
+pub fn check(day: &Day, expected: &Expected) -> Outcome {
+    let file = day.input_file(&expected.input);
+    let result = fs::read_to_string(&file)
+        .map_err(|e| format!("Error reading input {}: {}", file, e))
+        .and_then(|input| solve(day, &input));
+
+    Outcome {
+        day: day.number,
+        input: expected.input.clone(),
+        expected: expected.parts.clone(),
+        result,
+    }
+}
+
+// Panics of the solution are reported as errors, so that the remaining days are still checked.
+fn solve(day: &Day, input: &str) -> Result<([String; 2], Duration), String> {
+    let report = match panic::catch_unwind(|| (day.run)(input, false)) {
+        Ok(report) => report.map_err(|e| format!("Error parsing input: {}", e))?,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown cause");
+            return Err(format!("Panicked: {}", message));
+        }
+    };
+    let time = report.total_time();
+    let [part1, part2] = report.parts;
+    Ok(([part1.answer, part2.answer], time))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use solution::answers;
+
+    fn expected(input: &str, part1: Option<&str>, part2: Option<&str>) -> Expected {
+        Expected {
+            day: 1,
+            input: String::from(input),
+            parts: [part1.map(String::from), part2.map(String::from)],
+        }
+    }
+
+    #[test]
+    fn passing_day() {
+        let day = days::find(1).unwrap();
+        let outcome = check(day, &expected("example", Some("34241"), None));
+        assert!(outcome.passed());
+        assert!(outcome.failures().is_empty());
+        assert!(outcome.row().starts_with("  1  example      ok      -  "));
+    }
+
+    #[test]
+    fn failing_day() {
+        let day = days::find(1).unwrap();
+        let outcome = check(day, &expected("example", Some("34241"), Some("1")));
+        assert!(!outcome.passed());
+        assert_eq!(
+            vec![String::from(
+                "Day 1 (example) part 2: expected 1, got 51316"
+            )],
+            outcome.failures()
+        );
+        assert!(outcome
+            .row()
+            .starts_with("  1  example      ok      FAIL  "));
+
+        let outcome = check(day, &expected("missing", Some("1"), None));
+        assert!(!outcome.passed());
+        assert!(outcome.failures()[0].starts_with("Day 1 (missing): Error reading input "));
+        assert!(outcome.row().ends_with(" -"));
+    }
+
+    // Every solved day has the answers of its puzzle input
+    #[test]
+    fn registry_file() {
+        let entries = answers::parse(&fs::read_to_string(answers::FILE).unwrap()).unwrap();
+        for day in days::DAYS {
+            let input = entries
+                .iter()
+                .find(|entry| entry.day == day.number && entry.input == "input")
+                .unwrap();
+            assert!(input.parts.iter().all(Option::is_some));
+        }
+    }
+}
@@ -0,0 +1,203 @@
+// Results of an earlier run, saved as one line per measurement, and the comparison with them
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::time::Duration;
+
+use crate::Measurement;
+
+// Relative changes of the median below this are considered noise.
+const NOISE: f64 = 0.05;
+
+const FILE_HEADER: &str = "# name median_ns mean_ns deviation_ns samples";
+
+#[derive(Debug, Default)]
+pub struct Baseline {
+    measurements: HashMap<String, Measurement>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    New,
+    // Relative change of the median, e.g. -0.25 if it takes a quarter less time
+    Unchanged(f64),
+    Faster(f64),
+    Slower(f64),
+}
+
+impl Baseline {
+    pub fn new(measurements: &[Measurement]) -> Self {
+        Self {
+            measurements: measurements
+                .iter()
+                .map(|m| (m.name.clone(), m.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Couldn't read baseline {}: {}", path, e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    pub fn save(measurements: &[Measurement], path: &str) -> Result<(), String> {
+        fs::write(path, Self::format(measurements))
+            .map_err(|e| format!("Couldn't write baseline {}: {}", path, e))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut measurements = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let invalid = || format!("Invalid measurement at line {}: {}", number + 1, line);
+            if fields.len() != 5 {
+                return Err(invalid());
+            }
+            let nanos = |field: &str| field.parse().map(Duration::from_nanos);
+            measurements.push(Measurement {
+                name: fields[0].to_string(),
+                median: nanos(fields[1]).map_err(|_| invalid())?,
+                mean: nanos(fields[2]).map_err(|_| invalid())?,
+                deviation: nanos(fields[3]).map_err(|_| invalid())?,
+                samples: fields[4].parse().map_err(|_| invalid())?,
+            });
+        }
+        Ok(Self::new(&measurements))
+    }
+
+    pub fn format(measurements: &[Measurement]) -> String {
+        let mut text = format!("{}\n", FILE_HEADER);
+        for m in measurements {
+            text.push_str(&format!(
+                "{} {} {} {} {}\n",
+                m.name,
+                m.median.as_nanos(),
+                m.mean.as_nanos(),
+                m.deviation.as_nanos(),
+                m.samples
+            ));
+        }
+        text
+    }
+
+    // A change only counts if it's larger than the noise and the deviations of both runs.
+    pub fn compare(&self, measurement: &Measurement) -> Comparison {
+        let old = match self.measurements.get(&measurement.name) {
+            Some(old) => old,
+            None => return Comparison::New,
+        };
+        let (before, after) = (old.median.as_secs_f64(), measurement.median.as_secs_f64());
+        if before == 0.0 {
+            return Comparison::Unchanged(0.0);
+        }
+        let change = (after - before) / before;
+        let spread = (old.deviation + measurement.deviation).as_secs_f64();
+        if change.abs() <= NOISE || (after - before).abs() <= spread {
+            Comparison::Unchanged(change)
+        } else if change < 0.0 {
+            Comparison::Faster(change)
+        } else {
+            Comparison::Slower(change)
+        }
+    }
+
+    // Counts the measurements that got faster, slower, stayed the same or are new.
+    pub fn summary(&self, measurements: &[Measurement]) -> String {
+        let mut counts = [0; 4];
+        for measurement in measurements {
+            let index = match self.compare(measurement) {
+                Comparison::Faster(_) => 0,
+                Comparison::Slower(_) => 1,
+                Comparison::Unchanged(_) => 2,
+                Comparison::New => 3,
+            };
+            counts[index] += 1;
+        }
+        format!(
+            "{} faster, {} slower, {} without change, {} new",
+            counts[0], counts[1], counts[2], counts[3]
+        )
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Comparison::New => write!(f, "new"),
+            Comparison::Unchanged(change) => write!(f, "{:+.1}% (no change)", change * 100.0),
+            Comparison::Faster(change) => write!(f, "{:+.1}% faster", change * 100.0),
+            Comparison::Slower(change) => write!(f, "{:+.1}% slower", change * 100.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(name: &str, median: u64, deviation: u64) -> Measurement {
+        Measurement {
+            name: name.to_string(),
+            median: Duration::from_micros(median),
+            mean: Duration::from_micros(median),
+            deviation: Duration::from_micros(deviation),
+            samples: 10,
+        }
+    }
+
+    #[test]
+    fn save_and_load() {
+        let measurements = vec![
+            measurement("day01/parse", 12, 1),
+            measurement("day16/part2", 40_000_000, 0),
+        ];
+        let text = Baseline::format(&measurements);
+        assert!(text.starts_with("# name"));
+        assert!(text.contains("day16/part2 40000000000 40000000000 0 10\n"));
+
+        let baseline = Baseline::parse(&text).unwrap();
+        assert_eq!(
+            Some(&measurements[0]),
+            baseline.measurements.get("day01/parse")
+        );
+        assert_eq!(2, baseline.measurements.len());
+
+        assert_eq!(
+            Err("Invalid measurement at line 2: day01/parse 12 x 1 10".to_string()),
+            Baseline::parse("# header\nday01/parse 12 x 1 10").map(|_| ())
+        );
+        assert!(Baseline::parse("day01/parse 12 12 1").is_err());
+    }
+
+    #[test]
+    fn comparisons() {
+        let baseline = Baseline::new(&[measurement("a", 1000, 10), measurement("b", 1000, 200)]);
+
+        let compare = |name, median, deviation| {
+            baseline
+                .compare(&measurement(name, median, deviation))
+                .to_string()
+        };
+        assert_eq!("new", compare("c", 1000, 10));
+        assert_eq!("-25.0% faster", compare("a", 750, 10));
+        assert_eq!("+50.0% slower", compare("a", 1500, 10));
+        assert_eq!("+3.0% (no change)", compare("a", 1030, 10));
+        // Within the deviations of noisy measurements
+        assert_eq!("+25.0% (no change)", compare("b", 1250, 100));
+
+        assert_eq!(
+            "1 faster, 0 slower, 1 without change, 1 new",
+            baseline.summary(&[
+                measurement("a", 500, 10),
+                measurement("b", 1000, 10),
+                measurement("c", 1000, 10)
+            ])
+        );
+    }
+}
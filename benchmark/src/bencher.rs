@@ -0,0 +1,175 @@
+// Runs functions repeatedly and collects the measurements
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::Measurement;
+
+pub const DEFAULT_TARGET: Duration = Duration::from_secs(2);
+
+const WARM_UP: Duration = Duration::from_millis(100);
+// Quick functions are called in batches that take about this long, so that the time of a sample
+// isn't dominated by the resolution of the clock.
+const BATCH_TIME: Duration = Duration::from_millis(1);
+const MIN_SAMPLES: usize = 5;
+const MAX_SAMPLES: usize = 100;
+
+type Observer = Box<dyn FnMut(&Measurement)>;
+
+pub struct Bencher {
+    target: Duration,
+    filter: Option<String>,
+    group: Option<String>,
+    observer: Option<Observer>,
+    measurements: Vec<Measurement>,
+}
+
+impl Bencher {
+    pub fn new() -> Self {
+        Self {
+            target: DEFAULT_TARGET,
+            filter: None,
+            group: None,
+            observer: None,
+            measurements: Vec::new(),
+        }
+    }
+
+    // Time spent measuring each function. Functions that take longer than this are called fewer
+    // times, but at least once.
+    pub fn with_target(mut self, target: Duration) -> Self {
+        self.target = target;
+        self
+    }
+
+    // Only measures the functions whose full name contains the filter.
+    pub fn with_filter(mut self, filter: &str) -> Self {
+        self.filter = Some(filter.to_string());
+        self
+    }
+
+    // Called after each measurement, e.g. to print the results while the others are still running
+    pub fn with_observer<F: FnMut(&Measurement) + 'static>(mut self, observer: F) -> Self {
+        self.observer = Some(Box::new(observer));
+        self
+    }
+
+    // Prefixes the names of the following measurements, e.g. "day16" for "day16/part2".
+    pub fn set_group(&mut self, group: &str) {
+        self.group = Some(group.to_string());
+    }
+
+    pub fn measurements(&self) -> &[Measurement] {
+        &self.measurements
+    }
+
+    // Whether `bench` would measure a function with this name; lets callers skip expensive setup.
+    pub fn is_selected(&self, name: &str) -> bool {
+        let name = self.full_name(name);
+        match &self.filter {
+            Some(filter) => name.contains(filter.as_str()),
+            None => true,
+        }
+    }
+
+    pub fn bench<T, F: FnMut() -> T>(&mut self, name: &str, mut f: F) {
+        if !self.is_selected(name) {
+            return;
+        }
+
+        // Estimates the time of a call while warming up the caches
+        let start = Instant::now();
+        let mut calls = 0u32;
+        while calls == 0 || start.elapsed() < WARM_UP.min(self.target) {
+            black_box(f());
+            calls += 1;
+        }
+        let estimate = start.elapsed() / calls;
+
+        // Slow functions that take longer than the target are only called once, so the call
+        // that warmed up is the only sample.
+        let times = if estimate > self.target {
+            vec![estimate]
+        } else {
+            let batch = if estimate.is_zero() {
+                1000
+            } else {
+                (BATCH_TIME.as_nanos() / estimate.as_nanos()).max(1) as u32
+            };
+            let samples = self.target.as_nanos() / (estimate * batch).as_nanos().max(1);
+            let samples = (samples as usize).clamp(MIN_SAMPLES, MAX_SAMPLES);
+            (0..samples)
+                .map(|_| {
+                    let start = Instant::now();
+                    for _ in 0..batch {
+                        black_box(f());
+                    }
+                    start.elapsed() / batch
+                })
+                .collect()
+        };
+
+        let measurement = Measurement::from_samples(&self.full_name(name), &times);
+        if let Some(observer) = &mut self.observer {
+            observer(&measurement);
+        }
+        self.measurements.push(measurement);
+    }
+
+    fn full_name(&self, name: &str) -> String {
+        match &self.group {
+            Some(group) => format!("{}/{}", group, name),
+            None => name.to_string(),
+        }
+    }
+}
+
+impl Default for Bencher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn names_and_filter() {
+        let mut bencher = Bencher::new()
+            .with_target(Duration::from_millis(5))
+            .with_filter("day01/");
+        bencher.set_group("day01");
+        bencher.bench("part1", || 1 + 1);
+        bencher.set_group("day02");
+        bencher.bench("part1", || 2 + 2);
+
+        let names: Vec<&str> = bencher
+            .measurements()
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(vec!["day01/part1"], names);
+        assert!(!bencher.is_selected("part2"));
+    }
+
+    #[test]
+    fn samples() {
+        let observed = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&observed);
+        let mut bencher = Bencher::new()
+            .with_target(Duration::from_millis(20))
+            .with_observer(move |_| counter.set(counter.get() + 1));
+
+        bencher.bench("quick", || (0..100u64).sum::<u64>());
+        bencher.bench("slow", || std::thread::sleep(Duration::from_millis(30)));
+
+        let measurements = bencher.measurements();
+        assert!(measurements[0].samples >= MIN_SAMPLES);
+        assert_eq!(1, measurements[1].samples);
+        assert!(measurements[1].median >= Duration::from_millis(30));
+        assert_eq!(2, observed.get());
+    }
+}
@@ -0,0 +1,58 @@
+// Measures the running time of functions and compares the results with those of an earlier run,
+// which can be saved to a file. Used by the `aoc` runner and by the binaries of the days.
+
+mod baseline;
+mod bencher;
+mod measurement;
+
+pub use baseline::{Baseline, Comparison};
+pub use bencher::{Bencher, DEFAULT_TARGET};
+pub use measurement::{format_time, Measurement};
+
+// Header of the rows of `row`
+pub const HEADER: &str =
+    "Benchmark                          Median       Mean ± σ          Samples  Change";
+
+// Formats a measurement, compared with the baseline if there is one.
+pub fn row(measurement: &Measurement, baseline: Option<&Baseline>) -> String {
+    let mut row = format!(
+        "{:<30} {:>10} {:>10} ± {:<10} {:>7}",
+        measurement.name,
+        format_time(measurement.median),
+        format_time(measurement.mean),
+        format_time(measurement.deviation),
+        measurement.samples
+    );
+    if let Some(baseline) = baseline {
+        row.push_str(&format!("  {}", baseline.compare(measurement)));
+    }
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn rows() {
+        let measurement = Measurement {
+            name: String::from("day16/phase"),
+            median: Duration::from_micros(1250),
+            mean: Duration::from_micros(1300),
+            deviation: Duration::from_micros(80),
+            samples: 100,
+        };
+        let row = row(&measurement, Some(&Baseline::default()));
+        assert!(row.starts_with("day16/phase "));
+        assert!(row.ends_with("  new"));
+        // The columns end below their titles, counted in characters because of "±" and "µ".
+        let end = |text: &str, column: &str| {
+            let start = text.find(column).unwrap();
+            text[..start].chars().count() + column.chars().count()
+        };
+        for (title, value) in [("Median", "1.25 ms"), ("Samples", "100")] {
+            assert_eq!(end(HEADER, title), end(&row, value));
+        }
+    }
+}
@@ -0,0 +1,88 @@
+// Statistics of the running times of a function
+
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub name: String,
+    pub median: Duration,
+    pub mean: Duration,
+    // Standard deviation of the samples
+    pub deviation: Duration,
+    pub samples: usize,
+}
+
+impl Measurement {
+    // Each sample is the time of a single call.
+    pub fn from_samples(name: &str, samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples of {}", name);
+        let mut seconds: Vec<f64> = samples.iter().map(Duration::as_secs_f64).collect();
+        seconds.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let n = seconds.len();
+        // The middle sample, or the mean of the two middle samples
+        let median = (seconds[(n - 1) / 2] + seconds[n / 2]) / 2.0;
+        let mean = seconds.iter().sum::<f64>() / n as f64;
+        let variance = seconds.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n as f64;
+
+        Self {
+            name: name.to_string(),
+            median: Duration::from_secs_f64(median),
+            mean: Duration::from_secs_f64(mean),
+            deviation: Duration::from_secs_f64(variance.sqrt()),
+            samples: n,
+        }
+    }
+}
+
+// Three significant digits, from nanoseconds to seconds
+pub fn format_time(duration: Duration) -> String {
+    let nanos = duration.as_secs_f64() * 1e9;
+    let (value, unit) = if nanos < 1e3 {
+        (nanos, "ns")
+    } else if nanos < 1e6 {
+        (nanos / 1e3, "µs")
+    } else if nanos < 1e9 {
+        (nanos / 1e6, "ms")
+    } else {
+        (nanos / 1e9, "s")
+    };
+    let decimals = if value < 10.0 {
+        2
+    } else if value < 100.0 {
+        1
+    } else {
+        0
+    };
+    format!("{:.*} {}", decimals, value, unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statistics() {
+        let samples: Vec<Duration> = [4, 1, 3, 2, 10]
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect();
+        let measurement = Measurement::from_samples("sort", &samples);
+        assert_eq!(Duration::from_millis(3), measurement.median);
+        assert_eq!(Duration::from_millis(4), measurement.mean);
+        // sqrt(10) ms
+        assert_eq!(3162, measurement.deviation.as_micros());
+        assert_eq!(5, measurement.samples);
+
+        let even = Measurement::from_samples("even", &samples[..4]);
+        assert_eq!(Duration::from_micros(2500), even.median);
+    }
+
+    #[test]
+    fn times() {
+        assert_eq!("850 ns", format_time(Duration::from_nanos(850)));
+        assert_eq!("1.25 µs", format_time(Duration::from_nanos(1250)));
+        assert_eq!("12.5 ms", format_time(Duration::from_micros(12_500)));
+        assert_eq!("39.8 s", format_time(Duration::from_millis(39_770)));
+    }
+}
@@ -0,0 +1,273 @@
+// Creates the crate of a new day and registers it with the workspace, the `aoc` runner and the
+// answers registry.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod skeleton;
+mod workspace;
+
+use skeleton::Kind;
+
+const USAGE: &str = "Usage: create <day> [--intcode | --adhoc]
+
+The day is named like its crate, e.g. 18-many-worlds-interpretation.
+
+Options:
+    --intcode            Depend on the intcode crate and parse the input as an intcode program
+    --adhoc              Parse each line of the input into a struct derived with adhoc_derive";
+
+const ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    name: String,
+    number: u32,
+    kind: Kind,
+}
+
+fn main() {
+    let options = match parse_options(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            println!("{}\n\n{}", e, USAGE);
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(e) = create(Path::new(ROOT), &options) {
+        println!("{}", e);
+        std::process::exit(1);
+    }
+    println!(
+        "Created {0}. Save the puzzle input to {0}/input.txt. Once the answers are known, fill \
+         them in in answers.toml and remove the `#[ignore]` of their tests in {0}/src/lib.rs.",
+        options.name
+    );
+}
+
+// Writes the crate of the day and registers it. All registrations are prepared before anything is
+// written, so that the workspace stays as it is if one of them fails. The crate is written first,
+// so that the registrations never refer to a crate that doesn't exist.
+fn create(root: &Path, options: &Options) -> Result<(), String> {
+    let directory = root.join(&options.name);
+    if directory.exists() {
+        return Err(format!("{} already exists", directory.display()));
+    }
+
+    let read = |file: &str| {
+        let path = root.join(file);
+        fs::read_to_string(&path)
+            .map(|text| (path.clone(), text))
+            .map_err(|e| format!("Error reading {}: {}", path.display(), e))
+    };
+    let (name, number) = (options.name.as_str(), options.number);
+    let mut registrations: Vec<(PathBuf, String)> = Vec::new();
+    let (path, text) = read("Cargo.toml")?;
+    registrations.push((path, workspace::add_member(&text, name)?));
+    let (path, text) = read("aoc/Cargo.toml")?;
+    registrations.push((path, workspace::add_dependency(&text, name, number)?));
+    let (path, text) = read("aoc/src/days.rs")?;
+    registrations.push((path, workspace::register_day(&text, number, name)?));
+    let (path, text) = read("answers.toml")?;
+    registrations.push((path, workspace::add_answers(&text, number)?));
+
+    let files = vec![
+        (
+            directory.join("Cargo.toml"),
+            skeleton::cargo_toml(name, number, options.kind),
+        ),
+        (directory.join("src/main.rs"), skeleton::main_rs(number)),
+        (
+            directory.join("src/lib.rs"),
+            skeleton::lib_rs(number, options.kind),
+        ),
+        (directory.join("input.txt"), String::new()),
+    ];
+
+    let src = directory.join("src");
+    fs::create_dir_all(&src).map_err(|e| format!("Error creating {}: {}", src.display(), e))?;
+    for (path, text) in files.into_iter().chain(registrations) {
+        fs::write(&path, text).map_err(|e| format!("Error writing {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+fn parse_options<I: Iterator<Item = String>>(args: I) -> Result<Options, String> {
+    let mut name = None;
+    let mut kind = Kind::Plain;
+    for arg in args {
+        let option = match arg.as_str() {
+            "--intcode" => Kind::Intcode,
+            "--adhoc" => Kind::Adhoc,
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {}", arg)),
+            _ if name.is_none() => {
+                name = Some(arg);
+                continue;
+            }
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        };
+        if kind != Kind::Plain && kind != option {
+            return Err(String::from(
+                "Only one of --intcode and --adhoc can be given",
+            ));
+        }
+        kind = option;
+    }
+
+    let name = name.ok_or_else(|| String::from("Please supply a day!"))?;
+    let number = parse_name(&name)?;
+    Ok(Options { name, number, kind })
+}
+
+// Names start with the number of the day (1 to 25) in two digits, followed by the title in
+// lowercase words separated by hyphens. Returns the number.
+fn parse_name(name: &str) -> Result<u32, String> {
+    let invalid = || {
+        format!(
+            "Invalid day: {} (expected e.g. 18-many-worlds-interpretation)",
+            name
+        )
+    };
+    let mut parts = name.splitn(2, '-');
+    let number = parts.next().unwrap_or_default();
+    let title = parts.next().ok_or_else(invalid)?;
+
+    if number.len() != 2 || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let words = title.split('-').all(|word| {
+        !word.is_empty()
+            && word
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    });
+    match number.parse() {
+        Ok(number) if words && (1..=25).contains(&number) => Ok(number),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Options, String> {
+        parse_options(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn parse_arguments() {
+        assert_eq!(
+            Ok(Options {
+                name: String::from("18-many-worlds-interpretation"),
+                number: 18,
+                kind: Kind::Plain
+            }),
+            parse("18-many-worlds-interpretation")
+        );
+        assert_eq!(
+            Ok(Options {
+                name: String::from("19-tractor-beam"),
+                number: 19,
+                kind: Kind::Intcode
+            }),
+            parse("--intcode 19-tractor-beam")
+        );
+        assert_eq!(Ok(Kind::Adhoc), parse("05-x --adhoc").map(|o| o.kind));
+
+        assert_eq!(Err(String::from("Please supply a day!")), parse(""));
+        assert_eq!(
+            Err(String::from(
+                "Only one of --intcode and --adhoc can be given"
+            )),
+            parse("19-tractor-beam --intcode --adhoc")
+        );
+        assert_eq!(
+            Err(String::from("Unknown option: --other")),
+            parse("19-tractor-beam --other")
+        );
+        assert_eq!(
+            Err(String::from("Unexpected argument: 20-x")),
+            parse("19-tractor-beam 20-x")
+        );
+        for name in &[
+            "18",
+            "18-",
+            "8-tractor",
+            "26-x",
+            "00-x",
+            "18_x",
+            "18-Beam",
+            "18-a--b",
+            "+1-x",
+        ] {
+            assert!(parse(name).is_err(), "{}", name);
+        }
+    }
+
+    // A workspace with the files that get edited, in a temporary directory
+    fn workspace(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("create-{}-{}", std::process::id(), name));
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\n\nmembers = [\n    \"aoc\",\n    \"01-a\",\n]\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("aoc/Cargo.toml"),
+            "[package]\nname = \"aoc\"\n\n[dependencies]\nday01 = { package = \"01-a\", path = \"../01-a\" }\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("aoc/src/days.rs"),
+            "days! {\n    1 => \"01-a\", day01::Day01;\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("answers.toml"),
+            "[day01]\ninput = { part1 = 1 }\n",
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn create_day() {
+        let root = workspace("day");
+        let options = parse("02-b --intcode").unwrap();
+        create(&root, &options).unwrap();
+
+        let read = |file: &str| fs::read_to_string(root.join(file)).unwrap();
+        assert!(read("Cargo.toml").contains("    \"01-a\",\n    \"02-b\",\n]"));
+        assert!(read("aoc/Cargo.toml")
+            .ends_with("day02 = { package = \"02-b\", path = \"../02-b\" }\n"));
+        assert!(read("aoc/src/days.rs").contains("    2 => \"02-b\", day02::Day02;\n}"));
+        assert!(read("answers.toml").ends_with("[day02]\ninput = {}\n"));
+        assert!(read("02-b/Cargo.toml").contains("intcode = { path = \"../intcode\" }"));
+        assert!(read("02-b/src/main.rs").contains("day02::Day02"));
+        assert!(read("02-b/src/lib.rs").contains("impl Solution for Day02"));
+        assert_eq!("", read("02-b/input.txt"));
+
+        assert!(create(&root, &options)
+            .unwrap_err()
+            .ends_with("02-b already exists"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    // Nothing is written if the day can't be registered.
+    #[test]
+    fn create_registered_day() {
+        let root = workspace("registered");
+        let before = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert_eq!(
+            Err(String::from("The runner already depends on day01")),
+            create(&root, &parse("01-other").unwrap())
+        );
+        assert!(!root.join("01-other").exists());
+        assert_eq!(before, fs::read_to_string(root.join("Cargo.toml")).unwrap());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,222 @@
+// Files of a new day's crate
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Kind {
+    // Parses the input line by line into strings
+    Plain,
+    // Runs an intcode program, depends on the `intcode` crate
+    Intcode,
+    // Parses each line with a regular expression derived by `adhoc_derive`
+    Adhoc,
+}
+
+// The package is named after the directory, the library after the day (e.g. "day18").
+pub fn cargo_toml(name: &str, number: u32, kind: Kind) -> String {
+    let mut dependencies = vec![r#"solution = { path = "../solution" }"#];
+    match kind {
+        Kind::Plain => {}
+        Kind::Intcode => dependencies.push(r#"intcode = { path = "../intcode" }"#),
+        Kind::Adhoc => dependencies.extend(&[
+            r#"adhoc_derive = "0.1.2""#,
+            r#"lazy_static = "1.2.0""#,
+            r#"regex = "1.1.0""#,
+        ]),
+    }
+    dependencies.sort_unstable();
+
+    format!(
+        r#"[package]
+name = "{}"
+version = "0.1.0"
+authors = ["df5602 <dominik@fankhausers.ch>"]
+edition = "2018"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[lib]
+name = "day{:02}"
+
+[dependencies]
+{}
+"#,
+        name,
+        number,
+        dependencies.join("\n")
+    )
+}
+
+pub fn main_rs(number: u32) -> String {
+    format!(
+        "fn main() {{\n    solution::main::<day{0:02}::Day{0:02}>();\n}}\n",
+        number
+    )
+}
+
+// The solution, with tests of the answers for the puzzle input that are ignored until the answers
+// are known and filled in in answers.toml
+pub fn lib_rs(number: u32, kind: Kind) -> String {
+    let (code, input) = match kind {
+        Kind::Plain => (PLAIN, "Vec<String>"),
+        Kind::Intcode => (INTCODE, "Vec<MemoryType>"),
+        Kind::Adhoc => (ADHOC, "Vec<Entry>"),
+    };
+    format!("{}{}", code, TESTS.replace("INPUT", input))
+        .replace("DayNN", &format!("Day{:02}", number))
+        .replace("expected(NN", &format!("expected({}", number))
+}
+
+const PLAIN: &str = r#"use solution::Solution;
+
+pub struct DayNN;
+
+impl Solution for DayNN {
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        solution::parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> usize {
+        input.len()
+    }
+
+    fn part2(_input: &Self::Input) -> usize {
+        0
+    }
+}
+"#;
+
+const INTCODE: &str = r#"use std::collections::VecDeque;
+
+use intcode::{Computer, MemoryType};
+use solution::Solution;
+
+pub struct DayNN;
+
+impl Solution for DayNN {
+    type Input = Vec<MemoryType>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        intcode::parse_program(input).map_err(|e| e.to_string())
+    }
+
+    fn part1(program: &Self::Input) -> usize {
+        run(program, &[1]).len()
+    }
+
+    fn part2(_program: &Self::Input) -> usize {
+        0
+    }
+}
+
+// Runs the program until it stops and returns its output.
+fn run(program: &[MemoryType], input: &[MemoryType]) -> Vec<MemoryType> {
+    let input: VecDeque<MemoryType> = input.iter().copied().collect();
+    let mut computer = Computer::new(0, program, input, Vec::new());
+    computer.run_program();
+    computer.get_output().clone()
+}
+"#;
+
+const ADHOC: &str = r#"use std::collections::HashSet;
+
+use adhoc_derive::FromStr;
+use solution::Solution;
+
+pub struct DayNN;
+
+impl Solution for DayNN {
+    type Input = Vec<Entry>;
+    type Answer1 = i64;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        solution::parse_lines(input)
+    }
+
+    fn part1(entries: &Self::Input) -> i64 {
+        entries.iter().map(|entry| entry.value).sum()
+    }
+
+    fn part2(entries: &Self::Input) -> usize {
+        let names: HashSet<&str> = entries.iter().map(|entry| entry.name.as_str()).collect();
+        names.len()
+    }
+}
+
+#[derive(Debug, Clone, FromStr)]
+#[adhoc(regex = r"^(?P<name>\w+) (?P<value>-?\d+)$")]
+pub struct Entry {
+    name: String,
+    value: i64,
+}
+"#;
+
+const TESTS: &str = r#"
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solution::{answers, Answer};
+    use std::fs;
+
+    fn input() -> INPUT {
+        DayNN::parse(&fs::read_to_string("input.txt").unwrap()).unwrap()
+    }
+
+    #[test]
+    #[ignore = "the answer isn't known yet"]
+    fn part_1() {
+        assert_eq!(answers::expected(NN, 1), DayNN::part1(&input()).to_answer());
+    }
+
+    #[test]
+    #[ignore = "the answer isn't known yet"]
+    fn part_2() {
+        assert_eq!(answers::expected(NN, 2), DayNN::part2(&input()).to_answer());
+    }
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifests() {
+        let plain = cargo_toml("18-many-worlds-interpretation", 18, Kind::Plain);
+        assert!(plain.starts_with("[package]\nname = \"18-many-worlds-interpretation\"\n"));
+        assert!(plain.contains("[lib]\nname = \"day18\"\n"));
+        assert!(plain.ends_with("[dependencies]\nsolution = { path = \"../solution\" }\n"));
+
+        let intcode = cargo_toml("19-tractor-beam", 19, Kind::Intcode);
+        assert!(intcode.ends_with(
+            "[dependencies]\nintcode = { path = \"../intcode\" }\nsolution = { path = \"../solution\" }\n"
+        ));
+
+        let adhoc = cargo_toml("04-secure-container", 4, Kind::Adhoc);
+        assert!(adhoc.contains("name = \"day04\""));
+        assert!(adhoc.contains("[dependencies]\nadhoc_derive = \"0.1.2\"\nlazy_static"));
+    }
+
+    #[test]
+    fn sources() {
+        assert_eq!(
+            "fn main() {\n    solution::main::<day07::Day07>();\n}\n",
+            main_rs(7)
+        );
+        for &kind in &[Kind::Plain, Kind::Intcode, Kind::Adhoc] {
+            let code = lib_rs(18, kind);
+            assert!(code.contains("pub struct Day18;\n"));
+            assert!(code.contains("impl Solution for Day18 {"));
+            assert!(code.contains("Day18::parse(&fs::read_to_string(\"input.txt\")"));
+            assert!(code.contains("answers::expected(18, 2), Day18::part2(&input())"));
+            assert!(!code.contains("NN") && !code.contains("INPUT"));
+        }
+        assert!(lib_rs(19, Kind::Intcode).contains("intcode::parse_program(input)"));
+        assert!(lib_rs(20, Kind::Adhoc).contains("use adhoc_derive::FromStr;"));
+    }
+}
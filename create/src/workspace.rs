@@ -0,0 +1,197 @@
+// Registers a new day with the workspace, the `aoc` runner and the answers registry. Each function
+// takes the contents of a file and returns them with the day added, keeping the rest as it is.
+
+use toml_edit::{DocumentMut, InlineTable, Item, Table, Value};
+
+fn parse(text: &str, file: &str) -> Result<DocumentMut, String> {
+    text.parse()
+        .map_err(|e| format!("Invalid TOML in {}: {}", file, e))
+}
+
+// Days are kept in order after the other members of the workspace.
+pub fn add_member(cargo_toml: &str, name: &str) -> Result<String, String> {
+    let mut document = parse(cargo_toml, "Cargo.toml")?;
+    let members = document
+        .get_mut("workspace")
+        .and_then(|workspace| workspace.get_mut("members"))
+        .and_then(Item::as_array_mut)
+        .ok_or_else(|| String::from("Cargo.toml has no workspace members"))?;
+    if members.iter().any(|member| member.as_str() == Some(name)) {
+        return Err(format!("{} is already a member of the workspace", name));
+    }
+
+    let index = members
+        .iter()
+        .position(|member| {
+            member
+                .as_str()
+                .is_some_and(|member| is_later_day(member, name))
+        })
+        .unwrap_or_else(|| members.len());
+    let mut member = Value::from(name);
+    member.decor_mut().set_prefix("\n    ");
+    members.insert_formatted(index, member);
+    members.set_trailing_comma(true);
+    members.set_trailing("\n");
+    Ok(document.to_string())
+}
+
+fn is_later_day(member: &str, name: &str) -> bool {
+    member.len() > 2 && member.as_bytes()[..2].iter().all(u8::is_ascii_digit) && member > name
+}
+
+// The runner depends on the crates of all days. Package names can't be used as crate names
+// because they start with a digit, so each dependency is renamed after the library of the day.
+pub fn add_dependency(cargo_toml: &str, name: &str, number: u32) -> Result<String, String> {
+    let mut document = parse(cargo_toml, "aoc/Cargo.toml")?;
+    let dependencies = document
+        .get_mut("dependencies")
+        .and_then(Item::as_table_mut)
+        .ok_or_else(|| String::from("aoc/Cargo.toml has no dependencies"))?;
+    let key = format!("day{:02}", number);
+    if dependencies.contains_key(&key) {
+        return Err(format!("The runner already depends on {}", key));
+    }
+
+    let mut dependency = InlineTable::new();
+    dependency.insert("package", name.into());
+    dependency.insert("path", format!("../{}", name).into());
+    dependencies.insert(&key, toml_edit::value(dependency));
+    dependencies.sort_values();
+    Ok(document.to_string())
+}
+
+// Adds the day to the invocation of the `days!` macro, which lists the days in order.
+pub fn register_day(days_rs: &str, number: u32, name: &str) -> Result<String, String> {
+    let start = days_rs
+        .find("days! {\n")
+        .map(|start| start + "days! {\n".len())
+        .ok_or_else(|| String::from("aoc/src/days.rs doesn't invoke days!"))?;
+    let end = start
+        + days_rs[start..]
+            .find("\n}")
+            .ok_or_else(|| String::from("aoc/src/days.rs doesn't close days!"))?
+        + 1;
+
+    let mut index = end;
+    let mut offset = start;
+    for line in days_rs[start..end].lines() {
+        let registered: Option<u32> = line
+            .split("=>")
+            .next()
+            .and_then(|number| number.trim().parse().ok());
+        match registered {
+            Some(registered) if registered == number => {
+                return Err(format!(
+                    "Day {} is already registered with the runner",
+                    number
+                ))
+            }
+            Some(registered) if registered > number && index == end => index = offset,
+            _ => {}
+        }
+        offset += line.len() + 1;
+    }
+
+    let entry = format!(
+        "    {} => \"{}\", day{:02}::Day{:02};\n",
+        number, name, number, number
+    );
+    Ok(format!(
+        "{}{}{}",
+        &days_rs[..index],
+        entry,
+        &days_rs[index..]
+    ))
+}
+
+// The day gets a table for the answers of its puzzle input, to be filled in once they are known.
+pub fn add_answers(answers_toml: &str, number: u32) -> Result<String, String> {
+    let mut document = parse(answers_toml, "answers.toml")?;
+    let key = format!("day{:02}", number);
+    if document.contains_key(&key) {
+        return Err(format!("answers.toml already has answers for {}", key));
+    }
+
+    let mut answers = Table::new();
+    answers.insert("input", toml_edit::value(InlineTable::new()));
+    document.insert(&key, Item::Table(answers));
+    Ok(document.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspace_members() {
+        let cargo_toml =
+            "[workspace]\n\nmembers = [\n    \"aoc\",\n    \"01-a\",\n    \"03-c\",\n]\n";
+        assert_eq!(
+            Ok(String::from(
+                "[workspace]\n\nmembers = [\n    \"aoc\",\n    \"01-a\",\n    \"02-b\",\n    \"03-c\",\n]\n"
+            )),
+            add_member(cargo_toml, "02-b")
+        );
+        assert_eq!(
+            Ok(String::from(
+                "[workspace]\n\nmembers = [\n    \"aoc\",\n    \"01-a\",\n    \"03-c\",\n    \"04-d\",\n]\n"
+            )),
+            add_member(cargo_toml, "04-d")
+        );
+        assert_eq!(
+            Err(String::from("03-c is already a member of the workspace")),
+            add_member(cargo_toml, "03-c")
+        );
+        assert!(add_member("[package]\nname = \"x\"\n", "02-b").is_err());
+    }
+
+    #[test]
+    fn runner_dependencies() {
+        let cargo_toml = "[package]\nname = \"aoc\"\n\n[dependencies]\nanimation = { path = \"../animation\" }\nday01 = { package = \"01-a\", path = \"../01-a\" }\nsolution = { path = \"../solution\" }\n";
+        assert_eq!(
+            Ok(String::from(
+                "[package]\nname = \"aoc\"\n\n[dependencies]\nanimation = { path = \"../animation\" }\nday01 = { package = \"01-a\", path = \"../01-a\" }\nday02 = { package = \"02-b\", path = \"../02-b\" }\nsolution = { path = \"../solution\" }\n"
+            )),
+            add_dependency(cargo_toml, "02-b", 2)
+        );
+        assert_eq!(
+            Err(String::from("The runner already depends on day01")),
+            add_dependency(cargo_toml, "01-other", 1)
+        );
+    }
+
+    #[test]
+    fn registry() {
+        let days_rs = "use x;\n\ndays! {\n    1 => \"01-a\", day01::Day01;\n    3 => \"03-c\", day03::Day03;\n}\n\npub fn find() {}\n";
+        assert_eq!(
+            Ok(String::from(
+                "use x;\n\ndays! {\n    1 => \"01-a\", day01::Day01;\n    2 => \"02-b\", day02::Day02;\n    3 => \"03-c\", day03::Day03;\n}\n\npub fn find() {}\n"
+            )),
+            register_day(days_rs, 2, "02-b")
+        );
+        assert_eq!(
+            Ok(String::from(
+                "use x;\n\ndays! {\n    1 => \"01-a\", day01::Day01;\n    3 => \"03-c\", day03::Day03;\n    12 => \"12-l\", day12::Day12;\n}\n\npub fn find() {}\n"
+            )),
+            register_day(days_rs, 12, "12-l")
+        );
+        assert_eq!(
+            Err(String::from("Day 3 is already registered with the runner")),
+            register_day(days_rs, 3, "03-c")
+        );
+        assert!(register_day("fn main() {}\n", 2, "02-b").is_err());
+    }
+
+    #[test]
+    fn answers() {
+        let answers_toml = "# Expected answers\n\n[day01]\ninput = { part1 = 1, part2 = 2 }\n";
+        assert_eq!(
+            Ok(String::from(
+                "# Expected answers\n\n[day01]\ninput = { part1 = 1, part2 = 2 }\n\n[day02]\ninput = {}\n"
+            )),
+            add_answers(answers_toml, 2)
+        );
+        assert!(add_answers(answers_toml, 1).is_err());
+    }
+}
@@ -0,0 +1,328 @@
+// Grid of fixed size, stored row by row. Points with negative coordinates or beyond the width or
+// height are outside of the grid.
+
+use std::error::Error;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::{distances, Point};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    Empty,
+    // A line (1-based) is longer or shorter than the first one
+    RaggedLine {
+        line: usize,
+        length: usize,
+        width: usize,
+    },
+    InvalidCharacter {
+        line: usize,
+        column: usize,
+        character: char,
+    },
+}
+
+impl fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGridError::Empty => write!(f, "Empty map"),
+            ParseGridError::RaggedLine {
+                line,
+                length,
+                width,
+            } => write!(
+                f,
+                "Line {} has {} characters, expected {}",
+                line, length, width
+            ),
+            ParseGridError::InvalidCharacter {
+                line,
+                column,
+                character,
+            } => write!(
+                f,
+                "Invalid character {:?} at line {}, column {}",
+                character, line, column
+            ),
+        }
+    }
+}
+
+impl Error for ParseGridError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    // Parses a character map with one row per line, converting each character with `tile`.
+    // Characters for which `tile` returns `None` are rejected. Empty lines at the end are
+    // ignored.
+    pub fn parse<F>(text: &str, mut tile: F) -> Result<Self, ParseGridError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut lines: Vec<&str> = text.lines().collect();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+
+        let width = match lines.first() {
+            Some(line) => line.chars().count(),
+            None => return Err(ParseGridError::Empty),
+        };
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            let length = line.chars().count();
+            if length != width {
+                return Err(ParseGridError::RaggedLine {
+                    line: i + 1,
+                    length,
+                    width,
+                });
+            }
+            for (column, character) in line.chars().enumerate() {
+                match tile(character) {
+                    Some(value) => cells.push(value),
+                    None => {
+                        return Err(ParseGridError::InvalidCharacter {
+                            line: i + 1,
+                            column: column + 1,
+                            character,
+                        })
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= 0
+            && point.y >= 0
+            && (point.x as usize) < self.width
+            && (point.y as usize) < self.height
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        if self.contains(point) {
+            Some(point.y as usize * self.width + point.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        match self.index_of(point) {
+            Some(index) => Some(&mut self.cells[index]),
+            None => None,
+        }
+    }
+
+    // All points of the grid, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.width * self.height)
+            .map(move |index| Point::new((index % width) as isize, (index / width) as isize))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    // First point, row by row, whose value matches the predicate
+    pub fn find<F>(&self, predicate: F) -> Option<Point>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.iter()
+            .find(|(_, value)| predicate(value))
+            .map(|(point, _)| point)
+    }
+
+    // Grid of the same size with each value converted by `f`
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // Draws the grid, one line per row.
+    pub fn render<F>(&self, mut draw: F) -> String
+    where
+        F: FnMut(Point, &T) -> char,
+    {
+        let mut rendered = String::with_capacity((self.width + 1) * self.height);
+        for (point, value) in self.iter() {
+            rendered.push(draw(point, value));
+            if point.x as usize == self.width - 1 {
+                rendered.push('\n');
+            }
+        }
+        rendered
+    }
+
+    // Steps from `start` to every point reachable through passable points, `None` for the
+    // points that can't be reached.
+    pub fn distances<F>(&self, start: Point, passable: F) -> Grid<Option<usize>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut grid = Grid::new(self.width, self.height, None);
+        let reachable = distances(start, |point| self.get(point).is_some_and(&passable));
+        for (point, distance) in reachable {
+            if let Some(cell) = grid.get_mut(point) {
+                *cell = Some(distance);
+            }
+        }
+        grid
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.index_of(point) {
+            Some(index) => &self.cells[index],
+            None => panic!("{} is outside of the grid", point),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        match self.index_of(point) {
+            Some(index) => &mut self.cells[index],
+            None => panic!("{} is outside of the grid", point),
+        }
+    }
+}
+
+// Draws each cell with its `Display` implementation.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (point, value) in self.iter() {
+            write!(f, "{}", value)?;
+            if point.x as usize == self.width - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#..#\n.##.\n....\n\n";
+
+    fn parse(text: &str) -> Result<Grid<bool>, ParseGridError> {
+        Grid::parse(text, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let grid = parse(MAP).unwrap();
+        assert_eq!((4, 3), (grid.width(), grid.height()));
+        assert!(grid[Point::new(3, 0)]);
+        assert!(!grid[Point::new(3, 1)]);
+        assert_eq!(None, grid.get(Point::new(4, 0)));
+        assert_eq!(None, grid.get(Point::new(0, -1)));
+        assert_eq!(Some(Point::new(0, 0)), grid.find(|&wall| wall));
+        assert_eq!(Some(Point::new(1, 0)), grid.find(|&wall| !wall));
+        assert_eq!(
+            MAP.trim_end().to_string() + "\n",
+            grid.render(|_, &wall| if wall { '#' } else { '.' })
+        );
+
+        let mut grid = Grid::new(2, 2, 0);
+        grid[Point::new(1, 0)] = 7;
+        assert_eq!("07\n00\n", grid.to_string());
+        assert_eq!("014\n00\n", grid.map(|value| value * 2).to_string());
+        assert_eq!(
+            vec![(Point::new(0, 1), &0), (Point::new(1, 1), &0)],
+            grid.iter().skip(2).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Err(ParseGridError::Empty), parse("\n"));
+        assert_eq!(
+            Err(ParseGridError::RaggedLine {
+                line: 2,
+                length: 3,
+                width: 4
+            }),
+            parse("#..#\n.##\n")
+        );
+        let error = parse("#..#\n.#x.\n").unwrap_err();
+        assert_eq!(
+            ParseGridError::InvalidCharacter {
+                line: 2,
+                column: 3,
+                character: 'x'
+            },
+            error
+        );
+        assert_eq!(
+            "Invalid character 'x' at line 2, column 3",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn distances() {
+        let grid = parse(MAP).unwrap();
+        let distances = grid.distances(Point::new(0, 1), |&wall| !wall);
+        assert_eq!(Some(0), distances[Point::new(0, 1)]);
+        assert_eq!(Some(5), distances[Point::new(3, 1)]);
+        assert_eq!(None, distances[Point::new(0, 0)]);
+        // Enclosed by walls
+        assert_eq!(None, distances[Point::new(1, 0)]);
+    }
+}
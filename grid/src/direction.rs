@@ -0,0 +1,73 @@
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    // Clockwise, starting up
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    // Direction after turning 90 degrees to the left.
+    pub fn left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    // Direction after turning 90 degrees to the right.
+    pub fn right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+
+    // (dx, dy) of a step in this direction
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        for &direction in &Direction::ALL {
+            assert_eq!(direction, direction.left().right());
+            assert_eq!(direction.reverse(), direction.left().left());
+            assert_eq!(direction.reverse(), direction.right().right());
+        }
+        assert_eq!(Direction::Left, Direction::Up.left());
+        assert_eq!(Direction::Right, Direction::Up.right());
+        assert_eq!((-1, 0), Direction::Left.offset());
+    }
+}
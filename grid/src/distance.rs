@@ -0,0 +1,44 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::Point;
+
+// Breadth-first search from `start` through the 4-neighborhoods of the points for which
+// `passable` returns true. Returns the number of steps to every reachable point, including the
+// start itself (0 steps). `passable` has to reject all points outside of a finite area.
+pub fn distances<F>(start: Point, mut passable: F) -> HashMap<Point, usize>
+where
+    F: FnMut(Point) -> bool,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    distances.insert(start, 0);
+    queue.push_back((start, 0));
+
+    while let Some((point, distance)) = queue.pop_front() {
+        for &neighbor in &point.neighbors4() {
+            if !distances.contains_key(&neighbor) && passable(neighbor) {
+                distances.insert(neighbor, distance + 1);
+                queue.push_back((neighbor, distance + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_area() {
+        // 3x3 square without its center
+        let distances = distances(Point::new(-1, -1), |point| {
+            point.x.abs() <= 1 && point.y.abs() <= 1 && point != Point::ORIGIN
+        });
+        assert_eq!(8, distances.len());
+        assert_eq!(Some(&0), distances.get(&Point::new(-1, -1)));
+        assert_eq!(Some(&4), distances.get(&Point::new(1, 1)));
+        assert_eq!(None, distances.get(&Point::ORIGIN));
+    }
+}
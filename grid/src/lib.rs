@@ -0,0 +1,15 @@
+// Points, directions and grids of the puzzles that move around on a 2D map. The y axis points
+// down, as on a screen: moving up decreases y.
+
+mod dense;
+mod direction;
+mod distance;
+pub mod ocr;
+mod point;
+mod sparse;
+
+pub use dense::{Grid, ParseGridError};
+pub use direction::Direction;
+pub use distance::distances;
+pub use point::Point;
+pub use sparse::SparseGrid;
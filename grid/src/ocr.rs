@@ -0,0 +1,242 @@
+// Recognition of the block letters that some puzzles draw as their answer. The letters are 6 or
+// 10 pixels high (4 or 6 wide) and separated by at least one empty column.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::{Grid, Point};
+
+const LETTERS_6: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[rustfmt::skip]
+const LETTERS_10: &[(char, [&str; 10])] = &[
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#",
+           "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.",
+           "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....",
+           "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.",
+           "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.",
+           "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....",
+           "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######",
+           "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.",
+           "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....",
+           "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....",
+           "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#",
+           "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.",
+           "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.",
+           "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..",
+           "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..",
+           "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    // Height of the letters, without the empty rows above and below them
+    UnsupportedHeight(usize),
+    // A letter starting at the given column (0-based) that doesn't match any known letter
+    UnknownLetter { column: usize, pixels: String },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::UnsupportedHeight(height) => {
+                write!(f, "Letters of height {} are not supported", height)
+            }
+            OcrError::UnknownLetter { column, pixels } => {
+                write!(f, "Unknown letter at column {}:\n{}", column, pixels)
+            }
+        }
+    }
+}
+
+impl Error for OcrError {}
+
+// Reads the letters whose pixels are set.
+pub fn recognize(grid: &Grid<bool>) -> Result<String, OcrError> {
+    let is_set = |x: usize, y: usize| grid[Point::new(x as isize, y as isize)];
+    let rows: Vec<usize> = (0..grid.height())
+        .filter(|&y| (0..grid.width()).any(|x| is_set(x, y)))
+        .collect();
+    let (top, bottom) = match (rows.first(), rows.last()) {
+        (Some(&top), Some(&bottom)) => (top, bottom),
+        _ => return Ok(String::new()),
+    };
+    let height = bottom - top + 1;
+
+    let mut text = String::new();
+    let mut x = 0;
+    while x < grid.width() {
+        let is_empty = |x: usize| (top..=bottom).all(|y| !is_set(x, y));
+        if is_empty(x) {
+            x += 1;
+            continue;
+        }
+
+        // A letter ends with the next empty column
+        let start = x;
+        while x < grid.width() && !is_empty(x) {
+            x += 1;
+        }
+        let pixels: Vec<String> = (top..=bottom)
+            .map(|y| {
+                (start..x)
+                    .map(|x| if is_set(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        let letter = match height {
+            6 => find(LETTERS_6, &pixels),
+            10 => find(LETTERS_10, &pixels),
+            _ => return Err(OcrError::UnsupportedHeight(height)),
+        };
+        match letter {
+            Some(letter) => text.push(letter),
+            None => {
+                return Err(OcrError::UnknownLetter {
+                    column: start,
+                    pixels: pixels.join("\n"),
+                })
+            }
+        }
+    }
+    Ok(text)
+}
+
+// Letters are compared without their empty columns at the left and the right.
+fn find<R: AsRef<[&'static str]>>(letters: &[(char, R)], pixels: &[String]) -> Option<char> {
+    letters
+        .iter()
+        .find(|(_, rows)| {
+            let rows = rows.as_ref();
+            let is_empty = |x: usize| rows.iter().all(|row| row.as_bytes()[x] == b'.');
+            let width = rows[0].len();
+            let left = (0..width).find(|&x| !is_empty(x)).unwrap_or(0);
+            let right = (0..width).rfind(|&x| !is_empty(x)).unwrap_or(0);
+            rows.iter()
+                .zip(pixels)
+                .all(|(row, pixels)| row[left..=right] == pixels[..])
+        })
+        .map(|&(letter, _)| letter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Grid<bool> {
+        Grid::parse(text, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap()
+    }
+
+    // Draws the letters next to each other, with a space between them and around them.
+    fn draw<R: AsRef<[&'static str]>>(letters: &[(char, R)]) -> Grid<bool> {
+        let height = letters[0].1.as_ref().len();
+        let mut text = String::new();
+        for y in 0..height + 2 {
+            text.push('.');
+            for (_, rows) in letters {
+                let width = rows.as_ref()[0].len();
+                match y {
+                    0 => text.push_str(&".".repeat(width)),
+                    y if y == height + 1 => text.push_str(&".".repeat(width)),
+                    y => text.push_str(rows.as_ref()[y - 1]),
+                }
+                text.push('.');
+            }
+            text.push('\n');
+        }
+        parse(&text)
+    }
+
+    #[test]
+    fn small_letters() {
+        let alphabet: String = LETTERS_6.iter().map(|&(letter, _)| letter).collect();
+        assert_eq!(Ok(alphabet), recognize(&draw(LETTERS_6)));
+
+        // Letters are usually 4 pixels wide, followed by one empty column
+        let text = parse(
+            "\
+###..####.#..#.
+#..#.#....#..#.
+#..#.###..####.
+###..#....#..#.
+#....#....#..#.
+#....####.#..#.",
+        );
+        assert_eq!(Ok(String::from("PEH")), recognize(&text));
+    }
+
+    #[test]
+    fn large_letters() {
+        let alphabet: String = LETTERS_10.iter().map(|&(letter, _)| letter).collect();
+        assert_eq!(Ok(alphabet), recognize(&draw(LETTERS_10)));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Ok(String::new()), recognize(&parse("....\n....")));
+        assert_eq!(
+            Err(OcrError::UnsupportedHeight(2)),
+            recognize(&parse("#..#\n####"))
+        );
+
+        let error = recognize(&parse(
+            "\
+.##..####
+#..#.#..#
+#..#.#..#
+####.#..#
+#..#.#..#
+#..#.####",
+        ))
+        .unwrap_err();
+        assert_eq!(
+            OcrError::UnknownLetter {
+                column: 5,
+                pixels: String::from("####\n#..#\n#..#\n#..#\n#..#\n####")
+            },
+            error
+        );
+        assert!(error
+            .to_string()
+            .starts_with("Unknown letter at column 5:\n####\n"));
+    }
+}
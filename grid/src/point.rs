@@ -0,0 +1,112 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+
+use crate::Direction;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    // Points sharing an edge with this one, clockwise starting above.
+    pub fn neighbors4(self) -> [Point; 4] {
+        [
+            self + Direction::Up,
+            self + Direction::Right,
+            self + Direction::Down,
+            self + Direction::Left,
+        ]
+    }
+
+    // Points sharing an edge or a corner with this one, clockwise starting above.
+    pub fn neighbors8(self) -> [Point; 8] {
+        let Point { x, y } = self;
+        [
+            Point::new(x, y - 1),
+            Point::new(x + 1, y - 1),
+            Point::new(x + 1, y),
+            Point::new(x + 1, y + 1),
+            Point::new(x, y + 1),
+            Point::new(x - 1, y + 1),
+            Point::new(x - 1, y),
+            Point::new(x - 1, y - 1),
+        ]
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> usize {
+        (self.x - other.x).unsigned_abs() + (self.y - other.y).unsigned_abs()
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, other: Point) -> Self {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, other: Point) -> Self {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+// One step in the direction
+impl Add<Direction> for Point {
+    type Output = Self;
+
+    fn add(self, direction: Direction) -> Self {
+        let (dx, dy) = direction.offset();
+        Point::new(self.x + dx, self.y + dy)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbors() {
+        let point = Point::new(2, -1);
+        assert_eq!(
+            [
+                Point::new(2, -2),
+                Point::new(3, -1),
+                Point::new(2, 0),
+                Point::new(1, -1)
+            ],
+            point.neighbors4()
+        );
+
+        let neighbors = point.neighbors8();
+        assert!(point
+            .neighbors4()
+            .iter()
+            .all(|neighbor| neighbors.contains(neighbor)));
+        assert!(neighbors
+            .iter()
+            .all(|&neighbor| neighbor != point && (neighbor - point).x.abs() <= 1));
+        assert_eq!(Point::new(3, 0), neighbors[3]);
+
+        assert_eq!(Point::new(2, -2), point + Direction::Up);
+        assert_eq!(4, point.manhattan_distance(Point::new(0, 1)));
+        assert_eq!("(2,-1)", point.to_string());
+    }
+}
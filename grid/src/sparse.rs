@@ -0,0 +1,157 @@
+// Grid of the points that were set so far, for maps that are discovered while moving around
+// and whose size isn't known in advance. The bounds grow with every point that is set.
+
+use std::collections::HashMap;
+
+use crate::{distances, Grid, Point};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    // Smallest and largest coordinates of the points set so far
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Sets a point and returns its previous value.
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                Point::new(min.x.min(point.x), min.y.min(point.y)),
+                Point::new(max.x.max(point.x), max.y.max(point.y)),
+            ),
+            None => (point, point),
+        });
+        self.cells.insert(point, value)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // Points that were set, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&point, value)| (point, value))
+    }
+
+    // Top left and bottom right corner of the area containing all points that were set.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    // Dense grid of the area within the bounds, whose top left corner is moved to the origin.
+    // Points that weren't set are `empty`.
+    pub fn to_grid(&self, empty: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        match self.bounds {
+            Some((min, max)) => {
+                let mut grid = Grid::new(
+                    (max.x - min.x + 1) as usize,
+                    (max.y - min.y + 1) as usize,
+                    empty,
+                );
+                for (&point, value) in &self.cells {
+                    grid[point - min] = value.clone();
+                }
+                grid
+            }
+            None => Grid::new(0, 0, empty),
+        }
+    }
+
+    // Draws the area within the bounds, one line per row. Points that weren't set are passed
+    // as `None`.
+    pub fn render<F>(&self, mut draw: F) -> String
+    where
+        F: FnMut(Point, Option<&T>) -> char,
+    {
+        let mut rendered = String::new();
+        if let Some((min, max)) = self.bounds {
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    let point = Point::new(x, y);
+                    rendered.push(draw(point, self.get(point)));
+                }
+                rendered.push('\n');
+            }
+        }
+        rendered
+    }
+
+    // Steps from `start` to every point reachable through points that were set and are
+    // passable.
+    pub fn distances<F>(&self, start: Point, passable: F) -> HashMap<Point, usize>
+    where
+        F: Fn(&T) -> bool,
+    {
+        distances(start, |point| self.get(point).is_some_and(&passable))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_and_rendering() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(None, grid.bounds());
+        assert_eq!("", grid.render(|_, _| '?'));
+
+        grid.insert(Point::new(1, 1), '#');
+        assert_eq!(Some((Point::new(1, 1), Point::new(1, 1))), grid.bounds());
+        grid.insert(Point::new(-1, 2), '#');
+        assert_eq!(Some('#'), grid.insert(Point::new(1, 1), '.'));
+        assert_eq!(2, grid.len());
+        assert_eq!(Some((Point::new(-1, 1), Point::new(1, 2))), grid.bounds());
+        assert_eq!(
+            "  .\n#  \n",
+            grid.render(|_, value| value.copied().unwrap_or(' '))
+        );
+        assert_eq!("  .\n#  \n", grid.to_grid(' ').to_string());
+    }
+
+    #[test]
+    fn distances_through_set_points() {
+        let mut grid = SparseGrid::new();
+        for x in 0..5 {
+            grid.insert(Point::new(x, 0), x != 2);
+            grid.insert(Point::new(x, 1), true);
+        }
+        let distances = grid.distances(Point::ORIGIN, |&open| open);
+        assert_eq!(9, distances.len());
+        assert_eq!(Some(&6), distances.get(&Point::new(4, 0)));
+        assert_eq!(None, distances.get(&Point::new(5, 1)));
+    }
+}
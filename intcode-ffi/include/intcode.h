@@ -0,0 +1,50 @@
+// Generated from intcode-ffi/src/lib.rs, do not edit.
+#ifndef INTCODE_H
+#define INTCODE_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// State codes returned by `intcode_resume`
+#define INTCODE_HALTED 0
+#define INTCODE_NEED_INPUT 1
+#define INTCODE_ERROR -1
+
+// Opaque handle to a computer with its input and output queues.
+typedef struct IntcodeMachine IntcodeMachine;
+
+// Creates a machine running a copy of `program` (`length` values). Returns NULL if `program`
+// is NULL.
+IntcodeMachine *intcode_load(const int64_t *program, size_t length);
+
+// Frees a machine created by `intcode_load`.
+void intcode_free(IntcodeMachine *machine);
+
+// Appends `value` to the input queue.
+void intcode_push_input(IntcodeMachine *machine, int64_t value);
+
+// Runs the program until it halts or needs more input. Returns one of the state codes.
+// After an error (e.g. an invalid opcode), the machine can't be resumed anymore.
+int32_t intcode_resume(IntcodeMachine *machine);
+
+// Removes the oldest output from the output queue and stores it in `value`. Returns false if
+// there is no output.
+bool intcode_pop_output(IntcodeMachine *machine, int64_t *value);
+
+// Reads the memory cell at `address`. Cells that were never written are 0.
+int64_t intcode_peek(const IntcodeMachine *machine, size_t address);
+
+// Writes `value` to the memory cell at `address`. Returns false if the address is beyond the
+// memory limit.
+bool intcode_poke(IntcodeMachine *machine, size_t address, int64_t value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,388 @@
+// C interface to the interpreter, built as a shared library with
+//
+//     cargo build -p intcode-ffi
+//
+// include/intcode.h is generated by `header()` from the declarations listed there, which also
+// document the functions. The types in the header are derived from the Rust signatures. The
+// `header_is_up_to_date` test fails if the header differs; run it with INTCODE_UPDATE_HEADER
+// set to rewrite the header.
+//
+// All functions accept NULL handles and do nothing in that case (or return an error value).
+// Other than that, handles must come from `intcode_load` and must not be used after
+// `intcode_free`. The computer panics on invalid programs; such panics are caught and leave the
+// machine in an error state instead of unwinding into C.
+#![allow(clippy::missing_safety_doc)]
+
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::panic::{self, AssertUnwindSafe};
+use std::{ptr, slice};
+
+use intcode::{Computer, MemoryType, RunState, MAX_MEMORY};
+
+pub const INTCODE_HALTED: i32 = 0;
+pub const INTCODE_NEED_INPUT: i32 = 1;
+pub const INTCODE_ERROR: i32 = -1;
+
+pub struct IntcodeMachine {
+    computer: Computer<VecDeque<MemoryType>, VecDeque<MemoryType>>,
+    failed: bool,
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn intcode_load(program: *const i64, length: usize) -> *mut IntcodeMachine {
+    if program.is_null() {
+        return ptr::null_mut();
+    }
+    let program = slice::from_raw_parts(program, length);
+    Box::into_raw(Box::new(IntcodeMachine {
+        computer: Computer::new(0, program, VecDeque::new(), VecDeque::new()),
+        failed: false,
+    }))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn intcode_free(machine: *mut IntcodeMachine) {
+    if !machine.is_null() {
+        drop(Box::from_raw(machine));
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn intcode_push_input(machine: *mut IntcodeMachine, value: i64) {
+    if let Some(machine) = machine.as_mut() {
+        machine.computer.get_input().push_back(value);
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn intcode_resume(machine: *mut IntcodeMachine) -> i32 {
+    let machine = match machine.as_mut() {
+        Some(machine) if !machine.failed => machine,
+        _ => return INTCODE_ERROR,
+    };
+    let computer = &mut machine.computer;
+    match panic::catch_unwind(AssertUnwindSafe(|| computer.resume())) {
+        Ok(RunState::Stopped(_)) => INTCODE_HALTED,
+        Ok(RunState::NeedInput) => INTCODE_NEED_INPUT,
+        Ok(_) | Err(_) => {
+            machine.failed = true;
+            INTCODE_ERROR
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn intcode_pop_output(machine: *mut IntcodeMachine, value: *mut i64) -> bool {
+    let output = match machine.as_mut() {
+        Some(machine) if !value.is_null() => machine.computer.get_output().pop_front(),
+        _ => None,
+    };
+    match output {
+        Some(output) => {
+            *value = output;
+            true
+        }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn intcode_peek(machine: *const IntcodeMachine, address: usize) -> i64 {
+    match machine.as_ref() {
+        Some(machine) => machine.computer.peek(address),
+        None => 0,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn intcode_poke(
+    machine: *mut IntcodeMachine,
+    address: usize,
+    value: i64,
+) -> bool {
+    match machine.as_mut() {
+        Some(machine) if address < MAX_MEMORY => {
+            machine.computer.poke(address, value);
+            true
+        }
+        _ => false,
+    }
+}
+
+// A Rust type that is passed to or returned from C
+trait CType {
+    fn c_type() -> String;
+}
+
+macro_rules! c_types {
+    ($($rust_type:ty => $c_type:expr),*) => {
+        $(
+            impl CType for $rust_type {
+                fn c_type() -> String {
+                    String::from($c_type)
+                }
+            }
+        )*
+    };
+}
+
+c_types!(
+    () => "void",
+    bool => "bool",
+    i32 => "int32_t",
+    i64 => "int64_t",
+    usize => "size_t",
+    IntcodeMachine => "IntcodeMachine"
+);
+
+impl<T: CType> CType for *const T {
+    fn c_type() -> String {
+        format!("const {} *", T::c_type())
+    }
+}
+
+impl<T: CType> CType for *mut T {
+    fn c_type() -> String {
+        format!("{} *", T::c_type())
+    }
+}
+
+// The signature of a function that is called from C
+trait CFunction {
+    fn parameter_types() -> Vec<String>;
+    fn return_type() -> String;
+}
+
+macro_rules! c_function {
+    ($($parameter:ident),*) => {
+        impl<R: CType, $($parameter: CType),*> CFunction
+            for unsafe extern "C" fn($($parameter),*) -> R
+        {
+            fn parameter_types() -> Vec<String> {
+                vec![$($parameter::c_type()),*]
+            }
+
+            fn return_type() -> String {
+                R::c_type()
+            }
+        }
+    };
+}
+
+c_function!(A);
+c_function!(A, B);
+c_function!(A, B, C);
+
+// Declaration of a function in C, with the given names for its parameters
+fn c_declaration<F: CFunction>(_function: F, name: &str, parameters: &[&str]) -> String {
+    let parameter_types = F::parameter_types();
+    assert_eq!(
+        parameter_types.len(),
+        parameters.len(),
+        "Wrong number of parameter names for {}",
+        name
+    );
+    let parameters: Vec<String> = parameter_types
+        .iter()
+        .zip(parameters)
+        .map(|(parameter_type, parameter)| {
+            if parameter_type.ends_with('*') {
+                format!("{}{}", parameter_type, parameter)
+            } else {
+                format!("{} {}", parameter_type, parameter)
+            }
+        })
+        .collect();
+    let return_type = F::return_type();
+    let separator = if return_type.ends_with('*') { "" } else { " " };
+    format!(
+        "{}{}{}({});",
+        return_type,
+        separator,
+        name,
+        parameters.join(", ")
+    )
+}
+
+macro_rules! inferred {
+    ($parameter:ident) => {
+        _
+    };
+}
+
+// Declares `function(parameters...)`, with the types of its signature
+macro_rules! function {
+    ($function:ident($($parameter:ident),*)) => {
+        c_declaration(
+            $function as unsafe extern "C" fn($(inferred!($parameter)),*) -> _,
+            stringify!($function),
+            &[$(stringify!($parameter)),*],
+        )
+    };
+}
+
+macro_rules! constant {
+    ($constant:ident) => {
+        format!("#define {} {}", stringify!($constant), $constant)
+    };
+}
+
+// Generates the C header: the state codes, the handle type and the functions, each group with
+// the comment in front of it.
+pub fn header() -> String {
+    let declarations = [
+        (
+            "// State codes returned by `intcode_resume`",
+            vec![
+                constant!(INTCODE_HALTED),
+                constant!(INTCODE_NEED_INPUT),
+                constant!(INTCODE_ERROR),
+            ],
+        ),
+        (
+            "// Opaque handle to a computer with its input and output queues.",
+            vec![format!(
+                "typedef struct {0} {0};",
+                IntcodeMachine::c_type()
+            )],
+        ),
+        (
+            "// Creates a machine running a copy of `program` (`length` values). Returns NULL if `program`\n\
+             // is NULL.",
+            vec![function!(intcode_load(program, length))],
+        ),
+        (
+            "// Frees a machine created by `intcode_load`.",
+            vec![function!(intcode_free(machine))],
+        ),
+        (
+            "// Appends `value` to the input queue.",
+            vec![function!(intcode_push_input(machine, value))],
+        ),
+        (
+            "// Runs the program until it halts or needs more input. Returns one of the state codes.\n\
+             // After an error (e.g. an invalid opcode), the machine can't be resumed anymore.",
+            vec![function!(intcode_resume(machine))],
+        ),
+        (
+            "// Removes the oldest output from the output queue and stores it in `value`. Returns false if\n\
+             // there is no output.",
+            vec![function!(intcode_pop_output(machine, value))],
+        ),
+        (
+            "// Reads the memory cell at `address`. Cells that were never written are 0.",
+            vec![function!(intcode_peek(machine, address))],
+        ),
+        (
+            "// Writes `value` to the memory cell at `address`. Returns false if the address is beyond the\n\
+             // memory limit.",
+            vec![function!(intcode_poke(machine, address, value))],
+        ),
+    ];
+
+    let mut header = String::new();
+    let _ = writeln!(
+        header,
+        "// Generated from intcode-ffi/src/lib.rs, do not edit."
+    );
+    let _ = writeln!(header, "#ifndef INTCODE_H");
+    let _ = writeln!(header, "#define INTCODE_H");
+    let _ = writeln!(header);
+    let _ = writeln!(header, "#include <stdbool.h>");
+    let _ = writeln!(header, "#include <stddef.h>");
+    let _ = writeln!(header, "#include <stdint.h>");
+    let _ = writeln!(header);
+    let _ = writeln!(header, "#ifdef __cplusplus");
+    let _ = writeln!(header, "extern \"C\" {{");
+    let _ = writeln!(header, "#endif");
+
+    for (comment, declarations) in &declarations {
+        let _ = writeln!(header);
+        let _ = writeln!(header, "{}", comment);
+        for declaration in declarations {
+            let _ = writeln!(header, "{}", declaration);
+        }
+    }
+
+    let _ = writeln!(header);
+    let _ = writeln!(header, "#ifdef __cplusplus");
+    let _ = writeln!(header, "}}");
+    let _ = writeln!(header, "#endif");
+    let _ = writeln!(header);
+    let _ = writeln!(header, "#endif");
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn header_is_up_to_date() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("include/intcode.h");
+        if env::var_os("INTCODE_UPDATE_HEADER").is_some() {
+            fs::write(&path, header()).unwrap();
+        }
+        assert_eq!(
+            fs::read_to_string(&path).unwrap_or_default(),
+            header(),
+            "include/intcode.h is out of date, run this test with INTCODE_UPDATE_HEADER=1"
+        );
+    }
+
+    #[test]
+    fn declarations() {
+        assert_eq!(
+            "IntcodeMachine *intcode_load(const int64_t *program, size_t length);",
+            function!(intcode_load(program, length))
+        );
+        assert_eq!(
+            "void intcode_free(IntcodeMachine *machine);",
+            function!(intcode_free(machine))
+        );
+        assert_eq!("#define INTCODE_ERROR -1", constant!(INTCODE_ERROR));
+    }
+
+    #[test]
+    fn machine() {
+        let program = [3, 0, 4, 0, 99];
+        unsafe {
+            let machine = intcode_load(program.as_ptr(), program.len());
+            assert_eq!(INTCODE_NEED_INPUT, intcode_resume(machine));
+            intcode_push_input(machine, 42);
+            assert_eq!(INTCODE_HALTED, intcode_resume(machine));
+
+            let mut value = 0;
+            assert!(intcode_pop_output(machine, &mut value));
+            assert_eq!(42, value);
+            assert!(!intcode_pop_output(machine, &mut value));
+
+            assert_eq!(42, intcode_peek(machine, 0));
+            assert!(intcode_poke(machine, 100, 7));
+            assert_eq!(7, intcode_peek(machine, 100));
+            assert!(!intcode_poke(machine, MAX_MEMORY, 7));
+            intcode_free(machine);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let program = [42];
+        unsafe {
+            let machine = intcode_load(program.as_ptr(), program.len());
+            assert_eq!(INTCODE_ERROR, intcode_resume(machine));
+            assert_eq!(INTCODE_ERROR, intcode_resume(machine));
+            intcode_free(machine);
+
+            assert!(intcode_load(ptr::null(), 0).is_null());
+            assert_eq!(INTCODE_ERROR, intcode_resume(ptr::null_mut()));
+            assert_eq!(0, intcode_peek(ptr::null(), 0));
+            intcode_free(ptr::null_mut());
+        }
+    }
+}
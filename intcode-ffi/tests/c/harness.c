@@ -0,0 +1,41 @@
+// Drives the interpreter through the C interface: prints the output of the day 9 quine, then
+// the results of feeding input to an echo program and of reading and writing its memory.
+
+#include <stdio.h>
+
+#include "intcode.h"
+
+static const int64_t QUINE[] = {109, 1,   204, -1,  1001, 100, 1, 100,
+                                1008, 100, 16, 101, 1006, 101, 0, 99};
+
+// in [0], out [0], halt
+static const int64_t ECHO[] = {3, 0, 4, 0, 99};
+
+int main(void) {
+    IntcodeMachine *machine = intcode_load(QUINE, sizeof(QUINE) / sizeof(QUINE[0]));
+    if (intcode_resume(machine) != INTCODE_HALTED) {
+        return 1;
+    }
+    int64_t value;
+    const char *separator = "";
+    while (intcode_pop_output(machine, &value)) {
+        printf("%s%lld", separator, (long long)value);
+        separator = ",";
+    }
+    printf("\n");
+    intcode_free(machine);
+
+    machine = intcode_load(ECHO, sizeof(ECHO) / sizeof(ECHO[0]));
+    printf("%d ", intcode_resume(machine));
+    intcode_push_input(machine, 42);
+    printf("%d ", intcode_resume(machine));
+    if (!intcode_pop_output(machine, &value)) {
+        return 1;
+    }
+    printf("%lld %lld ", (long long)value, (long long)intcode_peek(machine, 0));
+    printf("%d ", intcode_poke(machine, 1000, -7));
+    printf("%lld\n", (long long)intcode_peek(machine, 1000));
+    intcode_free(machine);
+
+    return 0;
+}
@@ -0,0 +1,54 @@
+// Builds the C interface as a shared library, compiles tests/c/harness.c against it with the
+// system C compiler ($CC or cc) and checks what the harness prints.
+
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+fn run(command: &mut Command) -> String {
+    let output = match command.output() {
+        Ok(output) => output,
+        Err(e) => panic!("Failed to run {:?}: {}", command, e),
+    };
+    assert!(
+        output.status.success(),
+        "{:?} failed:\n{}",
+        command,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn quine_from_c() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("c_abi");
+
+    // A separate target directory, so this doesn't wait for the lock of the running build
+    let cargo = env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    run(Command::new(cargo)
+        .current_dir(manifest_dir)
+        .args(["build", "--lib"])
+        .arg("--target-dir")
+        .arg(&target_dir));
+
+    let library_dir = target_dir.join("debug");
+    let harness = target_dir.join("harness");
+    let cc = env::var("CC").unwrap_or_else(|_| String::from("cc"));
+    run(Command::new(cc)
+        .arg(manifest_dir.join("tests/c/harness.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg("-L")
+        .arg(&library_dir)
+        .arg("-lintcode_ffi")
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .arg("-o")
+        .arg(&harness));
+
+    assert_eq!(
+        "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99\n\
+         1 0 42 42 1 -7\n",
+        run(&mut Command::new(&harness))
+    );
+}
@@ -0,0 +1,124 @@
+// Uses the intcode crate without its `std` feature, and runs the example programs on it. Cargo
+// enables the union of the features requested in a workspace, so this crate isn't a member of
+// it. Check the no_std build with `cargo test --manifest-path intcode-no-std/Cargo.toml`.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use intcode::{Computer, MemoryType, RunState};
+
+// Runs `program` with `input` and returns the final state, the memory of the program and
+// the outputs.
+pub fn run(
+    program: &[MemoryType],
+    input: &[MemoryType],
+) -> (RunState, Vec<MemoryType>, Vec<MemoryType>) {
+    let input: VecDeque<MemoryType> = input.iter().copied().collect();
+    let mut computer = Computer::new(0, program, input, Vec::new());
+    let run_state = computer.run_program();
+    let memory = (0..program.len())
+        .map(|address| computer.peek(address))
+        .collect();
+    (run_state, memory, computer.get_output().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use intcode::{compiler, parse_program, BoundedQueue, Termination};
+
+    fn memory(program: &[MemoryType]) -> Vec<MemoryType> {
+        run(program, &[]).1
+    }
+
+    fn output(program: &[MemoryType], input: &[MemoryType]) -> Vec<MemoryType> {
+        run(program, input).2
+    }
+
+    #[test]
+    fn example_programs() {
+        assert_eq!(vec![2, 0, 0, 0, 99], memory(&[1, 0, 0, 0, 99]));
+        assert_eq!(vec![2, 3, 0, 6, 99], memory(&[2, 3, 0, 3, 99]));
+        assert_eq!(vec![2, 4, 4, 5, 99, 9801], memory(&[2, 4, 4, 5, 99, 0]));
+        assert_eq!(
+            vec![30, 1, 1, 4, 2, 5, 6, 0, 99],
+            memory(&[1, 1, 1, 4, 99, 5, 6, 0, 99])
+        );
+    }
+
+    #[test]
+    fn input_output() {
+        assert_eq!(vec![42], output(&[3, 0, 4, 0, 99], &[42]));
+    }
+
+    #[test]
+    fn parameter_modes() {
+        assert_eq!(vec![1002, 4, 3, 4, 99], memory(&[1002, 4, 3, 4, 33]));
+    }
+
+    #[test]
+    fn example_program_5() {
+        let program = [
+            3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0,
+            0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
+            20, 1105, 1, 46, 98, 99,
+        ];
+        assert_eq!(vec![999], output(&program, &[7]));
+        assert_eq!(vec![1000], output(&program, &[8]));
+        assert_eq!(vec![1001], output(&program, &[9]));
+    }
+
+    #[test]
+    fn quine() {
+        let program = [
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+        assert_eq!(program.to_vec(), output(&program, &[]));
+    }
+
+    #[test]
+    fn large_numbers() {
+        assert_eq!(
+            vec![1219070632396864],
+            output(&[1102, 34915192, 34915192, 7, 4, 7, 99, 0], &[])
+        );
+        assert_eq!(
+            vec![1125899906842624],
+            output(&[104, 1125899906842624, 99], &[])
+        );
+    }
+
+    #[test]
+    fn need_input() {
+        let (run_state, _, _) = run(&[3, 0, 99], &[]);
+        assert_eq!(RunState::NeedInput, run_state);
+    }
+
+    #[test]
+    fn bounded_output_and_report() {
+        let program = parse_program("104,1,104,2,99").unwrap();
+        let mut computer = Computer::new(0, &program, VecDeque::new(), BoundedQueue::new(1));
+        assert_eq!(RunState::OutputFull { ip: 2 }, computer.run_program());
+        assert_eq!(Some(1), computer.get_output().pop_front());
+        assert_eq!(RunState::Stopped(2), computer.resume());
+
+        let report = computer.report();
+        assert_eq!(Termination::Halted, report.termination);
+        assert_eq!(3, report.instructions);
+    }
+
+    #[test]
+    fn compiled_program() {
+        let program = compiler::compile(
+            "fn square(x) { return x * x; }
+             fn main() { output(square(input())); }",
+        )
+        .unwrap();
+        assert_eq!(vec![49], output(&program, &[7]));
+    }
+}
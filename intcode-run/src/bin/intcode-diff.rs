@@ -0,0 +1,32 @@
+use std::env;
+
+const USAGE: &str = "Usage: intcode-diff <old program> <new program>
+
+Prints the structural differences between two programs. Operands that only changed because the
+code or data they refer to moved are counted, but not listed. Exits with 1 if the programs differ
+and with 2 on errors.";
+
+fn main() {
+    let paths: Vec<String> = env::args().skip(1).collect();
+    if paths.len() != 2 {
+        println!("{}", USAGE);
+        std::process::exit(2);
+    }
+
+    let mut programs = Vec::new();
+    for path in &paths {
+        match intcode::load_program(path) {
+            Ok(program) => programs.push(program),
+            Err(e) => {
+                println!("Error reading {}: {}", path, e);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    let diff = intcode::diff::diff(&programs[0], &programs[1]);
+    println!("{}", diff);
+    if !diff.is_empty() {
+        std::process::exit(1);
+    }
+}
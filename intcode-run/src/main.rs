@@ -0,0 +1,408 @@
+use std::collections::VecDeque;
+use std::env;
+use std::io::{self, BufRead, Write};
+
+use intcode::{Computer, MemoryType, Output, RunReport, RunState, MAX_MEMORY};
+
+const USAGE: &str = "Usage: intcode-run <program> [options]
+
+Options:
+    --input 1,2,3        Input values, before any input from stdin
+    --ascii              Read the input from stdin as ASCII text
+    --interactive        Read stdin one line at a time, whenever the program needs input
+                         (comma separated numbers, or text with --ascii)
+    --patch ADDR=VALUE   Overwrite a memory cell before running (can be repeated)
+    --limit N            Stop after N instructions
+    --output FORMAT      numbers (default), ascii, or json (outputs and run statistics)";
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum OutputFormat {
+    Numbers,
+    // Values in the ASCII range are printed as characters, other values as numbers.
+    Ascii,
+    // All outputs are collected and printed together with the run report at the end.
+    Json,
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    program: String,
+    input: Vec<MemoryType>,
+    ascii: bool,
+    interactive: bool,
+    patches: Vec<(usize, MemoryType)>,
+    limit: Option<u64>,
+    format: OutputFormat,
+}
+
+fn main() {
+    let options = match parse_options(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            println!("{}\n\n{}", e, USAGE);
+            std::process::exit(1);
+        }
+    };
+
+    let program = match intcode::load_program(&options.program) {
+        Ok(program) => program,
+        Err(e) => {
+            println!("Error reading input: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    if let Err(e) = execute(&options, &program, stdin.lock(), stdout.lock()) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn parse_values(values: &str) -> Result<Vec<MemoryType>, String> {
+    values
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| format!("Invalid value: {}", value))
+        })
+        .collect()
+}
+
+fn ascii_values(text: &str) -> impl Iterator<Item = MemoryType> + '_ {
+    text.chars().filter(|&c| c != '\r').map(|c| c as MemoryType)
+}
+
+fn parse_options<I: Iterator<Item = String>>(mut args: I) -> Result<Options, String> {
+    fn value<I: Iterator<Item = String>>(args: &mut I, option: &str) -> Result<String, String> {
+        args.next()
+            .ok_or_else(|| format!("Missing value for {}", option))
+    }
+
+    let mut program = None;
+    let mut options = Options {
+        program: String::new(),
+        input: Vec::new(),
+        ascii: false,
+        interactive: false,
+        patches: Vec::new(),
+        limit: None,
+        format: OutputFormat::Numbers,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => options
+                .input
+                .extend(parse_values(&value(&mut args, &arg)?)?),
+            "--ascii" => options.ascii = true,
+            "--interactive" => options.interactive = true,
+            "--patch" => {
+                let patch = value(&mut args, &arg)?;
+                let mut parts = patch.splitn(2, '=');
+                let address = parts
+                    .next()
+                    .and_then(|address| address.parse().ok())
+                    .filter(|&address| address < MAX_MEMORY);
+                let value = parts.next().and_then(|value| value.parse().ok());
+                match (address, value) {
+                    (Some(address), Some(value)) => options.patches.push((address, value)),
+                    _ => return Err(format!("Invalid patch: {}", patch)),
+                }
+            }
+            "--limit" => {
+                let limit = value(&mut args, &arg)?;
+                match limit.parse() {
+                    Ok(limit) => options.limit = Some(limit),
+                    Err(_) => return Err(format!("Invalid instruction limit: {}", limit)),
+                }
+            }
+            "--output" => {
+                options.format = match value(&mut args, &arg)?.as_str() {
+                    "numbers" => OutputFormat::Numbers,
+                    "ascii" => OutputFormat::Ascii,
+                    "json" => OutputFormat::Json,
+                    format => return Err(format!("Unknown output format: {}", format)),
+                }
+            }
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {}", arg)),
+            _ if program.is_none() => program = Some(arg),
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+
+    match program {
+        Some(program) => {
+            options.program = program;
+            Ok(options)
+        }
+        None => Err(String::from("Please supply a program file!")),
+    }
+}
+
+// Writes the outputs of the program in the selected format as they are produced.
+struct FormattedOutput<W: Write> {
+    writer: W,
+    format: OutputFormat,
+    values: Vec<MemoryType>,
+}
+
+impl<W: Write> Output<MemoryType> for FormattedOutput<W> {
+    type WriteError = io::Error;
+
+    fn write(&mut self, value: MemoryType) -> Result<(), Self::WriteError> {
+        match self.format {
+            OutputFormat::Numbers => writeln!(self.writer, "{}", value),
+            OutputFormat::Ascii if (0..128).contains(&value) => {
+                write!(self.writer, "{}", value as u8 as char)
+            }
+            OutputFormat::Ascii => writeln!(self.writer, "{}", value),
+            OutputFormat::Json => {
+                self.values.push(value);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn to_json(outputs: &[MemoryType], report: &RunReport) -> String {
+    let outputs: Vec<String> = outputs.iter().map(|value| value.to_string()).collect();
+    format!(
+        "{{\"outputs\":[{}],\"stats\":{{\"termination\":\"{}\",\"instructions\":{},\"ip\":{},\
+         \"relative_base\":{},\"memory_size\":{},\"inputs\":{},\"outputs\":{},\
+         \"wall_time_us\":{}}}}}",
+        outputs.join(","),
+        report.termination,
+        report.instructions,
+        report.ip,
+        report.relative_base,
+        report.memory_size,
+        report.inputs,
+        report.outputs,
+        report.wall_time.as_micros()
+    )
+}
+
+// Runs `program` until it halts. Returns an error if it stops for any other reason.
+fn execute<R: BufRead, W: Write>(
+    options: &Options,
+    program: &[MemoryType],
+    mut stdin: R,
+    stdout: W,
+) -> Result<(), String> {
+    let mut program = program.to_vec();
+    for &(address, value) in &options.patches {
+        if address >= program.len() {
+            program.resize(address + 1, 0);
+        }
+        program[address] = value;
+    }
+
+    let mut input: VecDeque<MemoryType> = options.input.iter().copied().collect();
+    if options.ascii && !options.interactive {
+        let mut text = String::new();
+        if let Err(e) = stdin.read_to_string(&mut text) {
+            return Err(format!("Error reading stdin: {}", e));
+        }
+        input.extend(ascii_values(&text));
+    }
+
+    let output = FormattedOutput {
+        writer: stdout,
+        format: options.format,
+        values: Vec::new(),
+    };
+    let mut computer = Computer::new(0, &program, input, output);
+    computer.set_instruction_limit(options.limit);
+
+    let run_state = loop {
+        match computer.resume() {
+            RunState::NeedInput if options.interactive => {
+                // Make sure the prompt is visible before waiting for the user
+                let _ = computer.get_output().writer.flush();
+                let mut line = String::new();
+                match stdin.read_line(&mut line) {
+                    Ok(0) => break RunState::NeedInput,
+                    Ok(_) => {}
+                    Err(e) => return Err(format!("Error reading stdin: {}", e)),
+                }
+                if options.ascii {
+                    if !line.ends_with('\n') {
+                        line.push('\n');
+                    }
+                    computer.get_input().extend(ascii_values(&line));
+                } else {
+                    match parse_values(&line) {
+                        Ok(values) => computer.get_input().extend(values),
+                        Err(e) => eprintln!("{}", e),
+                    }
+                }
+            }
+            run_state => break run_state,
+        }
+    };
+
+    let report = computer.report();
+    let output = computer.get_output();
+    if options.format == OutputFormat::Json {
+        let json = to_json(&output.values, &report);
+        let _ = writeln!(output.writer, "{}", json);
+    }
+    let _ = output.writer.flush();
+
+    match run_state {
+        RunState::Stopped(_) => Ok(()),
+        RunState::NeedInput => Err(format!("Program needs more input (at ip {})", report.ip)),
+        RunState::InstructionLimit { ip } => {
+            Err(format!("Instruction limit reached (at ip {})", ip))
+        }
+        RunState::InputFailed { .. } => match computer.take_read_error() {
+            Some(e) => Err(format!("Error reading input: {}", e)),
+            None => Err(String::from("Error reading input")),
+        },
+        RunState::OutputFailed { .. } => match computer.take_write_error() {
+            Some(e) => Err(format!("Error writing output: {}", e)),
+            None => Err(String::from("Error writing output")),
+        },
+        RunState::NotYetStarted | RunState::Breakpoint { .. } | RunState::OutputFull { .. } => {
+            unreachable!()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUINE: [MemoryType; 16] = [
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+
+    // Echoes its input up to and including the first newline.
+    const ECHO: [MemoryType; 12] = [3, 100, 4, 100, 1008, 100, 10, 101, 1006, 101, 0, 99];
+
+    // Outputs twice its input.
+    const DOUBLE: [MemoryType; 10] = [3, 9, 102, 2, 9, 9, 4, 9, 99, 0];
+
+    fn options(args: &str) -> Options {
+        parse_options(args.split_whitespace().map(String::from)).unwrap()
+    }
+
+    fn run(args: &str, program: &[MemoryType], stdin: &str) -> (Result<(), String>, String) {
+        let mut stdout = Vec::new();
+        let result = execute(&options(args), program, stdin.as_bytes(), &mut stdout);
+        (result, String::from_utf8(stdout).unwrap())
+    }
+
+    #[test]
+    fn parse_arguments() {
+        assert_eq!(
+            Options {
+                program: String::from("input.txt"),
+                input: vec![1, 2, 3, -4],
+                ascii: true,
+                interactive: false,
+                patches: vec![(0, 2), (5, -1)],
+                limit: Some(1000),
+                format: OutputFormat::Json,
+            },
+            options(
+                "--input 1,2 --ascii input.txt --input 3,-4 --patch 0=2 --patch 5=-1 \
+                 --limit 1000 --output json"
+            )
+        );
+
+        let error = |args: &str| parse_options(args.split_whitespace().map(String::from));
+        assert_eq!(
+            Err(String::from("Please supply a program file!")),
+            error("")
+        );
+        assert_eq!(
+            Err(String::from("Invalid value: x")),
+            error("a --input 1,x")
+        );
+        assert_eq!(Err(String::from("Invalid patch: 0")), error("a --patch 0"));
+        assert_eq!(
+            Err(String::from("Invalid patch: 99999999999=1")),
+            error("a --patch 99999999999=1")
+        );
+        assert_eq!(
+            Err(String::from("Missing value for --limit")),
+            error("a --limit")
+        );
+        assert_eq!(
+            Err(String::from("Unknown output format: hex")),
+            error("a --output hex")
+        );
+        assert_eq!(Err(String::from("Unexpected argument: b")), error("a b"));
+    }
+
+    #[test]
+    fn output_formats() {
+        let quine: Vec<String> = QUINE.iter().map(|value| value.to_string()).collect();
+        assert_eq!((Ok(()), quine.join("\n") + "\n"), run("a", &QUINE, ""));
+
+        assert_eq!(
+            (Ok(()), String::from("hi\n")),
+            run("a --input 104,105,10 --output ascii", &ECHO, "")
+        );
+        assert_eq!(
+            (Ok(()), String::from("a233\n\n")),
+            run("a --ascii --output ascii", &ECHO, "aé\r\n")
+        );
+
+        let (result, json) = run("a --input 21 --output json", &DOUBLE, "");
+        assert_eq!(Ok(()), result);
+        assert!(json.starts_with(
+            "{\"outputs\":[42],\"stats\":{\"termination\":\"halted\",\"instructions\":4,\"ip\":8,\
+             \"relative_base\":0,\"memory_size\":10,\"inputs\":1,\"outputs\":1,\"wall_time_us\":"
+        ));
+    }
+
+    #[test]
+    fn interactive_input() {
+        assert_eq!(
+            (Ok(()), String::from("ok\n")),
+            run(
+                "a --ascii --interactive --output ascii",
+                &ECHO,
+                "ok\nignored\n"
+            )
+        );
+        assert_eq!(
+            (Ok(()), String::from("14\n")),
+            run("a --interactive", &DOUBLE, "\n7\n")
+        );
+        assert_eq!(
+            (
+                Err(String::from("Program needs more input (at ip 0)")),
+                String::new()
+            ),
+            run("a --interactive", &DOUBLE, "")
+        );
+    }
+
+    #[test]
+    fn patches_and_limit() {
+        // Doubling becomes tripling
+        assert_eq!(
+            (Ok(()), String::from("21\n")),
+            run("a --input 7 --patch 3=3", &DOUBLE, "")
+        );
+        assert_eq!(
+            (Ok(()), String::from("4\n")),
+            run("a --patch 0=104 --patch 1=4 --patch 2=99", &DOUBLE, "")
+        );
+
+        let (result, output) = run("a --limit 10", &QUINE, "");
+        assert_eq!(
+            Err(String::from("Instruction limit reached (at ip 0)")),
+            result
+        );
+        assert_eq!("109\n1\n", output);
+    }
+}
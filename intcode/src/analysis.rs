@@ -0,0 +1,173 @@
+// Static discovery of the code of a program: starting at address 0, follows all jumps with
+// immediate targets and the return addresses pushed before calls.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::instruction::{Instruction, Opcode, ParameterMode};
+use crate::MemoryType;
+
+pub(crate) struct Analysis {
+    pub(crate) instructions: BTreeMap<usize, Instruction>,
+    pub(crate) invalid: BTreeSet<usize>,
+    pub(crate) leaders: BTreeSet<usize>,
+}
+
+impl Analysis {
+    pub(crate) fn new(program: &[MemoryType]) -> Self {
+        let mut analysis = Self {
+            instructions: BTreeMap::new(),
+            invalid: BTreeSet::new(),
+            leaders: BTreeSet::new(),
+        };
+
+        analysis.explore(program, 0);
+
+        // Return addresses are usually stored as an immediate value right before the jump
+        // to a function, and they point right behind an unconditional jump. Treat such values
+        // as additional entry points, until no new ones are found.
+        loop {
+            let after_jumps: BTreeSet<usize> = analysis
+                .instructions
+                .values()
+                .filter(|instruction| Self::is_unconditional_jump(instruction))
+                .map(|instruction| instruction.next_address())
+                .collect();
+            let candidates: Vec<usize> = analysis
+                .instructions
+                .values()
+                .filter_map(Self::constant_move)
+                .filter(|&value| value >= 0 && (value as usize) < program.len())
+                .map(|value| value as usize)
+                .filter(|address| {
+                    after_jumps.contains(address) && !analysis.leaders.contains(address)
+                })
+                .collect();
+
+            if candidates.is_empty() {
+                break;
+            }
+            for address in candidates {
+                analysis.explore(program, address);
+            }
+        }
+
+        analysis
+    }
+
+    fn explore(&mut self, program: &[MemoryType], start: usize) {
+        let mut worklist = vec![start];
+        self.leaders.insert(start);
+
+        while let Some(address) = worklist.pop() {
+            if self.instructions.contains_key(&address) || self.invalid.contains(&address) {
+                continue;
+            }
+            let instruction = match Instruction::decode(program, address) {
+                Ok(instruction) => instruction,
+                Err(_) => {
+                    self.invalid.insert(address);
+                    continue;
+                }
+            };
+            self.instructions.insert(address, instruction);
+
+            let parameters = instruction.parameters();
+            match instruction.opcode {
+                Opcode::JumpIfTrue | Opcode::JumpIfFalse => {
+                    let target = match parameters[1].mode {
+                        ParameterMode::Immediate => Some(parameters[1].value as usize),
+                        _ => None,
+                    };
+                    if let Some(target) = target {
+                        if Self::jump_condition(&instruction) != Some(false) {
+                            self.leaders.insert(target);
+                            worklist.push(target);
+                        }
+                    }
+                    if Self::jump_condition(&instruction) != Some(true) {
+                        self.leaders.insert(instruction.next_address());
+                        worklist.push(instruction.next_address());
+                    }
+                }
+                Opcode::Halt => {}
+                Opcode::Input => {
+                    // Execution resumes at input instructions after the program yielded
+                    self.leaders.insert(address);
+                    worklist.push(instruction.next_address());
+                }
+                _ => worklist.push(instruction.next_address()),
+            }
+        }
+    }
+
+    // Statically known outcome of a conditional jump (if the condition is an immediate).
+    pub(crate) fn jump_condition(instruction: &Instruction) -> Option<bool> {
+        let condition = match instruction.opcode {
+            Opcode::JumpIfTrue | Opcode::JumpIfFalse => instruction.parameters()[0],
+            _ => return None,
+        };
+        match (condition.mode, instruction.opcode) {
+            (ParameterMode::Immediate, Opcode::JumpIfTrue) => Some(condition.value != 0),
+            (ParameterMode::Immediate, _) => Some(condition.value == 0),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_unconditional_jump(instruction: &Instruction) -> bool {
+        Self::jump_condition(instruction) == Some(true)
+    }
+
+    // Value of `add X, 0, dst` / `mul X, 1, dst` style instructions with immediate operands.
+    pub(crate) fn constant_move(instruction: &Instruction) -> Option<MemoryType> {
+        let parameters = instruction.parameters();
+        let neutral = match instruction.opcode {
+            Opcode::Add => 0,
+            Opcode::Multiply => 1,
+            _ => return None,
+        };
+        if parameters[0].mode != ParameterMode::Immediate
+            || parameters[1].mode != ParameterMode::Immediate
+        {
+            return None;
+        }
+        if parameters[1].value == neutral {
+            Some(parameters[0].value)
+        } else if parameters[0].value == neutral {
+            Some(parameters[1].value)
+        } else {
+            None
+        }
+    }
+
+    // Cells whose values are baked into the generated code: the opcodes (with the parameter
+    // modes) of all discovered instructions, and the immediate conditions and targets of
+    // jumps. Writing to any of them makes the compiled code invalid.
+    pub(crate) fn code_ranges(&self) -> Vec<(usize, usize)> {
+        let mut cells = BTreeSet::new();
+        for instruction in self.instructions.values() {
+            cells.insert(instruction.address);
+            if let Opcode::JumpIfTrue | Opcode::JumpIfFalse = instruction.opcode {
+                let condition = Self::jump_condition(instruction);
+                if condition.is_some() {
+                    cells.insert(instruction.address + 1);
+                }
+                if condition != Some(false)
+                    && instruction.parameters()[1].mode == ParameterMode::Immediate
+                {
+                    cells.insert(instruction.address + 2);
+                }
+            }
+        }
+
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        for cell in cells {
+            match ranges.last_mut() {
+                Some(last) if cell == last.1 => last.1 += 1,
+                _ => ranges.push((cell, cell + 1)),
+            }
+        }
+        ranges
+    }
+}
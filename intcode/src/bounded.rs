@@ -0,0 +1,105 @@
+use alloc::collections::VecDeque;
+use alloc::string::String;
+
+use crate::{Input, Output, TryReadError, TryWriteError};
+
+// A queue that holds at most `capacity` values. A computer writing to a full queue yields with
+// `RunState::OutputFull` instead of letting the queue grow without limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundedQueue<T> {
+    queue: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> BoundedQueue<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            queue: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.queue.len() >= self.capacity
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.queue.pop_front()
+    }
+
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.queue.drain(..)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.queue.iter()
+    }
+}
+
+impl<T> Input<T> for BoundedQueue<T> {
+    type ReadError = String;
+
+    fn read(&mut self) -> Result<T, Self::ReadError> {
+        match self.queue.pop_front() {
+            Some(t) => Ok(t),
+            None => Err(String::from("Queue is empty.")),
+        }
+    }
+
+    fn try_read(&mut self) -> Result<T, TryReadError<Self::ReadError>> {
+        self.queue.pop_front().ok_or(TryReadError::Empty)
+    }
+}
+
+impl<T> Output<T> for BoundedQueue<T> {
+    // The value that didn't fit into the queue.
+    type WriteError = T;
+
+    fn write(&mut self, t: T) -> Result<(), Self::WriteError> {
+        if self.is_full() {
+            return Err(t);
+        }
+        self.queue.push_back(t);
+        Ok(())
+    }
+
+    fn try_write(&mut self, t: T) -> Result<(), TryWriteError<Self::WriteError>> {
+        if self.is_full() {
+            return Err(TryWriteError::Full);
+        }
+        self.queue.push_back(t);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounded_queue() {
+        let mut queue = BoundedQueue::new(2);
+        assert_eq!(Ok(()), queue.try_write(1));
+        assert_eq!(Ok(()), queue.write(2));
+        assert!(queue.is_full());
+        assert_eq!(Err(TryWriteError::Full), queue.try_write(3));
+        assert_eq!(Err(3), queue.write(3));
+
+        assert_eq!(Ok(1), queue.try_read());
+        assert_eq!(Ok(()), queue.try_write(3));
+        assert_eq!(vec![2, 3], queue.drain().collect::<Vec<_>>());
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,64 @@
+use std::sync::mpsc::{
+    Receiver, RecvError, SendError, Sender, SyncSender, TryRecvError, TrySendError,
+};
+
+use crate::{Input, Output, TryReadError, TryWriteError};
+
+impl<T> Input<T> for Receiver<T> {
+    type ReadError = RecvError;
+
+    fn read(&mut self) -> Result<T, Self::ReadError> {
+        self.recv()
+    }
+
+    // A disconnected channel fails instead of waiting for input that will never arrive.
+    fn try_read(&mut self) -> Result<T, TryReadError<Self::ReadError>> {
+        match self.try_recv() {
+            Ok(t) => Ok(t),
+            Err(TryRecvError::Empty) => Err(TryReadError::Empty),
+            Err(TryRecvError::Disconnected) => Err(TryReadError::Failed(RecvError)),
+        }
+    }
+}
+
+impl<T> Output<T> for Sender<T> {
+    type WriteError = SendError<T>;
+
+    fn write(&mut self, t: T) -> Result<(), Self::WriteError> {
+        self.send(t)
+    }
+}
+
+// Bounded channel: a full channel makes the computer yield instead of blocking.
+impl<T> Output<T> for SyncSender<T> {
+    type WriteError = SendError<T>;
+
+    fn write(&mut self, t: T) -> Result<(), Self::WriteError> {
+        self.send(t)
+    }
+
+    fn try_write(&mut self, t: T) -> Result<(), TryWriteError<Self::WriteError>> {
+        match self.try_send(t) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => Err(TryWriteError::Full),
+            Err(TrySendError::Disconnected(t)) => Err(TryWriteError::Failed(SendError(t))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::mpsc::{Receiver, SyncSender};
+
+    use crate::{Computer, MemoryType};
+
+    fn assert_send<T: Send>() {}
+
+    // Computers connected by channels are run on their own threads.
+    #[test]
+    fn computers_are_send() {
+        assert_send::<Computer<VecDeque<MemoryType>, Vec<MemoryType>>>();
+        assert_send::<Computer<Receiver<MemoryType>, SyncSender<MemoryType>>>();
+    }
+}
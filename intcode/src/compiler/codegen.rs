@@ -0,0 +1,474 @@
+// Code generation. The relative base is used as the frame pointer of the current function; the
+// frame of a function looks like this:
+//
+//     [rb+0]       return address
+//     [rb+1]       return value
+//     [rb+2]...    parameters, then local variables, then temporaries
+//
+// To call a function, the caller writes the arguments and the return address right behind its
+// own frame, moves the relative base there and jumps to the function. The callee returns by
+// jumping to [rb+0], after which the caller moves the relative base back and picks up the
+// return value. The stack starts right behind the program.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{BinaryOperator, CompileError, Expression, Function, Position, Program, Statement};
+use crate::instruction::Opcode;
+use crate::MemoryType;
+
+const RETURN_ADDRESS: MemoryType = 0;
+const RETURN_VALUE: MemoryType = 1;
+const FIRST_PARAMETER: MemoryType = 2;
+
+type Label = usize;
+
+#[derive(Debug, Copy, Clone)]
+enum Operand {
+    Immediate(MemoryType),
+    Label(Label),
+    // [rb+slot] in the frame of the current function.
+    Slot(MemoryType),
+    // [rb+slot] in the frame of a function called by the current one.
+    CalleeSlot(MemoryType),
+    // Size of the current frame, multiplied by the factor (1 or -1).
+    FrameSize(MemoryType),
+}
+
+struct Generator<'a> {
+    code: Vec<MemoryType>,
+    labels: Vec<Option<usize>>,
+    label_uses: Vec<(usize, Label)>,
+    functions: BTreeMap<&'a str, (Label, usize)>,
+
+    // State of the function that is being generated
+    variables: BTreeMap<&'a str, MemoryType>,
+    declared: BTreeSet<&'a str>,
+    next_temporary: MemoryType,
+    frame_size: MemoryType,
+    // Cells that depend on the frame size, which is only known once the function is done: the
+    // address, the factor of the frame size and the offset to add.
+    frame_uses: Vec<(usize, MemoryType, MemoryType)>,
+}
+
+impl<'a> Generator<'a> {
+    fn new_label(&mut self) -> Label {
+        self.labels.push(None);
+        self.labels.len() - 1
+    }
+
+    fn place_label(&mut self, label: Label) {
+        self.labels[label] = Some(self.code.len());
+    }
+
+    fn emit(&mut self, opcode: Opcode, operands: &[Operand]) {
+        let mut value = MemoryType::from(opcode.value());
+        let mut mode_factor = 100;
+        for operand in operands {
+            let mode = match operand {
+                Operand::Immediate(_) | Operand::Label(_) | Operand::FrameSize(_) => 1,
+                Operand::Slot(_) | Operand::CalleeSlot(_) => 2,
+            };
+            value += mode * mode_factor;
+            mode_factor *= 10;
+        }
+        self.code.push(value);
+
+        for &operand in operands {
+            let address = self.code.len();
+            let value = match operand {
+                Operand::Immediate(value) | Operand::Slot(value) => value,
+                Operand::Label(label) => {
+                    self.label_uses.push((address, label));
+                    0
+                }
+                Operand::CalleeSlot(slot) => {
+                    self.frame_uses.push((address, 1, slot));
+                    0
+                }
+                Operand::FrameSize(factor) => {
+                    self.frame_uses.push((address, factor, 0));
+                    0
+                }
+            };
+            self.code.push(value);
+        }
+    }
+
+    fn copy(&mut self, from: Operand, to: Operand) {
+        self.emit(Opcode::Add, &[from, Operand::Immediate(0), to]);
+    }
+
+    fn jump(&mut self, label: Label) {
+        self.emit(
+            Opcode::JumpIfTrue,
+            &[Operand::Immediate(1), Operand::Label(label)],
+        );
+    }
+
+    fn allocate_temporary(&mut self) -> MemoryType {
+        let slot = self.next_temporary;
+        self.next_temporary += 1;
+        self.frame_size = self.frame_size.max(self.next_temporary);
+        slot
+    }
+
+    fn free_temporary(&mut self) {
+        self.next_temporary -= 1;
+    }
+
+    fn function(&mut self, function: &'a Function) -> Result<(), CompileError> {
+        let label = self.functions[function.name.as_str()].0;
+        self.place_label(label);
+
+        self.variables.clear();
+        self.declared.clear();
+        let mut slot = FIRST_PARAMETER;
+        for parameter in &function.parameters {
+            self.variables.insert(parameter, slot);
+            self.declared.insert(parameter);
+            slot += 1;
+        }
+
+        // Local variables get their slots up front, and are set to 0 on entry so that frames
+        // of earlier calls don't leak into this one.
+        let mut locals = Vec::new();
+        collect_locals(&function.body, &mut locals);
+        for local in locals {
+            if !self.variables.contains_key(local) {
+                self.variables.insert(local, slot);
+                self.copy(Operand::Immediate(0), Operand::Slot(slot));
+                slot += 1;
+            }
+        }
+        self.next_temporary = slot;
+        self.frame_size = slot;
+        self.frame_uses.clear();
+
+        for statement in &function.body {
+            self.statement(statement)?;
+        }
+        self.copy(Operand::Immediate(0), Operand::Slot(RETURN_VALUE));
+        self.emit(
+            Opcode::JumpIfTrue,
+            &[Operand::Immediate(1), Operand::Slot(RETURN_ADDRESS)],
+        );
+
+        for &(address, factor, offset) in &self.frame_uses {
+            self.code[address] = factor * self.frame_size + offset;
+        }
+        Ok(())
+    }
+
+    fn statement(&mut self, statement: &'a Statement) -> Result<(), CompileError> {
+        match statement {
+            Statement::Let(name, value) => {
+                // The value is evaluated before the variable is declared, so "let x = x" only
+                // works if there already is an x.
+                let slot = self.variables[name.as_str()];
+                self.assign(slot, value)?;
+                self.declared.insert(name);
+            }
+            Statement::Assign(name, value, position) => {
+                let slot = self.variable(name, *position)?;
+                self.assign(slot, value)?;
+            }
+            Statement::If(condition, then_branch, else_branch) => {
+                let else_label = self.new_label();
+                let end_label = self.new_label();
+                self.condition(condition, else_label)?;
+                for statement in then_branch {
+                    self.statement(statement)?;
+                }
+                if !else_branch.is_empty() {
+                    self.jump(end_label);
+                }
+                self.place_label(else_label);
+                for statement in else_branch {
+                    self.statement(statement)?;
+                }
+                self.place_label(end_label);
+            }
+            Statement::While(condition, body) => {
+                let start_label = self.new_label();
+                let end_label = self.new_label();
+                self.place_label(start_label);
+                self.condition(condition, end_label)?;
+                for statement in body {
+                    self.statement(statement)?;
+                }
+                self.jump(start_label);
+                self.place_label(end_label);
+            }
+            Statement::Return(value) => {
+                match value {
+                    Some(value) => self.expression(value, RETURN_VALUE)?,
+                    None => self.copy(Operand::Immediate(0), Operand::Slot(RETURN_VALUE)),
+                }
+                self.emit(
+                    Opcode::JumpIfTrue,
+                    &[Operand::Immediate(1), Operand::Slot(RETURN_ADDRESS)],
+                );
+            }
+            Statement::Expression(expression) => {
+                let temporary = self.allocate_temporary();
+                self.expression(expression, temporary)?;
+                self.free_temporary();
+            }
+        }
+        Ok(())
+    }
+
+    fn variable(&self, name: &str, position: Position) -> Result<MemoryType, CompileError> {
+        match self.variables.get(name) {
+            Some(&slot) if self.declared.contains(name) => Ok(slot),
+            _ => Err(CompileError::new(
+                position,
+                format!("Undefined variable: {}", name),
+            )),
+        }
+    }
+
+    // Evaluates into a temporary first, since the value may depend on the variable itself.
+    fn assign(&mut self, slot: MemoryType, value: &'a Expression) -> Result<(), CompileError> {
+        let temporary = self.allocate_temporary();
+        self.expression(value, temporary)?;
+        self.copy(Operand::Slot(temporary), Operand::Slot(slot));
+        self.free_temporary();
+        Ok(())
+    }
+
+    // Jumps to `label` if `condition` is false.
+    fn condition(&mut self, condition: &'a Expression, label: Label) -> Result<(), CompileError> {
+        let temporary = self.allocate_temporary();
+        self.expression(condition, temporary)?;
+        self.emit(
+            Opcode::JumpIfFalse,
+            &[Operand::Slot(temporary), Operand::Label(label)],
+        );
+        self.free_temporary();
+        Ok(())
+    }
+
+    // Evaluates `expression` into [rb+target]. Only slots from the next temporary on are used
+    // as scratch space.
+    fn expression(
+        &mut self,
+        expression: &'a Expression,
+        target: MemoryType,
+    ) -> Result<(), CompileError> {
+        let result = Operand::Slot(target);
+        match expression {
+            Expression::Number(value) => self.copy(Operand::Immediate(*value), result),
+            Expression::Variable(name, position) => {
+                let slot = self.variable(name, *position)?;
+                self.copy(Operand::Slot(slot), result);
+            }
+            Expression::Negate(operand) => {
+                self.expression(operand, target)?;
+                self.emit(Opcode::Multiply, &[result, Operand::Immediate(-1), result]);
+            }
+            Expression::Binary(operator, lhs, rhs) => {
+                self.expression(lhs, target)?;
+                let temporary = self.allocate_temporary();
+                self.expression(rhs, temporary)?;
+                self.binary(*operator, result, Operand::Slot(temporary));
+                self.free_temporary();
+            }
+            Expression::Call(name, arguments, position) => {
+                self.call(name, arguments, *position, target)?
+            }
+        }
+        Ok(())
+    }
+
+    // result = result <operator> rhs
+    fn binary(&mut self, operator: BinaryOperator, result: Operand, rhs: Operand) {
+        let zero = Operand::Immediate(0);
+        match operator {
+            BinaryOperator::Add => self.emit(Opcode::Add, &[result, rhs, result]),
+            BinaryOperator::Subtract => {
+                self.emit(Opcode::Multiply, &[rhs, Operand::Immediate(-1), rhs]);
+                self.emit(Opcode::Add, &[result, rhs, result]);
+            }
+            BinaryOperator::Multiply => self.emit(Opcode::Multiply, &[result, rhs, result]),
+            BinaryOperator::Less => self.emit(Opcode::LessThan, &[result, rhs, result]),
+            BinaryOperator::Greater => self.emit(Opcode::LessThan, &[rhs, result, result]),
+            BinaryOperator::LessEqual => {
+                self.emit(Opcode::LessThan, &[rhs, result, result]);
+                self.emit(Opcode::Equals, &[result, zero, result]);
+            }
+            BinaryOperator::GreaterEqual => {
+                self.emit(Opcode::LessThan, &[result, rhs, result]);
+                self.emit(Opcode::Equals, &[result, zero, result]);
+            }
+            BinaryOperator::Equal => self.emit(Opcode::Equals, &[result, rhs, result]),
+            BinaryOperator::NotEqual => {
+                self.emit(Opcode::Equals, &[result, rhs, result]);
+                self.emit(Opcode::Equals, &[result, zero, result]);
+            }
+        }
+    }
+
+    fn call(
+        &mut self,
+        name: &str,
+        arguments: &'a [Expression],
+        position: Position,
+        target: MemoryType,
+    ) -> Result<(), CompileError> {
+        let (label, parameter_count) = match name {
+            "input" => (None, 0),
+            "output" => (None, 1),
+            _ => match self.functions.get(name) {
+                Some(&(label, parameter_count)) => (Some(label), parameter_count),
+                None => {
+                    return Err(CompileError::new(
+                        position,
+                        format!("Undefined function: {}", name),
+                    ))
+                }
+            },
+        };
+        if parameter_count != arguments.len() {
+            return Err(CompileError::new(
+                position,
+                format!(
+                    "{} takes {} arguments, got {}",
+                    name,
+                    parameter_count,
+                    arguments.len()
+                ),
+            ));
+        }
+
+        let result = Operand::Slot(target);
+        let label = match label {
+            Some(label) => label,
+            None if name == "input" => {
+                self.emit(Opcode::Input, &[result]);
+                return Ok(());
+            }
+            None => {
+                self.expression(&arguments[0], target)?;
+                self.emit(Opcode::Output, &[result]);
+                self.copy(Operand::Immediate(0), result);
+                return Ok(());
+            }
+        };
+
+        // Arguments may contain calls themselves, which would overwrite the callee's frame, so
+        // they are only copied there once all of them are evaluated.
+        let first_temporary = self.next_temporary;
+        for argument in arguments {
+            let temporary = self.allocate_temporary();
+            self.expression(argument, temporary)?;
+        }
+        for i in 0..arguments.len() as MemoryType {
+            self.copy(
+                Operand::Slot(first_temporary + i),
+                Operand::CalleeSlot(FIRST_PARAMETER + i),
+            );
+        }
+        for _ in arguments {
+            self.free_temporary();
+        }
+
+        let return_label = self.new_label();
+        self.copy(
+            Operand::Label(return_label),
+            Operand::CalleeSlot(RETURN_ADDRESS),
+        );
+        self.emit(Opcode::RelativeBaseOffset, &[Operand::FrameSize(1)]);
+        self.jump(label);
+        self.place_label(return_label);
+        self.emit(Opcode::RelativeBaseOffset, &[Operand::FrameSize(-1)]);
+        self.copy(Operand::CalleeSlot(RETURN_VALUE), result);
+        Ok(())
+    }
+}
+
+fn collect_locals<'a>(statements: &'a [Statement], locals: &mut Vec<&'a str>) {
+    for statement in statements {
+        match statement {
+            Statement::Let(name, _) => locals.push(name),
+            Statement::If(_, then_branch, else_branch) => {
+                collect_locals(then_branch, locals);
+                collect_locals(else_branch, locals);
+            }
+            Statement::While(_, body) => collect_locals(body, locals),
+            _ => {}
+        }
+    }
+}
+
+pub(super) fn generate(program: &Program) -> Result<Vec<MemoryType>, CompileError> {
+    let mut generator = Generator {
+        code: Vec::new(),
+        labels: Vec::new(),
+        label_uses: Vec::new(),
+        functions: BTreeMap::new(),
+        variables: BTreeMap::new(),
+        declared: BTreeSet::new(),
+        next_temporary: 0,
+        frame_size: 0,
+        frame_uses: Vec::new(),
+    };
+
+    for function in &program.functions {
+        if let "input" | "output" = function.name.as_str() {
+            return Err(CompileError::new(
+                function.position,
+                format!("{} is a builtin function", function.name),
+            ));
+        }
+        let label = generator.new_label();
+        let previous = generator
+            .functions
+            .insert(&function.name, (label, function.parameters.len()));
+        if previous.is_some() {
+            return Err(CompileError::new(
+                function.position,
+                format!("Function {} is already defined", function.name),
+            ));
+        }
+    }
+    let main = match generator.functions.get("main") {
+        Some(&(label, 0)) => label,
+        Some(_) => {
+            let function = program.functions.iter().find(|f| f.name == "main").unwrap();
+            return Err(CompileError::new(
+                function.position,
+                String::from("main can't take any arguments"),
+            ));
+        }
+        None => {
+            return Err(CompileError::new(
+                Position { line: 1, column: 1 },
+                String::from("No main function"),
+            ))
+        }
+    };
+
+    // Entry point: set up the stack, call main with a frame of size 0 and halt.
+    let stack_label = generator.new_label();
+    let halt_label = generator.new_label();
+    generator.emit(Opcode::RelativeBaseOffset, &[Operand::Label(stack_label)]);
+    generator.copy(Operand::Label(halt_label), Operand::Slot(RETURN_ADDRESS));
+    generator.jump(main);
+    generator.place_label(halt_label);
+    generator.emit(Opcode::Halt, &[]);
+
+    for function in &program.functions {
+        generator.function(function)?;
+    }
+    generator.place_label(stack_label);
+
+    let mut code = generator.code;
+    for (address, label) in generator.label_uses {
+        code[address] = generator.labels[label].expect("Label was never placed") as MemoryType;
+    }
+    Ok(code)
+}
@@ -0,0 +1,159 @@
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{BinaryOperator, Expression, Function, Program, Statement};
+use crate::MemoryType;
+
+enum Flow {
+    Continue,
+    Return(MemoryType),
+}
+
+struct Interpreter<'a> {
+    functions: BTreeMap<&'a str, &'a Function>,
+    input: VecDeque<MemoryType>,
+    output: Vec<MemoryType>,
+}
+
+impl<'a> Interpreter<'a> {
+    fn call(&mut self, name: &str, arguments: Vec<MemoryType>) -> Result<MemoryType, String> {
+        let function = match self.functions.get(name) {
+            Some(function) => *function,
+            None => return Err(format!("Undefined function: {}", name)),
+        };
+        if function.parameters.len() != arguments.len() {
+            return Err(format!(
+                "{} takes {} arguments, got {}",
+                name,
+                function.parameters.len(),
+                arguments.len()
+            ));
+        }
+
+        let mut variables: BTreeMap<&'a str, MemoryType> = function
+            .parameters
+            .iter()
+            .map(String::as_str)
+            .zip(arguments)
+            .collect();
+        match self.block(&function.body, &mut variables)? {
+            Flow::Return(value) => Ok(value),
+            Flow::Continue => Ok(0),
+        }
+    }
+
+    fn block(
+        &mut self,
+        statements: &'a [Statement],
+        variables: &mut BTreeMap<&'a str, MemoryType>,
+    ) -> Result<Flow, String> {
+        for statement in statements {
+            if let Flow::Return(value) = self.statement(statement, variables)? {
+                return Ok(Flow::Return(value));
+            }
+        }
+        Ok(Flow::Continue)
+    }
+
+    fn statement(
+        &mut self,
+        statement: &'a Statement,
+        variables: &mut BTreeMap<&'a str, MemoryType>,
+    ) -> Result<Flow, String> {
+        match statement {
+            Statement::Let(name, value) | Statement::Assign(name, value, _) => {
+                let value = self.expression(value, variables)?;
+                variables.insert(name, value);
+            }
+            Statement::If(condition, then_branch, else_branch) => {
+                return if self.expression(condition, variables)? != 0 {
+                    self.block(then_branch, variables)
+                } else {
+                    self.block(else_branch, variables)
+                };
+            }
+            Statement::While(condition, body) => {
+                while self.expression(condition, variables)? != 0 {
+                    if let Flow::Return(value) = self.block(body, variables)? {
+                        return Ok(Flow::Return(value));
+                    }
+                }
+            }
+            Statement::Return(value) => {
+                let value = match value {
+                    Some(value) => self.expression(value, variables)?,
+                    None => 0,
+                };
+                return Ok(Flow::Return(value));
+            }
+            Statement::Expression(expression) => {
+                self.expression(expression, variables)?;
+            }
+        }
+        Ok(Flow::Continue)
+    }
+
+    fn expression(
+        &mut self,
+        expression: &Expression,
+        variables: &mut BTreeMap<&'a str, MemoryType>,
+    ) -> Result<MemoryType, String> {
+        let value = match expression {
+            Expression::Number(value) => *value,
+            // Like memory, variables that were declared but not yet assigned read as 0.
+            Expression::Variable(name, _) => variables.get(name.as_str()).copied().unwrap_or(0),
+            Expression::Negate(operand) => -self.expression(operand, variables)?,
+            Expression::Binary(operator, lhs, rhs) => {
+                let a = self.expression(lhs, variables)?;
+                let b = self.expression(rhs, variables)?;
+                match operator {
+                    BinaryOperator::Add => a + b,
+                    BinaryOperator::Subtract => a - b,
+                    BinaryOperator::Multiply => a * b,
+                    BinaryOperator::Less => (a < b) as MemoryType,
+                    BinaryOperator::Greater => (a > b) as MemoryType,
+                    BinaryOperator::LessEqual => (a <= b) as MemoryType,
+                    BinaryOperator::GreaterEqual => (a >= b) as MemoryType,
+                    BinaryOperator::Equal => (a == b) as MemoryType,
+                    BinaryOperator::NotEqual => (a != b) as MemoryType,
+                }
+            }
+            Expression::Call(name, arguments, _) => {
+                let mut values = Vec::with_capacity(arguments.len());
+                for argument in arguments {
+                    values.push(self.expression(argument, variables)?);
+                }
+                match (name.as_str(), values.as_slice()) {
+                    ("input", []) => match self.input.pop_front() {
+                        Some(value) => value,
+                        None => return Err(String::from("No input available.")),
+                    },
+                    ("output", &[value]) => {
+                        self.output.push(value);
+                        0
+                    }
+                    _ => self.call(name, values)?,
+                }
+            }
+        };
+        Ok(value)
+    }
+}
+
+// Runs `program` directly on the syntax tree and returns its outputs. This is the reference
+// the compiled programs are checked against.
+pub fn interpret(program: &Program, input: &[MemoryType]) -> Result<Vec<MemoryType>, String> {
+    let mut interpreter = Interpreter {
+        functions: program
+            .functions
+            .iter()
+            .map(|function| (function.name.as_str(), function))
+            .collect(),
+        input: input.iter().copied().collect(),
+        output: Vec::new(),
+    };
+    interpreter.call("main", Vec::new())?;
+    Ok(interpreter.output)
+}
@@ -0,0 +1,262 @@
+// Compiler for a small language targeting intcode, so test fixtures and toy programs don't
+// have to be written as raw numbers. Example:
+//
+//     fn square(x) {
+//         return x * x;
+//     }
+//
+//     fn main() {
+//         let n = input();
+//         let i = 0;
+//         while i < n {
+//             output(square(i));
+//             i = i + 1;
+//         }
+//     }
+//
+// All values are integers. Besides `let`, assignments, `if`/`else`, `while` and `return`, the
+// language has the builtins `input()` and `output(x)`; comparisons evaluate to 0 or 1. Variables
+// are scoped to the function they are declared in. Execution starts at `main`.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::MemoryType;
+
+mod codegen;
+mod interpreter;
+mod parser;
+
+pub use interpreter::interpret;
+pub use parser::parse;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompileError {
+    pub position: Position,
+    pub message: String,
+}
+
+impl CompileError {
+    fn new(position: Position, message: String) -> Self {
+        Self { position, message }
+    }
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}",
+            self.position.line, self.position.column, self.message
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    pub functions: Vec<Function>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    pub name: String,
+    pub parameters: Vec<String>,
+    pub body: Vec<Statement>,
+    pub position: Position,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Let(String, Expression),
+    Assign(String, Expression, Position),
+    If(Expression, Vec<Statement>, Vec<Statement>),
+    While(Expression, Vec<Statement>),
+    Return(Option<Expression>),
+    Expression(Expression),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Number(MemoryType),
+    Variable(String, Position),
+    Negate(Box<Expression>),
+    Binary(BinaryOperator, Box<Expression>, Box<Expression>),
+    Call(String, Vec<Expression>, Position),
+}
+
+// Compiles `source` into a program for `Computer`.
+pub fn compile(source: &str) -> Result<Vec<MemoryType>, CompileError> {
+    codegen::generate(&parse(source)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::VecDeque;
+
+    use super::*;
+    use crate::{Computer, RunState};
+
+    fn run_compiled(program: &[MemoryType], input: &[MemoryType]) -> Vec<MemoryType> {
+        let input: VecDeque<MemoryType> = input.iter().copied().collect();
+        let mut computer = Computer::new(0, program, input, Vec::new());
+        assert!(matches!(computer.run_program(), RunState::Stopped(_)));
+        computer.get_output().clone()
+    }
+
+    // Runs `source` compiled and interpreted with each of the inputs and checks that both
+    // produce the same output.
+    fn check(source: &str, inputs: &[&[MemoryType]]) -> Vec<Vec<MemoryType>> {
+        let program = compile(source).unwrap();
+        let ast = parse(source).unwrap();
+        inputs
+            .iter()
+            .map(|input| {
+                let expected = interpret(&ast, input).unwrap();
+                assert_eq!(
+                    expected,
+                    run_compiled(&program, input),
+                    "input: {:?}",
+                    input
+                );
+                expected
+            })
+            .collect()
+    }
+
+    #[test]
+    fn arithmetic() {
+        let source = "
+            fn main() {
+                let a = input();
+                let b = input();
+                output(a + b);
+                output(a - b);
+                output(a * b);
+                output(-a * (b - 3));
+                output(1 + a * 2 - -b);
+            }
+        ";
+        let outputs = check(source, &[&[3, 4], &[-7, 12], &[0, 0]]);
+        assert_eq!(vec![7, -1, 12, -3, 11], outputs[0]);
+    }
+
+    #[test]
+    fn comparisons() {
+        let source = "
+            fn main() {
+                let a = input();
+                let b = input();
+                output(a < b);
+                output(a > b);
+                output(a <= b);
+                output(a >= b);
+                output(a == b);
+                output(a != b);
+            }
+        ";
+        let outputs = check(source, &[&[1, 2], &[2, 2], &[3, 2]]);
+        assert_eq!(vec![1, 0, 1, 0, 0, 1], outputs[0]);
+        assert_eq!(vec![0, 0, 1, 1, 1, 0], outputs[1]);
+    }
+
+    #[test]
+    fn control_flow() {
+        let source = "
+            // Collatz sequence of the input
+            fn main() {
+                let n = input();
+                while n != 1 {
+                    output(n);
+                    let half = 0;
+                    while half * 2 < n {
+                        half = half + 1;
+                    }
+                    if half * 2 == n {
+                        n = half;
+                    } else if n > 0 {
+                        n = 3 * n + 1;
+                    } else {
+                        n = 1;
+                    }
+                }
+                output(n);
+            }
+        ";
+        let outputs = check(source, &[&[6], &[27], &[1], &[-5]]);
+        assert_eq!(vec![6, 3, 10, 5, 16, 8, 4, 2, 1], outputs[0]);
+    }
+
+    #[test]
+    fn functions() {
+        let source = "
+            fn factorial(n) {
+                if n <= 1 {
+                    return 1;
+                }
+                return n * factorial(n - 1);
+            }
+
+            fn fibonacci(n) {
+                if n < 2 {
+                    return n;
+                }
+                return fibonacci(n - 1) + fibonacci(n - 2);
+            }
+
+            fn sum3(a, b, c) {
+                return a + b + c;
+            }
+
+            fn nothing() {}
+
+            fn main() {
+                let n = input();
+                output(factorial(n));
+                output(fibonacci(n));
+                output(sum3(n, factorial(3), sum3(1, 2, fibonacci(n))));
+                output(nothing());
+            }
+        ";
+        let outputs = check(source, &[&[0], &[5], &[10]]);
+        assert_eq!(vec![120, 5, 5 + 6 + 8, 0], outputs[1]);
+    }
+
+    #[test]
+    fn compile_errors() {
+        let error = |source: &str| compile(source).unwrap_err().to_string();
+
+        assert_eq!("1:1: No main function", error(""));
+        assert_eq!(
+            "1:24: Undefined variable: x",
+            error("fn main() { output(1 + x); }")
+        );
+        assert_eq!("1:13: Undefined function: f", error("fn main() { f(); }"));
+        assert_eq!(
+            "2:13: f takes 1 arguments, got 2",
+            error("fn f(a) { return a; }\nfn main() { f(1, 2); }")
+        );
+        assert_eq!("1:17: Expected ';', found '}'", error("fn main() { f() }"));
+        assert_eq!("1:13: Unexpected character: '#'", error("fn main() { # }"));
+    }
+}
@@ -0,0 +1,367 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{BinaryOperator, CompileError, Expression, Function, Position, Program, Statement};
+use crate::MemoryType;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(MemoryType),
+    Identifier(String),
+    Fn,
+    Let,
+    If,
+    Else,
+    While,
+    Return,
+    // Punctuation and operators, e.g. "(" or "<="
+    Symbol(&'static str),
+    End,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Number(value) => value.to_string(),
+            Token::Identifier(name) => name.clone(),
+            Token::Fn => String::from("'fn'"),
+            Token::Let => String::from("'let'"),
+            Token::If => String::from("'if'"),
+            Token::Else => String::from("'else'"),
+            Token::While => String::from("'while'"),
+            Token::Return => String::from("'return'"),
+            Token::Symbol(symbol) => format!("'{}'", symbol),
+            Token::End => String::from("end of input"),
+        }
+    }
+}
+
+// Longer symbols first, so "<=" isn't lexed as "<" followed by "=".
+const SYMBOLS: [&str; 16] = [
+    "<=", ">=", "==", "!=", "(", ")", "{", "}", ",", ";", "=", "+", "-", "*", "<", ">",
+];
+
+fn tokenize(source: &str) -> Result<Vec<(Token, Position)>, CompileError> {
+    let mut tokens = Vec::new();
+    for (line_index, line) in source.lines().enumerate() {
+        let mut column = 0;
+        while column < line.len() {
+            let rest = &line[column..];
+            let position = Position {
+                line: line_index + 1,
+                column: column + 1,
+            };
+            let c = rest.chars().next().unwrap();
+
+            if c.is_whitespace() {
+                column += c.len_utf8();
+            } else if rest.starts_with("//") {
+                break;
+            } else if c.is_ascii_digit() {
+                let length = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                let value = match rest[..length].parse() {
+                    Ok(value) => value,
+                    Err(_) => {
+                        return Err(CompileError::new(
+                            position,
+                            format!("Number too large: {}", &rest[..length]),
+                        ))
+                    }
+                };
+                tokens.push((Token::Number(value), position));
+                column += length;
+            } else if c.is_ascii_alphabetic() || c == '_' {
+                let length = rest
+                    .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                    .unwrap_or(rest.len());
+                let token = match &rest[..length] {
+                    "fn" => Token::Fn,
+                    "let" => Token::Let,
+                    "if" => Token::If,
+                    "else" => Token::Else,
+                    "while" => Token::While,
+                    "return" => Token::Return,
+                    name => Token::Identifier(String::from(name)),
+                };
+                tokens.push((token, position));
+                column += length;
+            } else {
+                match SYMBOLS.iter().find(|symbol| rest.starts_with(*symbol)) {
+                    Some(symbol) => {
+                        tokens.push((Token::Symbol(symbol), position));
+                        column += symbol.len();
+                    }
+                    None => {
+                        return Err(CompileError::new(
+                            position,
+                            format!("Unexpected character: '{}'", c),
+                        ))
+                    }
+                }
+            }
+        }
+    }
+
+    let end = Position {
+        line: source.lines().count().max(1),
+        column: source.lines().last().map_or(0, str::len) + 1,
+    };
+    tokens.push((Token::End, end));
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, Position)>,
+    index: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index].0
+    }
+
+    fn peek_second(&self) -> &Token {
+        let index = (self.index + 1).min(self.tokens.len() - 1);
+        &self.tokens[index].0
+    }
+
+    fn position(&self) -> Position {
+        self.tokens[self.index].1
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.index].0.clone();
+        if self.index + 1 < self.tokens.len() {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn unexpected<T>(&self, expected: &str) -> Result<T, CompileError> {
+        Err(CompileError::new(
+            self.position(),
+            format!("Expected {}, found {}", expected, self.peek().describe()),
+        ))
+    }
+
+    fn accept(&mut self, symbol: &'static str) -> bool {
+        if *self.peek() == Token::Symbol(symbol) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, symbol: &'static str) -> Result<(), CompileError> {
+        if self.accept(symbol) {
+            Ok(())
+        } else {
+            self.unexpected(&format!("'{}'", symbol))
+        }
+    }
+
+    fn identifier(&mut self) -> Result<String, CompileError> {
+        match self.peek().clone() {
+            Token::Identifier(name) => {
+                self.next();
+                Ok(name)
+            }
+            _ => self.unexpected("identifier"),
+        }
+    }
+
+    fn program(&mut self) -> Result<Program, CompileError> {
+        let mut functions = Vec::new();
+        while *self.peek() != Token::End {
+            functions.push(self.function()?);
+        }
+        Ok(Program { functions })
+    }
+
+    fn function(&mut self) -> Result<Function, CompileError> {
+        let position = self.position();
+        if *self.peek() != Token::Fn {
+            return self.unexpected("'fn'");
+        }
+        self.next();
+        let name = self.identifier()?;
+
+        self.expect("(")?;
+        let mut parameters = Vec::new();
+        if !self.accept(")") {
+            loop {
+                parameters.push(self.identifier()?);
+                if self.accept(")") {
+                    break;
+                }
+                self.expect(",")?;
+            }
+        }
+
+        let body = self.block()?;
+        Ok(Function {
+            name,
+            parameters,
+            body,
+            position,
+        })
+    }
+
+    fn block(&mut self) -> Result<Vec<Statement>, CompileError> {
+        self.expect("{")?;
+        let mut statements = Vec::new();
+        while !self.accept("}") {
+            statements.push(self.statement()?);
+        }
+        Ok(statements)
+    }
+
+    fn statement(&mut self) -> Result<Statement, CompileError> {
+        let statement = match self.peek() {
+            Token::Let => {
+                self.next();
+                let name = self.identifier()?;
+                self.expect("=")?;
+                Statement::Let(name, self.expression()?)
+            }
+            Token::If => return self.if_statement(),
+            Token::While => {
+                self.next();
+                let condition = self.expression()?;
+                return Ok(Statement::While(condition, self.block()?));
+            }
+            Token::Return => {
+                self.next();
+                if *self.peek() == Token::Symbol(";") {
+                    Statement::Return(None)
+                } else {
+                    Statement::Return(Some(self.expression()?))
+                }
+            }
+            Token::Identifier(_) if *self.peek_second() == Token::Symbol("=") => {
+                let position = self.position();
+                let name = self.identifier()?;
+                self.next();
+                Statement::Assign(name, self.expression()?, position)
+            }
+            _ => Statement::Expression(self.expression()?),
+        };
+        self.expect(";")?;
+        Ok(statement)
+    }
+
+    fn if_statement(&mut self) -> Result<Statement, CompileError> {
+        self.next();
+        let condition = self.expression()?;
+        let then_branch = self.block()?;
+        let else_branch = if *self.peek() != Token::Else {
+            Vec::new()
+        } else {
+            self.next();
+            if *self.peek() == Token::If {
+                vec![self.if_statement()?]
+            } else {
+                self.block()?
+            }
+        };
+        Ok(Statement::If(condition, then_branch, else_branch))
+    }
+
+    // Comparisons don't chain, so "a < b < c" is an error.
+    fn expression(&mut self) -> Result<Expression, CompileError> {
+        let lhs = self.additive()?;
+        let operator = match self.peek() {
+            Token::Symbol("<") => BinaryOperator::Less,
+            Token::Symbol(">") => BinaryOperator::Greater,
+            Token::Symbol("<=") => BinaryOperator::LessEqual,
+            Token::Symbol(">=") => BinaryOperator::GreaterEqual,
+            Token::Symbol("==") => BinaryOperator::Equal,
+            Token::Symbol("!=") => BinaryOperator::NotEqual,
+            _ => return Ok(lhs),
+        };
+        self.next();
+        let rhs = self.additive()?;
+        Ok(Expression::Binary(operator, Box::new(lhs), Box::new(rhs)))
+    }
+
+    fn additive(&mut self) -> Result<Expression, CompileError> {
+        let mut lhs = self.term()?;
+        loop {
+            let operator = match self.peek() {
+                Token::Symbol("+") => BinaryOperator::Add,
+                Token::Symbol("-") => BinaryOperator::Subtract,
+                _ => return Ok(lhs),
+            };
+            self.next();
+            lhs = Expression::Binary(operator, Box::new(lhs), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expression, CompileError> {
+        let mut lhs = self.unary()?;
+        while self.accept("*") {
+            lhs = Expression::Binary(
+                BinaryOperator::Multiply,
+                Box::new(lhs),
+                Box::new(self.unary()?),
+            );
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expression, CompileError> {
+        if self.accept("-") {
+            Ok(Expression::Negate(Box::new(self.unary()?)))
+        } else {
+            self.primary()
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expression, CompileError> {
+        let position = self.position();
+        match self.peek().clone() {
+            Token::Number(value) => {
+                self.next();
+                Ok(Expression::Number(value))
+            }
+            Token::Identifier(name) => {
+                self.next();
+                if !self.accept("(") {
+                    return Ok(Expression::Variable(name, position));
+                }
+                let mut arguments = Vec::new();
+                if !self.accept(")") {
+                    loop {
+                        arguments.push(self.expression()?);
+                        if self.accept(")") {
+                            break;
+                        }
+                        self.expect(",")?;
+                    }
+                }
+                Ok(Expression::Call(name, arguments, position))
+            }
+            Token::Symbol("(") => {
+                self.next();
+                let expression = self.expression()?;
+                self.expect(")")?;
+                Ok(expression)
+            }
+            _ => self.unexpected("expression"),
+        }
+    }
+}
+
+pub fn parse(source: &str) -> Result<Program, CompileError> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        index: 0,
+    };
+    parser.program()
+}
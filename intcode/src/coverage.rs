@@ -0,0 +1,338 @@
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::instruction::{Instruction, Opcode};
+use crate::MemoryType;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct BranchCount {
+    pub taken: u64,
+    pub not_taken: u64,
+}
+
+// Coverage information collected while running a program: how often each instruction
+// address was executed and, for the conditional jumps (opcodes 5 and 6), how often the
+// jump was taken or not.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Coverage {
+    executed: BTreeMap<usize, u64>,
+    branches: BTreeMap<usize, BranchCount>,
+}
+
+impl Coverage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn record_instruction(&mut self, address: usize) {
+        *self.executed.entry(address).or_insert(0) += 1;
+    }
+
+    pub(crate) fn record_branch(&mut self, address: usize, taken: bool) {
+        let branch = self.branches.entry(address).or_default();
+        if taken {
+            branch.taken += 1;
+        } else {
+            branch.not_taken += 1;
+        }
+    }
+
+    pub fn is_executed(&self, address: usize) -> bool {
+        self.executed.contains_key(&address)
+    }
+
+    pub fn hit_count(&self, address: usize) -> u64 {
+        self.executed.get(&address).copied().unwrap_or(0)
+    }
+
+    pub fn executed_addresses(&self) -> impl Iterator<Item = usize> + '_ {
+        self.executed.keys().copied()
+    }
+
+    pub fn branch(&self, address: usize) -> Option<BranchCount> {
+        self.branches.get(&address).copied()
+    }
+
+    pub fn branches(&self) -> impl Iterator<Item = (usize, BranchCount)> + '_ {
+        self.branches
+            .iter()
+            .map(|(&address, &branch)| (address, branch))
+    }
+
+    // Combines the coverage of another run (e.g. a different input) into this one.
+    pub fn merge(&mut self, other: &Coverage) {
+        for (&address, &count) in &other.executed {
+            *self.executed.entry(address).or_insert(0) += count;
+        }
+        for (&address, branch) in &other.branches {
+            let entry = self.branches.entry(address).or_default();
+            entry.taken += branch.taken;
+            entry.not_taken += branch.not_taken;
+        }
+    }
+
+    // Executed addresses are always decoded as instructions. Other addresses only if the
+    // instruction fits into the program and doesn't overlap an executed instruction.
+    fn instruction_at(&self, program: &[MemoryType], address: usize) -> Option<Instruction> {
+        match Instruction::decode(program, address) {
+            Ok(instruction) if self.is_executed(address) => Some(instruction),
+            Ok(instruction)
+                if instruction.next_address() <= program.len()
+                    && (address + 1..instruction.next_address())
+                        .all(|operand| !self.is_executed(operand)) =>
+            {
+                Some(instruction)
+            }
+            _ => None,
+        }
+    }
+
+    // Renders a disassembly of `program` with the hit count of every instruction in the first
+    // column ("-----" for instructions that were never executed) and the branch counts of
+    // conditional jumps at the end of the line. Cells that don't decode to an instruction
+    // (or would overlap an executed instruction) are listed as data.
+    pub fn annotate(&self, program: &[MemoryType]) -> String {
+        let mut result = String::new();
+        let mut address = 0;
+
+        while address < program.len() {
+            match self.instruction_at(program, address) {
+                Some(instruction) => {
+                    let hits = match self.executed.get(&address) {
+                        Some(count) => count.to_string(),
+                        None => String::from("-----"),
+                    };
+                    let _ = write!(result, "{:>8} {:>6}: {}", hits, address, instruction);
+                    if let Opcode::JumpIfTrue | Opcode::JumpIfFalse = instruction.opcode {
+                        let branch = self.branch(address).unwrap_or_default();
+                        let _ = write!(
+                            result,
+                            "    [taken: {}, not taken: {}]",
+                            branch.taken, branch.not_taken
+                        );
+                    }
+                    result.push('\n');
+                    address = instruction.next_address();
+                }
+                None => {
+                    let _ = writeln!(
+                        result,
+                        "{:>8} {:>6}: data {}",
+                        "", address, program[address]
+                    );
+                    address += 1;
+                }
+            }
+        }
+
+        result
+    }
+
+    // Renders the coverage in lcov's tracefile format, so it can be fed to tools like genhtml.
+    // Every instruction of the program is reported as a "line"; since lcov lines start at 1,
+    // line numbers are the instruction addresses plus one.
+    pub fn lcov(&self, source_file: &str, program: &[MemoryType]) -> String {
+        let mut result = String::new();
+        let _ = writeln!(result, "TN:");
+        let _ = writeln!(result, "SF:{}", source_file);
+
+        let mut instructions = Vec::new();
+        let mut address = 0;
+        while address < program.len() {
+            match self.instruction_at(program, address) {
+                Some(instruction) => {
+                    instructions.push(instruction);
+                    address = instruction.next_address();
+                }
+                None => address += 1,
+            }
+        }
+
+        let mut branches_found = 0;
+        let mut branches_hit = 0;
+        for instruction in &instructions {
+            if let Opcode::JumpIfTrue | Opcode::JumpIfFalse = instruction.opcode {
+                let line = instruction.address + 1;
+                // Branches of jumps that were never reached are reported as "-", as lcov expects.
+                let counts = match self.branch(instruction.address) {
+                    Some(branch) => [Some(branch.taken), Some(branch.not_taken)],
+                    None => [None, None],
+                };
+                for (index, count) in counts.iter().enumerate() {
+                    match count {
+                        Some(count) => {
+                            let _ = writeln!(result, "BRDA:{},0,{},{}", line, index, count);
+                            if *count > 0 {
+                                branches_hit += 1;
+                            }
+                        }
+                        None => {
+                            let _ = writeln!(result, "BRDA:{},0,{},-", line, index);
+                        }
+                    }
+                    branches_found += 1;
+                }
+            }
+        }
+        let _ = writeln!(result, "BRF:{}", branches_found);
+        let _ = writeln!(result, "BRH:{}", branches_hit);
+
+        for instruction in &instructions {
+            let _ = writeln!(
+                result,
+                "DA:{},{}",
+                instruction.address + 1,
+                self.hit_count(instruction.address)
+            );
+        }
+        let _ = writeln!(result, "LF:{}", instructions.len());
+        let _ = writeln!(
+            result,
+            "LH:{}",
+            instructions
+                .iter()
+                .filter(|instruction| self.is_executed(instruction.address))
+                .count()
+        );
+        let _ = writeln!(result, "end_of_record");
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::VecDeque;
+
+    use super::*;
+    use crate::{Computer, RunState};
+
+    // Outputs 999 if the input is below 8, 1000 if it is equal to 8 and 1001 otherwise.
+    const COMPARE_TO_8: [MemoryType; 47] = [
+        3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+        1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20,
+        1105, 1, 46, 98, 99,
+    ];
+
+    fn run_with_coverage(input: MemoryType) -> Coverage {
+        let mut input_queue = VecDeque::new();
+        input_queue.push_back(input);
+        let mut computer = Computer::new(0, &COMPARE_TO_8, input_queue, Vec::new());
+        computer.enable_coverage();
+        computer.run_program();
+        computer.take_coverage().unwrap()
+    }
+
+    #[test]
+    fn executed_addresses_and_branches() {
+        let coverage = run_with_coverage(7);
+        assert_eq!(
+            vec![0, 2, 6, 9, 13, 31, 33, 46],
+            coverage.executed_addresses().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(BranchCount {
+                taken: 0,
+                not_taken: 1
+            }),
+            coverage.branch(6)
+        );
+        assert_eq!(
+            Some(BranchCount {
+                taken: 1,
+                not_taken: 0
+            }),
+            coverage.branch(13)
+        );
+        assert_eq!(
+            Some(BranchCount {
+                taken: 1,
+                not_taken: 0
+            }),
+            coverage.branch(33)
+        );
+    }
+
+    #[test]
+    fn merge_runs() {
+        let mut coverage = run_with_coverage(7);
+        coverage.merge(&run_with_coverage(8));
+        coverage.merge(&run_with_coverage(9));
+
+        assert_eq!(3, coverage.hit_count(0));
+        assert_eq!(1, coverage.hit_count(16));
+        assert_eq!(1, coverage.hit_count(22));
+        assert_eq!(
+            Some(BranchCount {
+                taken: 1,
+                not_taken: 2
+            }),
+            coverage.branch(6)
+        );
+        assert_eq!(
+            Some(BranchCount {
+                taken: 1,
+                not_taken: 1
+            }),
+            coverage.branch(13)
+        );
+    }
+
+    #[test]
+    fn suspended_instruction() {
+        let program = vec![3, 0, 4, 0, 99];
+        let mut computer = Computer::new(0, &program, VecDeque::new(), Vec::new());
+        computer.enable_coverage();
+        assert_eq!(RunState::NeedInput, computer.run_program());
+        assert_eq!(RunState::NeedInput, computer.resume());
+        computer.get_input().push_back(5);
+        assert_eq!(RunState::Stopped(5), computer.resume());
+
+        let coverage = computer.coverage().unwrap();
+        assert_eq!(1, coverage.hit_count(0));
+        assert_eq!(1, coverage.hit_count(2));
+    }
+
+    #[test]
+    fn annotated_disassembly() {
+        let program = vec![1105, 1, 4, 99, 104, 42, 99];
+        let mut computer = Computer::new(0, &program, VecDeque::new(), Vec::new());
+        computer.enable_coverage();
+        computer.run_program();
+
+        assert_eq!(
+            "       1      0: jnz 1, 4    [taken: 1, not taken: 0]\n\
+             \x20  -----      3: hlt\n\
+             \x20      1      4: out 42\n\
+             \x20      1      6: hlt\n",
+            computer.coverage().unwrap().annotate(&program)
+        );
+    }
+
+    #[test]
+    fn lcov_report() {
+        let program = vec![1105, 1, 4, 99, 104, 42, 99];
+        let mut computer = Computer::new(0, &program, VecDeque::new(), Vec::new());
+        computer.enable_coverage();
+        computer.run_program();
+
+        assert_eq!(
+            "TN:\n\
+             SF:test.int\n\
+             BRDA:1,0,0,1\n\
+             BRDA:1,0,1,0\n\
+             BRF:2\n\
+             BRH:1\n\
+             DA:1,1\n\
+             DA:4,0\n\
+             DA:5,1\n\
+             DA:7,1\n\
+             LF:4\n\
+             LH:3\n\
+             end_of_record\n",
+            computer.coverage().unwrap().lcov("test.int", &program)
+        );
+    }
+}
@@ -0,0 +1,824 @@
+// Decompiler that recovers the functions of a program and prints them as structured pseudo-code.
+//
+// Compiled intcode programs (like the puzzle inputs) use the relative base as a stack pointer.
+// A call stores the return address at [rb+0] and the arguments at [rb+1], [rb+2], ... and then
+// jumps to the function, which starts by moving rb behind its frame (`arb N`). Inside the
+// function, [rb-N] holds the return address, followed by the parameters and the local
+// variables. The function returns with `arb -N` and a jump to [rb+0]. By convention, results
+// are passed back in the slot of the first parameter.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::analysis::Analysis;
+use crate::instruction::{Instruction, Opcode, Parameter, ParameterMode};
+use crate::MemoryType;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FunctionInfo {
+    pub entry: usize,
+    // Number of stack slots reserved by the prologue, including the return address.
+    pub frame_size: usize,
+    pub parameters: usize,
+    pub locals: usize,
+}
+
+struct Call {
+    target: usize,
+    // Stores to the callee's frame that are printed as arguments of the call, by slot.
+    arguments: BTreeMap<MemoryType, Instruction>,
+}
+
+// Condition of a conditional jump, as text for the case that the jump is taken and for the
+// case that it isn't.
+struct Condition {
+    taken: String,
+    not_taken: String,
+}
+
+enum Statement {
+    Label(usize),
+    Simple(String),
+    If(String, Vec<Statement>, Vec<Statement>),
+    While(String, Vec<Statement>),
+    DoWhile(Vec<Statement>, String),
+    Loop(Vec<Statement>),
+    Break,
+    Goto(usize),
+}
+
+struct Decompiler {
+    analysis: Analysis,
+    calls: BTreeMap<usize, Call>,
+    // Conditional jumps whose condition was computed by the instruction right before them.
+    conditions: BTreeMap<usize, Instruction>,
+    // Instructions that aren't printed on their own: arguments of calls, conditions of jumps,
+    // and function prologues and epilogues.
+    folded: BTreeSet<usize>,
+    functions: BTreeMap<usize, FunctionInfo>,
+}
+
+// Where an instruction stores its result.
+fn destination(instruction: &Instruction) -> Option<Parameter> {
+    match instruction.opcode {
+        Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => {
+            Some(instruction.parameters()[2])
+        }
+        Opcode::Input => Some(instruction.parameters()[0]),
+        _ => None,
+    }
+}
+
+// The slot in the frame of a callee an instruction stores into (e.g. 1 for [rb+1]).
+fn callee_slot(instruction: &Instruction) -> Option<MemoryType> {
+    match destination(instruction) {
+        Some(Parameter {
+            mode: ParameterMode::Relative,
+            value,
+        }) if value >= 0 => Some(value),
+        _ => None,
+    }
+}
+
+fn reads_callee_slot(instruction: &Instruction) -> bool {
+    let sources = match instruction.opcode {
+        Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => 2,
+        _ => instruction.parameters().len(),
+    };
+    instruction.parameters()[..sources]
+        .iter()
+        .any(|parameter| parameter.mode == ParameterMode::Relative && parameter.value >= 0)
+}
+
+fn immediate_target(instruction: &Instruction) -> Option<usize> {
+    match instruction.opcode {
+        Opcode::JumpIfTrue | Opcode::JumpIfFalse => {
+            let target = instruction.parameters()[1];
+            if target.mode == ParameterMode::Immediate && target.value >= 0 {
+                Some(target.value as usize)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+fn is_return(instruction: &Instruction) -> bool {
+    Analysis::is_unconditional_jump(instruction)
+        && instruction.parameters()[1]
+            == Parameter {
+                mode: ParameterMode::Relative,
+                value: 0,
+            }
+}
+
+fn frame_adjustment(instruction: &Instruction) -> Option<MemoryType> {
+    let parameter = instruction.parameters().first()?;
+    if instruction.opcode == Opcode::RelativeBaseOffset
+        && parameter.mode == ParameterMode::Immediate
+    {
+        Some(parameter.value)
+    } else {
+        None
+    }
+}
+
+fn function_name(entry: usize) -> String {
+    if entry == 0 {
+        String::from("main")
+    } else {
+        format!("f{}", entry)
+    }
+}
+
+impl Decompiler {
+    fn new(program: &[MemoryType]) -> Self {
+        let mut decompiler = Self {
+            analysis: Analysis::new(program),
+            calls: BTreeMap::new(),
+            conditions: BTreeMap::new(),
+            folded: BTreeSet::new(),
+            functions: BTreeMap::new(),
+        };
+        decompiler.find_calls();
+        decompiler.find_functions();
+        decompiler.find_conditions();
+        decompiler
+    }
+
+    fn instruction_before(&self, address: usize) -> Option<&Instruction> {
+        self.analysis
+            .instructions
+            .range(..address)
+            .next_back()
+            .map(|(_, instruction)| instruction)
+            .filter(|instruction| instruction.next_address() == address)
+    }
+
+    // A call is an unconditional jump preceded by stores into the callee's frame, one of which
+    // is the address right behind the jump into [rb+0].
+    fn find_calls(&mut self) {
+        let mut parameters: BTreeMap<usize, usize> = BTreeMap::new();
+        for jump in self.analysis.instructions.values() {
+            let target = match immediate_target(jump) {
+                Some(target) if Analysis::is_unconditional_jump(jump) => target,
+                _ => continue,
+            };
+
+            let mut stores = Vec::new();
+            let mut address = jump.address;
+            while let Some(store) = self.instruction_before(address) {
+                if address != jump.address && self.analysis.leaders.contains(&address) {
+                    break;
+                }
+                if callee_slot(store).is_none() {
+                    break;
+                }
+                stores.push(*store);
+                address = store.address;
+            }
+            let return_address = jump.next_address() as MemoryType;
+            if !stores.iter().any(|store| {
+                callee_slot(store) == Some(0)
+                    && Analysis::constant_move(store) == Some(return_address)
+            }) {
+                continue;
+            }
+
+            // Stores are folded into the call, starting with the one closest to the jump, as
+            // long as nothing else depends on their order.
+            let mut arguments = BTreeMap::new();
+            for store in &stores {
+                let slot = callee_slot(store).unwrap();
+                if store.opcode == Opcode::Input
+                    || reads_callee_slot(store)
+                    || arguments.contains_key(&slot)
+                {
+                    break;
+                }
+                arguments.insert(slot, *store);
+                self.folded.insert(store.address);
+            }
+
+            let count = stores.iter().filter_map(callee_slot).max().unwrap_or(0) as usize;
+            let entry = parameters.entry(target).or_insert(0);
+            *entry = (*entry).max(count);
+            self.calls.insert(jump.address, Call { target, arguments });
+        }
+
+        for (&entry, &parameters) in &parameters {
+            self.functions.insert(
+                entry,
+                FunctionInfo {
+                    entry,
+                    frame_size: 0,
+                    parameters,
+                    locals: 0,
+                },
+            );
+        }
+        self.functions.entry(0).or_insert(FunctionInfo {
+            entry: 0,
+            frame_size: 0,
+            parameters: 0,
+            locals: 0,
+        });
+    }
+
+    fn find_functions(&mut self) {
+        for function in self.functions.values_mut() {
+            if function.entry == 0 {
+                continue;
+            }
+            let prologue = self
+                .analysis
+                .instructions
+                .get(&function.entry)
+                .and_then(frame_adjustment);
+            if let Some(size) = prologue.filter(|&size| size > 0) {
+                function.frame_size = size as usize;
+                function.parameters = function.parameters.min(function.frame_size - 1);
+                function.locals = function.frame_size - 1 - function.parameters;
+                self.folded.insert(function.entry);
+            }
+        }
+
+        // Epilogues: `arb -N` right before returning
+        for instruction in self.analysis.instructions.values() {
+            if !is_return(instruction) {
+                continue;
+            }
+            if let Some(epilogue) = self.instruction_before(instruction.address) {
+                if matches!(frame_adjustment(epilogue), Some(size) if size < 0)
+                    && !self.analysis.leaders.contains(&instruction.address)
+                {
+                    self.folded.insert(epilogue.address);
+                }
+            }
+        }
+    }
+
+    // Folds comparisons into the conditional jumps that test their result right away.
+    fn find_conditions(&mut self) {
+        for jump in self.analysis.instructions.values() {
+            if Analysis::jump_condition(jump).is_some() || immediate_target(jump).is_none() {
+                continue;
+            }
+            if self.analysis.leaders.contains(&jump.address) {
+                continue;
+            }
+            let comparison = match self.instruction_before(jump.address) {
+                Some(comparison) => *comparison,
+                None => continue,
+            };
+            if let Opcode::LessThan | Opcode::Equals = comparison.opcode {
+                if destination(&comparison) == Some(jump.parameters()[0])
+                    && !self.folded.contains(&comparison.address)
+                {
+                    self.conditions.insert(jump.address, comparison);
+                    self.folded.insert(comparison.address);
+                }
+            }
+        }
+    }
+
+    // Instructions reachable from the entry of a function, without following calls.
+    fn function_code(&self, entry: usize) -> BTreeSet<usize> {
+        let mut code = BTreeSet::new();
+        let mut worklist = vec![entry];
+        while let Some(address) = worklist.pop() {
+            let instruction = match self.analysis.instructions.get(&address) {
+                Some(instruction) if code.insert(address) => instruction,
+                _ => continue,
+            };
+            match (instruction.opcode, Analysis::jump_condition(instruction)) {
+                (Opcode::Halt, _) => {}
+                (Opcode::JumpIfTrue, _) | (Opcode::JumpIfFalse, _) => {
+                    if self.calls.contains_key(&address) || is_return(instruction) {
+                        if !is_return(instruction) {
+                            worklist.push(instruction.next_address());
+                        }
+                        continue;
+                    }
+                    if let Some(target) = immediate_target(instruction) {
+                        worklist.push(target);
+                    }
+                    if Analysis::jump_condition(instruction) != Some(true) {
+                        worklist.push(instruction.next_address());
+                    }
+                }
+                _ => worklist.push(instruction.next_address()),
+            }
+        }
+        code
+    }
+
+    fn operand(&self, function: &FunctionInfo, parameter: Parameter) -> String {
+        match parameter.mode {
+            ParameterMode::Immediate => parameter.value.to_string(),
+            ParameterMode::Position => format!("m[{}]", parameter.value),
+            ParameterMode::Relative => {
+                let slot = parameter.value + function.frame_size as MemoryType;
+                if parameter.value >= 0 {
+                    format!("out{}", parameter.value)
+                } else if slot < 0 {
+                    format!("[rb{}]", parameter.value)
+                } else if slot == 0 {
+                    String::from("return_address")
+                } else if slot as usize <= function.parameters {
+                    format!("arg{}", slot - 1)
+                } else {
+                    format!("local{}", slot as usize - function.parameters - 1)
+                }
+            }
+        }
+    }
+
+    // Right-hand side of an instruction that stores a value.
+    fn expression(&self, function: &FunctionInfo, instruction: &Instruction) -> String {
+        let parameters = instruction.parameters();
+        if instruction.opcode == Opcode::Input {
+            return String::from("input()");
+        }
+        let a = self.operand(function, parameters[0]);
+        let b = self.operand(function, parameters[1]);
+        let immediate = |parameter: Parameter| {
+            if parameter.mode == ParameterMode::Immediate {
+                Some(parameter.value)
+            } else {
+                None
+            }
+        };
+
+        match instruction.opcode {
+            Opcode::Add => match (immediate(parameters[0]), immediate(parameters[1])) {
+                (Some(0), _) => b,
+                (_, Some(0)) => a,
+                (_, Some(value)) if value < 0 => format!("{} - {}", a, -value),
+                _ => format!("{} + {}", a, b),
+            },
+            Opcode::Multiply => match (immediate(parameters[0]), immediate(parameters[1])) {
+                (Some(1), _) => b,
+                (_, Some(1)) => a,
+                (Some(-1), _) => format!("-{}", b),
+                (_, Some(-1)) => format!("-{}", a),
+                _ => format!("{} * {}", a, b),
+            },
+            Opcode::LessThan => format!("{} < {}", a, b),
+            Opcode::Equals => format!("{} == {}", a, b),
+            _ => unreachable!(),
+        }
+    }
+
+    fn condition(&self, function: &FunctionInfo, jump: &Instruction) -> Condition {
+        let (is_true, is_false) = match self.conditions.get(&jump.address) {
+            Some(comparison) => {
+                let parameters = comparison.parameters();
+                let a = self.operand(function, parameters[0]);
+                let b = self.operand(function, parameters[1]);
+                match comparison.opcode {
+                    Opcode::LessThan => (format!("{} < {}", a, b), format!("{} >= {}", a, b)),
+                    _ => (format!("{} == {}", a, b), format!("{} != {}", a, b)),
+                }
+            }
+            None => {
+                let value = self.operand(function, jump.parameters()[0]);
+                (format!("{} != 0", value), format!("{} == 0", value))
+            }
+        };
+        match jump.opcode {
+            Opcode::JumpIfTrue => Condition {
+                taken: is_true,
+                not_taken: is_false,
+            },
+            _ => Condition {
+                taken: is_false,
+                not_taken: is_true,
+            },
+        }
+    }
+
+    fn simple_statement(&self, function: &FunctionInfo, instruction: &Instruction) -> String {
+        let parameters = instruction.parameters();
+        match instruction.opcode {
+            Opcode::Output => format!("output({})", self.operand(function, parameters[0])),
+            Opcode::RelativeBaseOffset => match frame_adjustment(instruction) {
+                Some(value) if value < 0 => format!("rb -= {}", -value),
+                _ => format!("rb += {}", self.operand(function, parameters[0])),
+            },
+            Opcode::Halt => String::from("halt"),
+            _ => format!(
+                "{} = {}",
+                self.operand(function, destination(instruction).unwrap()),
+                self.expression(function, instruction)
+            ),
+        }
+    }
+
+    fn call_statement(&self, function: &FunctionInfo, call: &Call) -> String {
+        let parameters = self.functions[&call.target].parameters as MemoryType;
+        let arguments: Vec<String> = (1..=parameters)
+            .map(|slot| match call.arguments.get(&slot) {
+                Some(store) => self.expression(function, store),
+                None => format!("out{}", slot),
+            })
+            .collect();
+        format!(
+            "call {}({})",
+            function_name(call.target),
+            arguments.join(", ")
+        )
+    }
+
+    // Structures the code of `function` in [start, end). `loop_end` is the end of the innermost
+    // enclosing loop; jumps there become `break`. A loop starting at `start` is only detected if
+    // `detect_loop` is set, so that the body of a loop isn't turned into the same loop again.
+    fn structure(
+        &self,
+        function: &FunctionInfo,
+        code: &BTreeSet<usize>,
+        start: usize,
+        end: usize,
+        loop_end: Option<usize>,
+        detect_loop: bool,
+    ) -> Vec<Statement> {
+        let instructions = &self.analysis.instructions;
+        let mut statements = Vec::new();
+        let mut next = start;
+
+        while let Some(&address) = code.range(next..).next().filter(|&&address| address < end) {
+            let instruction = &instructions[&address];
+            next = instruction.next_address();
+            // The label of a loop is printed in front of the loop, not in its body
+            let loop_body = !detect_loop && address == start;
+            if self.analysis.leaders.contains(&address) && address != function.entry && !loop_body {
+                statements.push(Statement::Label(address));
+            }
+
+            let back_jump = code
+                .range(address..end)
+                .rev()
+                .map(|jump| &instructions[jump])
+                .find(|jump| {
+                    immediate_target(jump) == Some(address)
+                        && Analysis::jump_condition(jump) != Some(false)
+                        && !self.calls.contains_key(&jump.address)
+                });
+            if let Some(back_jump) = back_jump.filter(|_| detect_loop || address != start) {
+                let end_of_loop = back_jump.next_address();
+                let head = code
+                    .range(address..back_jump.address)
+                    .find(|head| !self.folded.contains(head))
+                    .map(|head| &instructions[head]);
+
+                let statement = match (Analysis::jump_condition(back_jump), head) {
+                    (Some(true), Some(head))
+                        if Analysis::jump_condition(head).is_none()
+                            && immediate_target(head) == Some(end_of_loop) =>
+                    {
+                        let condition = self.condition(function, head);
+                        let body = self.structure(
+                            function,
+                            code,
+                            head.next_address(),
+                            back_jump.address,
+                            Some(end_of_loop),
+                            true,
+                        );
+                        Statement::While(condition.not_taken, body)
+                    }
+                    (None, _) => {
+                        let condition = self.condition(function, back_jump);
+                        let body = self.structure(
+                            function,
+                            code,
+                            address,
+                            back_jump.address,
+                            Some(end_of_loop),
+                            false,
+                        );
+                        Statement::DoWhile(body, condition.taken)
+                    }
+                    _ => Statement::Loop(self.structure(
+                        function,
+                        code,
+                        address,
+                        back_jump.address,
+                        Some(end_of_loop),
+                        false,
+                    )),
+                };
+                statements.push(statement);
+                next = end_of_loop;
+                continue;
+            }
+
+            if self.folded.contains(&address) {
+                continue;
+            }
+
+            match instruction.opcode {
+                Opcode::JumpIfTrue | Opcode::JumpIfFalse => {}
+                _ => {
+                    statements.push(Statement::Simple(
+                        self.simple_statement(function, instruction),
+                    ));
+                    continue;
+                }
+            }
+
+            if let Some(call) = self.calls.get(&address) {
+                statements.push(Statement::Simple(self.call_statement(function, call)));
+                continue;
+            }
+            if is_return(instruction) {
+                statements.push(Statement::Simple(String::from("return")));
+                continue;
+            }
+
+            let jump = match (immediate_target(instruction), loop_end) {
+                (Some(target), Some(loop_end)) if target == loop_end => Statement::Break,
+                (Some(target), _) => Statement::Goto(target),
+                (None, _) => Statement::Simple(format!(
+                    "goto *{}",
+                    self.operand(function, instruction.parameters()[1])
+                )),
+            };
+            match Analysis::jump_condition(instruction) {
+                Some(true) => statements.push(jump),
+                Some(false) => {}
+                None => {
+                    let condition = self.condition(function, instruction);
+                    let target = match immediate_target(instruction) {
+                        Some(target) if target > address && target <= end => target,
+                        _ => {
+                            statements.push(Statement::If(condition.taken, vec![jump], Vec::new()));
+                            continue;
+                        }
+                    };
+
+                    // if/else: the then branch ends with a jump over the else branch
+                    let else_jump = code
+                        .range(address + 1..target)
+                        .next_back()
+                        .map(|jump| &instructions[jump])
+                        .filter(|jump| {
+                            Analysis::jump_condition(jump) == Some(true)
+                                && !self.calls.contains_key(&jump.address)
+                        })
+                        .and_then(|jump| immediate_target(jump).map(|end| (jump.address, end)))
+                        .filter(|&(_, else_end)| else_end > target && else_end <= end);
+
+                    let (then_end, else_branch) = match else_jump {
+                        Some((then_end, else_end)) => {
+                            next = else_end;
+                            let else_branch =
+                                self.structure(function, code, target, else_end, loop_end, true);
+                            (then_end, else_branch)
+                        }
+                        None => {
+                            next = target;
+                            (target, Vec::new())
+                        }
+                    };
+                    let then_branch = self.structure(
+                        function,
+                        code,
+                        instruction.next_address(),
+                        then_end,
+                        loop_end,
+                        true,
+                    );
+                    statements.push(Statement::If(condition.not_taken, then_branch, else_branch));
+                }
+            }
+        }
+        statements
+    }
+}
+
+fn collect_gotos(statements: &[Statement], targets: &mut BTreeSet<usize>) {
+    for statement in statements {
+        match statement {
+            Statement::Goto(target) => {
+                targets.insert(*target);
+            }
+            Statement::If(_, then_branch, else_branch) => {
+                collect_gotos(then_branch, targets);
+                collect_gotos(else_branch, targets);
+            }
+            Statement::While(_, body) | Statement::DoWhile(body, _) | Statement::Loop(body) => {
+                collect_gotos(body, targets)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn print(result: &mut String, statements: &[Statement], labels: &BTreeSet<usize>, depth: usize) {
+    let indent = "    ".repeat(depth);
+    for statement in statements {
+        let _ = match statement {
+            Statement::Label(address) if labels.contains(address) => {
+                writeln!(result, "{}label_{}:", indent, address)
+            }
+            Statement::Label(_) => Ok(()),
+            Statement::Simple(text) => writeln!(result, "{}{}", indent, text),
+            Statement::If(condition, then_branch, else_branch) => {
+                let _ = writeln!(result, "{}if {} {{", indent, condition);
+                print(result, then_branch, labels, depth + 1);
+                if !else_branch.is_empty() {
+                    let _ = writeln!(result, "{}}} else {{", indent);
+                    print(result, else_branch, labels, depth + 1);
+                }
+                writeln!(result, "{}}}", indent)
+            }
+            Statement::While(condition, body) => {
+                let _ = writeln!(result, "{}while {} {{", indent, condition);
+                print(result, body, labels, depth + 1);
+                writeln!(result, "{}}}", indent)
+            }
+            Statement::DoWhile(body, condition) => {
+                let _ = writeln!(result, "{}do {{", indent);
+                print(result, body, labels, depth + 1);
+                writeln!(result, "{}}} while {}", indent, condition)
+            }
+            Statement::Loop(body) => {
+                let _ = writeln!(result, "{}loop {{", indent);
+                print(result, body, labels, depth + 1);
+                writeln!(result, "{}}}", indent)
+            }
+            Statement::Break => writeln!(result, "{}break", indent),
+            Statement::Goto(target) => writeln!(result, "{}goto label_{}", indent, target),
+        };
+    }
+}
+
+// Functions of `program`: the entry point at address 0 (as a function without a frame) and
+// the targets of all calls.
+pub fn find_functions(program: &[MemoryType]) -> Vec<FunctionInfo> {
+    Decompiler::new(program)
+        .functions
+        .values()
+        .copied()
+        .collect()
+}
+
+// Prints `program` as pseudo-code, one function after the other. Stack slots are named after
+// their role: `return_address`, `argN` and `localN` in the function's own frame, `outN` in the
+// frame of the next call. Other memory cells are printed as `m[address]`.
+pub fn decompile(program: &[MemoryType]) -> String {
+    let decompiler = Decompiler::new(program);
+    let mut result = String::new();
+
+    for function in decompiler.functions.values() {
+        let code = decompiler.function_code(function.entry);
+        let end = code.iter().next_back().map_or(0, |&address| address + 1);
+        let body = decompiler.structure(function, &code, function.entry, end, None, true);
+        let mut labels = BTreeSet::new();
+        collect_gotos(&body, &mut labels);
+
+        if !result.is_empty() {
+            result.push('\n');
+        }
+        if function.entry != 0 {
+            let _ = writeln!(
+                result,
+                "// frame size {}: {} parameters, {} locals",
+                function.frame_size, function.parameters, function.locals
+            );
+        }
+        let parameters: Vec<String> = (0..function.parameters)
+            .map(|i| format!("arg{}", i))
+            .collect();
+        let _ = writeln!(
+            result,
+            "fn {}({}) {{",
+            function_name(function.entry),
+            parameters.join(", ")
+        );
+        print(&mut result, &body, &labels, 1);
+        let _ = writeln!(result, "}}");
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::VecDeque;
+
+    use super::*;
+    use crate::Computer;
+
+    // Reads n and outputs the sum of 1..=n, computed by a function with a loop.
+    const SUM: [MemoryType; 47] = [
+        109, 100, // 0: arb 100
+        203, 1, // 2: in [rb+1]
+        21101, 11, 0, 0, // 4: add 11, 0, [rb+0]
+        1105, 1, 14, // 8: jnz 1, 14
+        204, 1,  // 11: out [rb+1]
+        99, // 13: hlt
+        109, 3, // 14: arb 3
+        21101, 0, 0, -1, // 16: add 0, 0, [rb-1]
+        2107, 0, -2, 50, // 20: lt 0, [rb-2], [50]
+        1006, 50, 38, // 24: jz [50], 38
+        22201, -1, -2, -1, // 27: add [rb-1], [rb-2], [rb-1]
+        21201, -2, -1, -2, // 31: add [rb-2], -1, [rb-2]
+        1105, 1, 20, // 35: jnz 1, 20
+        22101, 0, -1, -2, // 38: add 0, [rb-1], [rb-2]
+        109, -3, // 42: arb -3
+        2105, 1, 0, // 44: jnz 1, [rb+0]
+    ];
+
+    #[test]
+    fn sum_program() {
+        let mut input = VecDeque::new();
+        input.push_back(4);
+        let mut computer = Computer::new(0, &SUM, input, Vec::new());
+        computer.run_program();
+        assert_eq!(&vec![10], computer.get_output());
+    }
+
+    #[test]
+    fn functions() {
+        assert_eq!(
+            vec![
+                FunctionInfo {
+                    entry: 0,
+                    frame_size: 0,
+                    parameters: 0,
+                    locals: 0
+                },
+                FunctionInfo {
+                    entry: 14,
+                    frame_size: 3,
+                    parameters: 1,
+                    locals: 1
+                }
+            ],
+            find_functions(&SUM)
+        );
+    }
+
+    #[test]
+    fn structured_output() {
+        assert_eq!(
+            "fn main() {\n\
+             \x20   rb += 100\n\
+             \x20   out1 = input()\n\
+             \x20   call f14(out1)\n\
+             \x20   output(out1)\n\
+             \x20   halt\n\
+             }\n\
+             \n\
+             // frame size 3: 1 parameters, 1 locals\n\
+             fn f14(arg0) {\n\
+             \x20   local0 = 0\n\
+             \x20   while 0 < arg0 {\n\
+             \x20       local0 = local0 + arg0\n\
+             \x20       arg0 = arg0 - 1\n\
+             \x20   }\n\
+             \x20   arg0 = local0\n\
+             \x20   return\n\
+             }\n",
+            decompile(&SUM)
+        );
+    }
+
+    #[test]
+    fn if_else_and_do_while() {
+        let program = vec![
+            3, 20, // 0: in [20]
+            1007, 20, 5, 21, // 2: lt [20], 5, [21]
+            1006, 21, 15, // 6: jz [21], 15
+            104, 1, // 9: out 1
+            1106, 0, 17, // 11: jz 0, 17
+            99, // 14: hlt (unreachable)
+            104, 2, // 15: out 2
+            1001, 20, -1, 20, // 17: add [20], -1, [20]
+            1005, 20, 17, // 21: jnz [20], 17
+            99, // 24: hlt
+        ];
+        assert_eq!(
+            "fn main() {\n\
+             \x20   m[20] = input()\n\
+             \x20   if m[20] < 5 {\n\
+             \x20       output(1)\n\
+             \x20   } else {\n\
+             \x20       output(2)\n\
+             \x20   }\n\
+             \x20   do {\n\
+             \x20       m[20] = m[20] - 1\n\
+             \x20   } while m[20] != 0\n\
+             \x20   halt\n\
+             }\n",
+            decompile(&program)
+        );
+    }
+}
@@ -0,0 +1,569 @@
+// Structural diff of two programs. Both programs are split into instructions (the code found by
+// static analysis, decoded like the computer decodes them) and data cells. The two streams are
+// aligned by opcode (including the parameter modes), so that instructions whose operands changed
+// still line up. Operands that only changed because the code or data they refer to moved are
+// counted as relocations instead of being reported.
+
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use crate::analysis::Analysis;
+use crate::instruction::{Instruction, Opcode, ParameterMode};
+use crate::MemoryType;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParameterKind {
+    Immediate,
+    // Immediate target of a jump
+    JumpTarget,
+    // Position mode parameter
+    Address,
+    // Relative mode parameter
+    RelativeOffset,
+}
+
+impl fmt::Display for ParameterKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            ParameterKind::Immediate => "immediate",
+            ParameterKind::JumpTarget => "jump target",
+            ParameterKind::Address => "address",
+            ParameterKind::RelativeOffset => "relative offset",
+        };
+        write!(f, "{}", kind)
+    }
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+// Ranges are addresses in the old or the new program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    RemovedCode {
+        old: Range<usize>,
+        instructions: usize,
+    },
+    InsertedCode {
+        new: Range<usize>,
+        instructions: usize,
+    },
+    RemovedData {
+        old: Range<usize>,
+    },
+    InsertedData {
+        new: Range<usize>,
+    },
+    // A run of data cells whose values differ.
+    ChangedData {
+        old: Range<usize>,
+        new: Range<usize>,
+    },
+    ChangedParameter {
+        old: Instruction,
+        new: Instruction,
+        parameter: usize,
+        kind: ParameterKind,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::RemovedCode { old, instructions } => write!(
+                f,
+                "- {}..{}: removed {} instruction{}",
+                old.start,
+                old.end,
+                instructions,
+                plural(*instructions)
+            ),
+            Change::InsertedCode { new, instructions } => write!(
+                f,
+                "+ {}..{}: inserted {} instruction{}",
+                new.start,
+                new.end,
+                instructions,
+                plural(*instructions)
+            ),
+            Change::RemovedData { old } => write!(f, "- {}..{}: removed data", old.start, old.end),
+            Change::InsertedData { new } => {
+                write!(f, "+ {}..{}: inserted data", new.start, new.end)
+            }
+            Change::ChangedData { old, new } => write!(
+                f,
+                "~ {}..{} -> {}..{}: changed data",
+                old.start, old.end, new.start, new.end
+            ),
+            Change::ChangedParameter { old, new, kind, .. } => write!(
+                f,
+                "~ {} -> {}: changed {} ({} -> {})",
+                old.address, new.address, kind, old, new
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramDiff {
+    pub changes: Vec<Change>,
+    // Number of operands that differ, but only because what they refer to moved.
+    pub relocations: usize,
+}
+
+impl ProgramDiff {
+    // True if the programs are the same up to relocation.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for ProgramDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        write!(
+            f,
+            "{} relocated operand{}",
+            self.relocations,
+            plural(self.relocations)
+        )
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Item {
+    Instruction(Instruction),
+    Data(usize, MemoryType),
+}
+
+impl Item {
+    fn address(&self) -> usize {
+        match self {
+            Item::Instruction(instruction) => instruction.address,
+            Item::Data(address, _) => *address,
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Item::Instruction(instruction) => instruction.size(),
+            Item::Data(..) => 1,
+        }
+    }
+
+    fn cells<'a>(&self, program: &'a [MemoryType]) -> &'a [MemoryType] {
+        &program[self.address()..self.address() + self.size()]
+    }
+
+    // Items can only be aligned if their keys are equal: the opcode cell for instructions.
+    fn key(&self, program: &[MemoryType]) -> Option<MemoryType> {
+        match self {
+            Item::Instruction(instruction) => Some(program[instruction.address]),
+            Item::Data(..) => None,
+        }
+    }
+}
+
+fn items(program: &[MemoryType]) -> Vec<Item> {
+    let analysis = Analysis::new(program);
+    let mut items = Vec::new();
+    let mut address = 0;
+    while address < program.len() {
+        let item = match analysis.instructions.get(&address) {
+            // Instructions reaching beyond the end of the program read 0s, which aren't cells
+            // that could be aligned.
+            Some(instruction) if instruction.next_address() <= program.len() => {
+                Item::Instruction(*instruction)
+            }
+            _ => Item::Data(address, program[address]),
+        };
+        address += item.size();
+        items.push(item);
+    }
+    items
+}
+
+enum Edit {
+    Keep(usize, usize),
+    Remove(usize),
+    Insert(usize),
+}
+
+// Aligns the items with the highest score: aligned items score 1, and 2 if their cells are
+// identical. This way, identical instructions are preferred over ones with the same opcode.
+fn align(
+    old: &[Item],
+    new: &[Item],
+    old_program: &[MemoryType],
+    new_program: &[MemoryType],
+) -> Vec<Edit> {
+    let identical = |i: usize, j: usize| {
+        old[i].key(old_program) == new[j].key(new_program)
+            && old[i].cells(old_program) == new[j].cells(new_program)
+    };
+    let score = |i: usize, j: usize| {
+        if old[i].key(old_program) != new[j].key(new_program) {
+            None
+        } else if identical(i, j) {
+            Some(2)
+        } else {
+            Some(1)
+        }
+    };
+
+    // Identical items at the start and the end are aligned directly, which keeps the table
+    // small for programs with local changes.
+    let (n, m) = (old.len(), new.len());
+    let mut prefix = 0;
+    while prefix < n.min(m) && identical(prefix, prefix) {
+        prefix += 1;
+    }
+    let mut suffix = 0;
+    while suffix < n.min(m) - prefix && identical(n - suffix - 1, m - suffix - 1) {
+        suffix += 1;
+    }
+    let (rows, columns) = (n - prefix - suffix, m - prefix - suffix);
+
+    // best[i][j]: highest score for aligning the remaining old[prefix + i..] with
+    // new[prefix + j..]
+    let mut best = vec![0u32; (rows + 1) * (columns + 1)];
+    let index = |i: usize, j: usize| i * (columns + 1) + j;
+    for i in (0..rows).rev() {
+        for j in (0..columns).rev() {
+            let mut value = best[index(i + 1, j)].max(best[index(i, j + 1)]);
+            if let Some(score) = score(prefix + i, prefix + j) {
+                value = value.max(best[index(i + 1, j + 1)] + score);
+            }
+            best[index(i, j)] = value;
+        }
+    }
+
+    let mut edits: Vec<Edit> = (0..prefix).map(|i| Edit::Keep(i, i)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < rows || j < columns {
+        if i < rows && j < columns {
+            if let Some(score) = score(prefix + i, prefix + j) {
+                if best[index(i, j)] == best[index(i + 1, j + 1)] + score {
+                    edits.push(Edit::Keep(prefix + i, prefix + j));
+                    i += 1;
+                    j += 1;
+                    continue;
+                }
+            }
+        }
+        if j == columns || (i < rows && best[index(i, j)] == best[index(i + 1, j)]) {
+            edits.push(Edit::Remove(prefix + i));
+            i += 1;
+        } else {
+            edits.push(Edit::Insert(prefix + j));
+            j += 1;
+        }
+    }
+    edits.extend((0..suffix).map(|k| Edit::Keep(n - suffix + k, m - suffix + k)));
+    edits
+}
+
+// A run of consecutive removed or inserted items of the same kind.
+struct Run {
+    range: Range<usize>,
+    // Number of instructions in the run, `None` for data.
+    instructions: Option<usize>,
+}
+
+fn add_to_runs(runs: &mut Vec<Run>, item: &Item) {
+    let range = item.address()..item.address() + item.size();
+    let is_code = matches!(item, Item::Instruction(_));
+    match runs.last_mut() {
+        Some(run) if run.range.end == range.start && run.instructions.is_some() == is_code => {
+            run.range.end = range.end;
+            if let Some(count) = &mut run.instructions {
+                *count += 1;
+            }
+        }
+        _ => runs.push(Run {
+            range,
+            instructions: if is_code { Some(1) } else { None },
+        }),
+    }
+}
+
+// Adds the pending runs of removed and inserted items to the changes.
+fn flush_runs(changes: &mut Vec<Change>, removed: &mut Vec<Run>, inserted: &mut Vec<Run>) {
+    changes.extend(removed.drain(..).map(|run| match run.instructions {
+        Some(instructions) => Change::RemovedCode {
+            old: run.range,
+            instructions,
+        },
+        None => Change::RemovedData { old: run.range },
+    }));
+    changes.extend(inserted.drain(..).map(|run| match run.instructions {
+        Some(instructions) => Change::InsertedCode {
+            new: run.range,
+            instructions,
+        },
+        None => Change::InsertedData { new: run.range },
+    }));
+}
+
+pub fn diff(old_program: &[MemoryType], new_program: &[MemoryType]) -> ProgramDiff {
+    let old = items(old_program);
+    let new = items(new_program);
+    let edits = align(&old, &new, old_program, new_program);
+
+    // Where the cells of the old program ended up in the new one. The end of the program is
+    // included, since it is often used as the start of free memory (e.g. for a stack).
+    let mut relocation = BTreeMap::new();
+    relocation.insert(old_program.len(), new_program.len());
+    for edit in &edits {
+        if let Edit::Keep(i, j) = *edit {
+            for offset in 0..old[i].size() {
+                relocation.insert(old[i].address() + offset, new[j].address() + offset);
+            }
+        }
+    }
+    let relocate = |value: MemoryType| -> Option<MemoryType> {
+        if value < 0 {
+            return None;
+        }
+        relocation
+            .get(&(value as usize))
+            .map(|&address| address as MemoryType)
+    };
+
+    let mut diff = ProgramDiff {
+        changes: Vec::new(),
+        relocations: 0,
+    };
+    let mut removed = Vec::new();
+    let mut inserted = Vec::new();
+    // Run of changed data cells
+    let mut changed = None;
+    for edit in &edits {
+        let (i, j) = match *edit {
+            Edit::Remove(i) => {
+                diff.changes.extend(changed.take());
+                add_to_runs(&mut removed, &old[i]);
+                continue;
+            }
+            Edit::Insert(j) => {
+                diff.changes.extend(changed.take());
+                add_to_runs(&mut inserted, &new[j]);
+                continue;
+            }
+            Edit::Keep(i, j) => (i, j),
+        };
+        flush_runs(&mut diff.changes, &mut removed, &mut inserted);
+        match (old[i], new[j]) {
+            (Item::Data(a, old_value), Item::Data(b, new_value)) if old_value != new_value => {
+                match &mut changed {
+                    Some(Change::ChangedData { old, new }) if old.end == a && new.end == b => {
+                        old.end += 1;
+                        new.end += 1;
+                    }
+                    _ => {
+                        diff.changes.extend(changed.take());
+                        changed = Some(Change::ChangedData {
+                            old: a..a + 1,
+                            new: b..b + 1,
+                        });
+                    }
+                }
+                continue;
+            }
+            (Item::Data(..), Item::Data(..)) => {}
+            (Item::Instruction(a), Item::Instruction(b)) => compare(&a, &b, &relocate, &mut diff),
+            _ => unreachable!("Only items with the same key are aligned"),
+        }
+        diff.changes.extend(changed.take());
+    }
+    flush_runs(&mut diff.changes, &mut removed, &mut inserted);
+    diff.changes.extend(changed.take());
+    diff
+}
+
+// Compares the parameters of two aligned instructions (with the same opcode and modes).
+fn compare<F>(old: &Instruction, new: &Instruction, relocate: &F, diff: &mut ProgramDiff)
+where
+    F: Fn(MemoryType) -> Option<MemoryType>,
+{
+    let is_jump = matches!(old.opcode, Opcode::JumpIfTrue | Opcode::JumpIfFalse);
+    for (parameter, (a, b)) in old.parameters().iter().zip(new.parameters()).enumerate() {
+        let kind = match a.mode {
+            ParameterMode::Immediate if is_jump && parameter == 1 => ParameterKind::JumpTarget,
+            ParameterMode::Immediate => ParameterKind::Immediate,
+            ParameterMode::Position => ParameterKind::Address,
+            ParameterMode::Relative => ParameterKind::RelativeOffset,
+        };
+        // Addresses should move with what they point to. Immediates usually aren't addresses,
+        // but return addresses are, so a change that matches the relocation is accepted.
+        let expected = match kind {
+            ParameterKind::JumpTarget | ParameterKind::Address => {
+                relocate(a.value).unwrap_or(a.value)
+            }
+            ParameterKind::Immediate | ParameterKind::RelativeOffset => a.value,
+        };
+        if b.value == expected && a.value == b.value {
+            continue;
+        }
+        let relocated = b.value == expected
+            || (kind == ParameterKind::Immediate && relocate(a.value) == Some(b.value));
+        if relocated {
+            diff.relocations += 1;
+        } else {
+            diff.changes.push(Change::ChangedParameter {
+                old: *old,
+                new: *new,
+                parameter,
+                kind,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_programs() {
+        let program = [1101, 1, 2, 5, 99, 0];
+        let diff = diff(&program, &program);
+        assert!(diff.is_empty());
+        assert_eq!(0, diff.relocations);
+    }
+
+    #[test]
+    fn changed_immediate() {
+        let old = [1101, 1, 2, 5, 99, 0];
+        let new = [1101, 1, 3, 5, 99, 0];
+        assert_eq!(
+            vec![Change::ChangedParameter {
+                old: Instruction::decode(&old, 0).unwrap(),
+                new: Instruction::decode(&new, 0).unwrap(),
+                parameter: 1,
+                kind: ParameterKind::Immediate,
+            }],
+            diff(&old, &new).changes
+        );
+    }
+
+    #[test]
+    fn inserted_code_with_relocated_jump() {
+        let old = [1205, 0, 5, 104, 1, 104, 2, 99];
+        let new = [1205, 0, 7, 104, 9, 104, 1, 104, 2, 99];
+        let diff = diff(&old, &new);
+        assert_eq!(
+            vec![Change::InsertedCode {
+                new: 3..5,
+                instructions: 1
+            }],
+            diff.changes
+        );
+        assert_eq!(1, diff.relocations);
+
+        // A jump that doesn't follow its target is a change
+        let new = [1205, 0, 5, 104, 9, 104, 1, 104, 2, 99];
+        let changes = super::diff(&old, &new).changes;
+        assert_eq!(2, changes.len());
+        assert!(matches!(
+            changes[0],
+            Change::ChangedParameter {
+                parameter: 1,
+                kind: ParameterKind::JumpTarget,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn changed_and_inserted_data() {
+        let old = [104, 5, 99, 10, 20, 30];
+        let new = [104, 5, 99, 10, 21, 30, 40];
+        assert_eq!(
+            vec![
+                Change::ChangedData {
+                    old: 4..5,
+                    new: 4..5
+                },
+                Change::InsertedData { new: 6..7 },
+            ],
+            diff(&old, &new).changes
+        );
+    }
+
+    #[test]
+    fn relocated_address() {
+        let old = [4, 4, 99, 0, 7];
+        let new = [4, 5, 99, 0, 1, 7];
+        let diff = diff(&old, &new);
+        assert_eq!(vec![Change::InsertedData { new: 4..5 }], diff.changes);
+        assert_eq!(1, diff.relocations);
+    }
+
+    #[test]
+    fn compiled_programs() {
+        let old = crate::compiler::compile(
+            "
+            fn twice(x) {
+                return x * 2;
+            }
+            fn main() {
+                let n = input();
+                while n > 0 {
+                    output(twice(n));
+                    n = n - 1;
+                }
+            }
+            ",
+        )
+        .unwrap();
+        let new = crate::compiler::compile(
+            "
+            fn twice(x) {
+                return x * 2;
+            }
+            fn main() {
+                let n = input();
+                output(7);
+                while n > 0 {
+                    output(twice(n));
+                    n = n - 1;
+                }
+            }
+            ",
+        )
+        .unwrap();
+        let diff = diff(&old, &new);
+        assert_eq!(1, diff.changes.len());
+        assert!(matches!(
+            diff.changes[0],
+            Change::InsertedCode {
+                instructions: 3,
+                ..
+            }
+        ));
+        assert_eq!(4, diff.relocations);
+    }
+
+    #[test]
+    fn display() {
+        let diff = diff(&[1101, 1, 2, 5, 99, 0, 7], &[1101, 1, 3, 6, 99, 1, 0, 8]);
+        assert_eq!(
+            "~ 0 -> 0: changed immediate (add 1, 2, [5] -> add 1, 3, [6])\n\
+             + 5..6: inserted data\n\
+             ~ 6..7 -> 7..8: changed data\n\
+             1 relocated operand",
+            diff.to_string()
+        );
+    }
+}
@@ -0,0 +1,325 @@
+use core::fmt;
+
+use crate::MemoryType;
+
+const ADD: u32 = 1;
+const MULTIPLY: u32 = 2;
+const INPUT: u32 = 3;
+const OUTPUT: u32 = 4;
+const JUMP_IF_TRUE: u32 = 5;
+const JUMP_IF_FALSE: u32 = 6;
+const LESS_THAN: u32 = 7;
+const EQUALS: u32 = 8;
+const RELATIVE_BASE_OFFSET: u32 = 9;
+const HALT: u32 = 99;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    Add,
+    Multiply,
+    Input,
+    Output,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    RelativeBaseOffset,
+    Halt,
+}
+
+impl Opcode {
+    pub fn from_value(value: u32) -> Option<Self> {
+        match value {
+            ADD => Some(Opcode::Add),
+            MULTIPLY => Some(Opcode::Multiply),
+            INPUT => Some(Opcode::Input),
+            OUTPUT => Some(Opcode::Output),
+            JUMP_IF_TRUE => Some(Opcode::JumpIfTrue),
+            JUMP_IF_FALSE => Some(Opcode::JumpIfFalse),
+            LESS_THAN => Some(Opcode::LessThan),
+            EQUALS => Some(Opcode::Equals),
+            RELATIVE_BASE_OFFSET => Some(Opcode::RelativeBaseOffset),
+            HALT => Some(Opcode::Halt),
+            _ => None,
+        }
+    }
+
+    pub fn value(self) -> u32 {
+        match self {
+            Opcode::Add => ADD,
+            Opcode::Multiply => MULTIPLY,
+            Opcode::Input => INPUT,
+            Opcode::Output => OUTPUT,
+            Opcode::JumpIfTrue => JUMP_IF_TRUE,
+            Opcode::JumpIfFalse => JUMP_IF_FALSE,
+            Opcode::LessThan => LESS_THAN,
+            Opcode::Equals => EQUALS,
+            Opcode::RelativeBaseOffset => RELATIVE_BASE_OFFSET,
+            Opcode::Halt => HALT,
+        }
+    }
+
+    pub fn parameter_count(self) -> usize {
+        match self {
+            Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => 3,
+            Opcode::JumpIfTrue | Opcode::JumpIfFalse => 2,
+            Opcode::Input | Opcode::Output | Opcode::RelativeBaseOffset => 1,
+            Opcode::Halt => 0,
+        }
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Add => "add",
+            Opcode::Multiply => "mul",
+            Opcode::Input => "in",
+            Opcode::Output => "out",
+            Opcode::JumpIfTrue => "jnz",
+            Opcode::JumpIfFalse => "jz",
+            Opcode::LessThan => "lt",
+            Opcode::Equals => "eq",
+            Opcode::RelativeBaseOffset => "arb",
+            Opcode::Halt => "hlt",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ParameterMode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+impl ParameterMode {
+    fn from_value(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(ParameterMode::Position),
+            1 => Some(ParameterMode::Immediate),
+            2 => Some(ParameterMode::Relative),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Parameter {
+    pub mode: ParameterMode,
+    pub value: MemoryType,
+}
+
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.mode {
+            ParameterMode::Position => write!(f, "[{}]", self.value),
+            ParameterMode::Immediate => write!(f, "{}", self.value),
+            ParameterMode::Relative if self.value < 0 => write!(f, "[rb-{}]", -self.value),
+            ParameterMode::Relative => write!(f, "[rb+{}]", self.value),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    InvalidOpcode {
+        address: usize,
+        value: MemoryType,
+    },
+    InvalidParameterMode {
+        address: usize,
+        value: MemoryType,
+        mode: u32,
+    },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidOpcode { address, value } => {
+                write!(f, "Invalid opcode ({}) at position {}!", value, address)
+            }
+            DecodeError::InvalidParameterMode { mode, .. } => {
+                write!(f, "Invalid parameter mode: {}", mode)
+            }
+        }
+    }
+}
+
+// Splits the value of an opcode cell into the opcode and the parameter modes. Modes of unused
+// parameters are `Position`.
+pub(crate) fn decode_opcode(
+    value: MemoryType,
+    address: usize,
+) -> Result<(Opcode, [ParameterMode; 3]), DecodeError> {
+    if !(0..=MemoryType::from(u32::MAX)).contains(&value) {
+        return Err(DecodeError::InvalidOpcode { address, value });
+    }
+    let instruction = value as u32;
+    let opcode = match Opcode::from_value(instruction % 100) {
+        Some(opcode) => opcode,
+        None => return Err(DecodeError::InvalidOpcode { address, value }),
+    };
+
+    let mut modes = [ParameterMode::Position; 3];
+    let mut divisor = 100;
+    for mode in modes.iter_mut().take(opcode.parameter_count()) {
+        let mode_digit = (instruction / divisor) % 10;
+        *mode = match ParameterMode::from_value(mode_digit) {
+            Some(mode) => mode,
+            None => {
+                return Err(DecodeError::InvalidParameterMode {
+                    address,
+                    value,
+                    mode: mode_digit,
+                })
+            }
+        };
+        divisor *= 10;
+    }
+    Ok((opcode, modes))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    pub address: usize,
+    pub opcode: Opcode,
+    parameters: [Parameter; 3],
+}
+
+impl Instruction {
+    // Decodes the instruction starting at `address`. Memory beyond the end of the slice reads as 0,
+    // the same way it does for a running `Computer`.
+    pub fn decode(memory: &[MemoryType], address: usize) -> Result<Self, DecodeError> {
+        let load = |address: usize| memory.get(address).copied().unwrap_or(0);
+
+        let (opcode, modes) = decode_opcode(load(address), address)?;
+        let mut parameters = [Parameter {
+            mode: ParameterMode::Position,
+            value: 0,
+        }; 3];
+        for (i, parameter) in parameters
+            .iter_mut()
+            .enumerate()
+            .take(opcode.parameter_count())
+        {
+            parameter.mode = modes[i];
+            parameter.value = load(address + i + 1);
+        }
+
+        Ok(Self {
+            address,
+            opcode,
+            parameters,
+        })
+    }
+
+    pub fn parameters(&self) -> &[Parameter] {
+        &self.parameters[..self.opcode.parameter_count()]
+    }
+
+    // Number of memory cells occupied by the instruction (including the opcode).
+    pub fn size(&self) -> usize {
+        1 + self.opcode.parameter_count()
+    }
+
+    pub fn next_address(&self) -> usize {
+        self.address + self.size()
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.opcode.mnemonic())?;
+        for (i, parameter) in self.parameters().iter().enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            write!(f, "{}{}", separator, parameter)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_parameter_modes() {
+        let instruction = Instruction::decode(&[1002, 4, 3, 4, 33], 0).unwrap();
+        assert_eq!(Opcode::Multiply, instruction.opcode);
+        assert_eq!(
+            &[
+                Parameter {
+                    mode: ParameterMode::Position,
+                    value: 4
+                },
+                Parameter {
+                    mode: ParameterMode::Immediate,
+                    value: 3
+                },
+                Parameter {
+                    mode: ParameterMode::Position,
+                    value: 4
+                },
+            ],
+            instruction.parameters()
+        );
+        assert_eq!(4, instruction.next_address());
+    }
+
+    #[test]
+    fn decode_beyond_end_of_memory() {
+        let instruction = Instruction::decode(&[109], 0).unwrap();
+        assert_eq!(Opcode::RelativeBaseOffset, instruction.opcode);
+        assert_eq!(0, instruction.parameters()[0].value);
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert_eq!(
+            Err(DecodeError::InvalidOpcode {
+                address: 1,
+                value: 42
+            }),
+            Instruction::decode(&[99, 42], 1)
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidOpcode {
+                address: 0,
+                value: -1
+            }),
+            Instruction::decode(&[-1], 0)
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidParameterMode {
+                address: 0,
+                value: 301,
+                mode: 3
+            }),
+            Instruction::decode(&[301, 0, 0, 0], 0)
+        );
+    }
+
+    #[test]
+    fn display() {
+        let program = vec![
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0,
+        ];
+        let mut address = 0;
+        let mut lines = Vec::new();
+        while address < program.len() {
+            let instruction = Instruction::decode(&program, address).unwrap();
+            lines.push(instruction.to_string());
+            address = instruction.next_address();
+        }
+        assert_eq!(
+            vec![
+                "arb 1",
+                "out [rb-1]",
+                "add [100], 1, [100]",
+                "eq [100], 16, [101]",
+                "jz [101], 0",
+            ],
+            lines
+        );
+    }
+}
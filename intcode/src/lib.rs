@@ -1,4 +1,49 @@
-use std::collections::VecDeque;
+// Without the default `std` feature, the crate builds with `no_std` and only needs `alloc`.
+// The channel adapters, loading programs from files and measuring wall time require `std`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+mod analysis;
+mod bounded;
+#[cfg(feature = "std")]
+mod channel;
+pub mod compiler;
+mod coverage;
+pub mod decompiler;
+pub mod diff;
+mod instruction;
+mod loader;
+mod regions;
+mod report;
+mod scheduler;
+mod self_modification;
+pub mod symbolic;
+pub mod transpiler;
+
+pub use bounded::BoundedQueue;
+pub use coverage::{BranchCount, Coverage};
+pub use instruction::{DecodeError, Instruction, Opcode, Parameter, ParameterMode};
+#[cfg(feature = "std")]
+pub use loader::load_program;
+pub use loader::{parse_program, ParseProgramError};
+pub use regions::{Region, RegionKind, RegionMap};
+pub use report::{RunReport, Termination};
+pub use scheduler::{Blocked, Scheduler, SchedulerError};
+pub use self_modification::{CodeCell, CodeWrite, Hotspot, SelfModification};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TryReadError<E> {
+    // The input has no value right now; try again later.
+    Empty,
+    Failed(E),
+}
 
 pub trait Input<T> {
     type ReadError;
@@ -6,7 +51,7 @@ pub trait Input<T> {
     fn read(&mut self) -> Result<T, Self::ReadError>;
 
     // Non-blocking read.
-    fn try_read(&mut self) -> Option<T>;
+    fn try_read(&mut self) -> Result<T, TryReadError<Self::ReadError>>;
 }
 
 impl<T> Input<T> for VecDeque<T> {
@@ -19,8 +64,8 @@ impl<T> Input<T> for VecDeque<T> {
         }
     }
 
-    fn try_read(&mut self) -> Option<T> {
-        self.pop_front()
+    fn try_read(&mut self) -> Result<T, TryReadError<Self::ReadError>> {
+        self.pop_front().ok_or(TryReadError::Empty)
     }
 }
 
@@ -34,15 +79,27 @@ impl<T> Input<T> for Option<T> {
         }
     }
 
-    fn try_read(&mut self) -> Option<T> {
-        self.take()
+    fn try_read(&mut self) -> Result<T, TryReadError<Self::ReadError>> {
+        self.take().ok_or(TryReadError::Empty)
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TryWriteError<E> {
+    // The output can't take any more values right now; try again later.
+    Full,
+    Failed(E),
+}
+
 pub trait Output<T> {
     type WriteError;
     // Blocking write.
     fn write(&mut self, t: T) -> Result<(), Self::WriteError>;
+
+    // Non-blocking write. Outputs that can't be full just write.
+    fn try_write(&mut self, t: T) -> Result<(), TryWriteError<Self::WriteError>> {
+        self.write(t).map_err(TryWriteError::Failed)
+    }
 }
 
 impl<T> Output<T> for Vec<T> {
@@ -72,35 +129,24 @@ impl<T> Output<T> for Option<T> {
     }
 }
 
-const ADD: u32 = 1;
-const MULTIPLY: u32 = 2;
-const INPUT: u32 = 3;
-const OUTPUT: u32 = 4;
-const JUMP_IF_TRUE: u32 = 5;
-const JUMP_IF_FALSE: u32 = 6;
-const LESS_THAN: u32 = 7;
-const EQUALS: u32 = 8;
-const RELATIVE_BASE_OFFSET: u32 = 9;
-const HALT: u32 = 99;
-
-type MemoryType = i64;
-
-#[derive(Debug, Copy, Clone)]
-enum ParameterMode {
-    Position,
-    Immediate,
-    Relative,
+pub type MemoryType = i64;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Access {
+    Read,
+    Write,
 }
 
-impl From<u32> for ParameterMode {
-    fn from(value: u32) -> Self {
-        match value {
-            0 => ParameterMode::Position,
-            1 => ParameterMode::Immediate,
-            2 => ParameterMode::Relative,
-            mode => panic!("Invalid parameter mode: {}", mode),
-        }
-    }
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BreakReason {
+    // Execution reached a breakpoint (and its condition, if any, was true).
+    Breakpoint,
+    // The instruction at `instruction` accessed a watched address.
+    Watchpoint {
+        address: usize,
+        access: Access,
+        instruction: usize,
+    },
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -108,16 +154,33 @@ pub enum RunState {
     NotYetStarted,
     NeedInput,
     Stopped(MemoryType),
+    // Execution was interrupted before executing the instruction at `ip`.
+    Breakpoint { ip: usize, reason: BreakReason },
+    // The output at `ip` couldn't be written because the output is full. Resuming retries it.
+    OutputFull { ip: usize },
+    // Writing the output at `ip` failed (see `Computer::take_write_error`). Resuming retries it.
+    OutputFailed { ip: usize },
+    // Reading the input at `ip` failed (see `Computer::take_read_error`). Resuming retries it.
+    InputFailed { ip: usize },
+    // The instruction limit was reached before executing the instruction at `ip`.
+    InstructionLimit { ip: usize },
 }
 
+// Conditions are `Send` so that computers can still be moved to other threads.
+type Condition<I, O> = Box<dyn Fn(&Computer<I, O>) -> bool + Send>;
+
 enum NextState {
     ContinueAbsolute(usize),
     ContinueRelative(isize),
     NeedInput,
+    InputFailed,
+    OutputFull,
+    OutputFailed,
     Terminate,
 }
 
-const MAX_MEMORY: usize = 1024 * 1024;
+// Number of memory cells a program can use
+pub const MAX_MEMORY: usize = 1024 * 1024;
 
 pub struct Computer<I: Input<MemoryType>, O: Output<MemoryType>> {
     _id: usize,
@@ -128,11 +191,26 @@ pub struct Computer<I: Input<MemoryType>, O: Output<MemoryType>> {
     ip: usize,
     run_state: RunState,
     relative_base: MemoryType,
+    coverage: Option<Coverage>,
+    self_modification: Option<SelfModification>,
+    breakpoints: BTreeMap<usize, Option<Condition<I, O>>>,
+    watchpoints: BTreeSet<(usize, Access)>,
+    watchpoint_hit: Option<(usize, Access)>,
+    // Address of the breakpoint the computer stopped at, which doesn't stop it again until the
+    // instruction there has completed, even if it suspends in between.
+    breakpoint_hit: Option<usize>,
+    read_error: Option<I::ReadError>,
+    write_error: Option<O::WriteError>,
+    instruction_limit: Option<u64>,
+    instructions: u64,
+    inputs: u64,
+    outputs: u64,
+    wall_time: Duration,
 }
 
 impl<I: Input<MemoryType>, O: Output<MemoryType>> Computer<I, O>
 where
-    I::ReadError: std::fmt::Debug,
+    I::ReadError: core::fmt::Debug,
 {
     pub fn new(id: usize, program: &[MemoryType], input: I, output: O) -> Self {
         Self {
@@ -144,9 +222,170 @@ where
             ip: 0,
             run_state: RunState::NotYetStarted,
             relative_base: 0,
+            coverage: None,
+            self_modification: None,
+            breakpoints: BTreeMap::new(),
+            watchpoints: BTreeSet::new(),
+            watchpoint_hit: None,
+            breakpoint_hit: None,
+            read_error: None,
+            write_error: None,
+            instruction_limit: None,
+            instructions: 0,
+            inputs: 0,
+            outputs: 0,
+            wall_time: Duration::default(),
+        }
+    }
+
+    // Continues a program that was started elsewhere (e.g. by compiled code) in the interpreter.
+    pub(crate) fn from_state(
+        tape: Vec<MemoryType>,
+        ip: usize,
+        relative_base: MemoryType,
+        last_output: MemoryType,
+        input: I,
+        output: O,
+    ) -> Self {
+        Self {
+            _id: 0,
+            tape,
+            input,
+            output,
+            last_output,
+            ip,
+            run_state: RunState::NotYetStarted,
+            relative_base,
+            coverage: None,
+            self_modification: None,
+            breakpoints: BTreeMap::new(),
+            watchpoints: BTreeSet::new(),
+            watchpoint_hit: None,
+            breakpoint_hit: None,
+            read_error: None,
+            write_error: None,
+            instruction_limit: None,
+            instructions: 0,
+            inputs: 0,
+            outputs: 0,
+            wall_time: Duration::default(),
+        }
+    }
+
+    // Starts collecting coverage information (executed instructions and branches).
+    pub fn enable_coverage(&mut self) {
+        if self.coverage.is_none() {
+            self.coverage = Some(Coverage::new());
         }
     }
 
+    pub fn coverage(&self) -> Option<&Coverage> {
+        self.coverage.as_ref()
+    }
+
+    pub fn take_coverage(&mut self) -> Option<Coverage> {
+        self.coverage.take()
+    }
+
+    // Starts tracking which cells are executed as code, and records all stores into them.
+    pub fn enable_self_modification_analysis(&mut self) {
+        if self.self_modification.is_none() {
+            self.self_modification = Some(SelfModification::new());
+        }
+    }
+
+    pub fn self_modification(&self) -> Option<&SelfModification> {
+        self.self_modification.as_ref()
+    }
+
+    pub fn take_self_modification(&mut self) -> Option<SelfModification> {
+        self.self_modification.take()
+    }
+
+    // Interrupts execution (with `RunState::Breakpoint`) before the instruction at `address`
+    // is executed.
+    pub fn add_breakpoint(&mut self, address: usize) {
+        self.breakpoints.insert(address, None);
+    }
+
+    // Like `add_breakpoint`, but only interrupts if `condition` holds at that point.
+    pub fn add_conditional_breakpoint<F>(&mut self, address: usize, condition: F)
+    where
+        F: Fn(&Computer<I, O>) -> bool + Send + 'static,
+    {
+        self.breakpoints.insert(address, Some(Box::new(condition)));
+    }
+
+    pub fn remove_breakpoint(&mut self, address: usize) {
+        self.breakpoints.remove(&address);
+    }
+
+    // Interrupts execution after an instruction read from or wrote to `address`.
+    pub fn add_watchpoint(&mut self, address: usize, access: Access) {
+        self.watchpoints.insert((address, access));
+    }
+
+    pub fn remove_watchpoint(&mut self, address: usize, access: Access) {
+        self.watchpoints.remove(&(address, access));
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn relative_base(&self) -> MemoryType {
+        self.relative_base
+    }
+
+    pub fn peek(&self, address: usize) -> MemoryType {
+        self.load(address)
+    }
+
+    // Writes to memory, growing it as needed. Panics beyond the memory limit, like a store
+    // by the program would.
+    pub fn poke(&mut self, address: usize, value: MemoryType) {
+        self.store(address, value);
+    }
+
+    // Interrupts execution (with `RunState::InstructionLimit`) once `limit` instructions have
+    // been executed in total. Raise or remove the limit to continue.
+    pub fn set_instruction_limit(&mut self, limit: Option<u64>) {
+        self.instruction_limit = limit;
+    }
+
+    pub fn report(&self) -> RunReport {
+        let termination = match self.run_state {
+            RunState::Stopped(_) => Termination::Halted,
+            RunState::InstructionLimit { .. } => Termination::InstructionLimit,
+            RunState::InputFailed { .. } => Termination::InputFailed,
+            RunState::OutputFailed { .. } => Termination::OutputFailed,
+            RunState::NotYetStarted
+            | RunState::NeedInput
+            | RunState::Breakpoint { .. }
+            | RunState::OutputFull { .. } => Termination::Suspended,
+        };
+        RunReport {
+            termination,
+            instructions: self.instructions,
+            ip: self.ip,
+            relative_base: self.relative_base,
+            memory_size: self.tape.len(),
+            inputs: self.inputs,
+            outputs: self.outputs,
+            wall_time: self.wall_time,
+        }
+    }
+
+    // Error of the last failed input (see `RunState::InputFailed`).
+    pub fn take_read_error(&mut self) -> Option<I::ReadError> {
+        self.read_error.take()
+    }
+
+    // Error of the last failed output (see `RunState::OutputFailed`).
+    pub fn take_write_error(&mut self) -> Option<O::WriteError> {
+        self.write_error.take()
+    }
+
     pub fn get_input(&mut self) -> &mut I {
         &mut self.input
     }
@@ -164,25 +403,95 @@ where
             return self.run_state;
         }
 
+        #[cfg(feature = "std")]
+        let start = std::time::Instant::now();
         loop {
+            if let Some(limit) = self.instruction_limit {
+                if self.instructions >= limit {
+                    self.run_state = RunState::InstructionLimit { ip: self.ip };
+                    break;
+                }
+            }
+            if self.breakpoint_hit != Some(self.ip) && self.breakpoint_reached() {
+                self.breakpoint_hit = Some(self.ip);
+                self.run_state = RunState::Breakpoint {
+                    ip: self.ip,
+                    reason: BreakReason::Breakpoint,
+                };
+                break;
+            }
+
+            let ip = self.ip;
             match self.execute_instruction() {
-                NextState::ContinueAbsolute(offset) => self.ip = offset,
+                NextState::ContinueAbsolute(offset) => {
+                    self.instructions += 1;
+                    self.breakpoint_hit = None;
+                    self.ip = offset
+                }
                 NextState::ContinueRelative(offset) => {
+                    self.instructions += 1;
+                    self.breakpoint_hit = None;
                     self.ip = (self.ip as isize + offset) as usize
                 }
                 NextState::NeedInput => {
                     self.run_state = RunState::NeedInput;
                     break;
                 }
+                NextState::InputFailed => {
+                    self.run_state = RunState::InputFailed { ip: self.ip };
+                    break;
+                }
+                NextState::OutputFull => {
+                    self.run_state = RunState::OutputFull { ip: self.ip };
+                    break;
+                }
+                NextState::OutputFailed => {
+                    self.run_state = RunState::OutputFailed { ip: self.ip };
+                    break;
+                }
                 NextState::Terminate => {
+                    self.instructions += 1;
                     self.run_state = RunState::Stopped(self.last_output);
                     break;
                 }
             }
+
+            if let Some((address, access)) = self.watchpoint_hit.take() {
+                self.run_state = RunState::Breakpoint {
+                    ip: self.ip,
+                    reason: BreakReason::Watchpoint {
+                        address,
+                        access,
+                        instruction: ip,
+                    },
+                };
+                break;
+            }
+        }
+        #[cfg(feature = "std")]
+        {
+            self.wall_time += start.elapsed();
         }
         self.run_state
     }
 
+    fn breakpoint_reached(&self) -> bool {
+        if self.breakpoints.is_empty() {
+            return false;
+        }
+        match self.breakpoints.get(&self.ip) {
+            Some(Some(condition)) => condition(self),
+            Some(None) => true,
+            None => false,
+        }
+    }
+
+    fn watch(&mut self, address: usize, access: Access) {
+        if !self.watchpoints.is_empty() && self.watchpoints.contains(&(address, access)) {
+            self.watchpoint_hit = Some((address, access));
+        }
+    }
+
     fn load(&self, address: usize) -> MemoryType {
         if address < self.tape.len() {
             self.tape[address]
@@ -205,14 +514,16 @@ where
         self.tape[address] = value;
     }
 
-    fn load_operand(&self, offset: usize, mode: ParameterMode) -> MemoryType {
-        match mode {
-            ParameterMode::Position => self.load(self.load(offset) as usize),
-            ParameterMode::Immediate => self.load(offset),
+    fn load_operand(&mut self, offset: usize, mode: ParameterMode) -> MemoryType {
+        let address = match mode {
+            ParameterMode::Position => self.load(offset) as usize,
+            ParameterMode::Immediate => return self.load(offset),
             ParameterMode::Relative => {
-                self.load((self.load(offset) as MemoryType + self.relative_base) as usize)
+                (self.load(offset) as MemoryType + self.relative_base) as usize
             }
-        }
+        };
+        self.watch(address, Access::Read);
+        self.load(address)
     }
 
     fn store_operand(&mut self, offset: usize, mode: ParameterMode, value: MemoryType) {
@@ -225,77 +536,131 @@ where
                 panic!("Write to immediate not allowed!");
             }
         };
+        self.watch(output_pos, Access::Write);
+        let old_value = self.load(output_pos);
+        if let Some(analysis) = &mut self.self_modification {
+            analysis.record_store(self.ip, output_pos, old_value, value);
+        }
         self.store(output_pos, value);
     }
 
-    fn should_jump(condition: MemoryType, opcode: u32) -> bool {
+    fn should_jump(condition: MemoryType, opcode: Opcode) -> bool {
         match opcode {
-            JUMP_IF_TRUE => condition != 0,
-            JUMP_IF_FALSE => condition == 0,
-            _ => panic!("Unexpected opcode: {}", opcode),
+            Opcode::JumpIfTrue => condition != 0,
+            Opcode::JumpIfFalse => condition == 0,
+            _ => panic!("Unexpected opcode: {}", opcode.value()),
         }
     }
 
-    fn operation(a: MemoryType, b: MemoryType, opcode: u32) -> MemoryType {
+    fn operation(a: MemoryType, b: MemoryType, opcode: Opcode) -> MemoryType {
         match opcode {
-            ADD => a + b,
-            MULTIPLY => a * b,
-            LESS_THAN => (a < b) as MemoryType,
-            EQUALS => (a == b) as MemoryType,
-            _ => panic!("Unexpected opcode: {}", opcode),
+            Opcode::Add => a + b,
+            Opcode::Multiply => a * b,
+            Opcode::LessThan => (a < b) as MemoryType,
+            Opcode::Equals => (a == b) as MemoryType,
+            _ => panic!("Unexpected opcode: {}", opcode.value()),
         }
     }
 
     fn execute_instruction(&mut self) -> NextState {
-        let instruction = self.load(self.ip) as u32;
-        let opcode = instruction % 100;
+        let instruction = match Instruction::decode(&self.tape, self.ip) {
+            Ok(instruction) => instruction,
+            Err(e) => panic!("{}", e),
+        };
+        let next_state = self.execute(&instruction);
+
+        // A suspended instruction is executed again when resuming, so it only counts once it has
+        // completed.
+        let completed = !matches!(
+            next_state,
+            NextState::NeedInput
+                | NextState::InputFailed
+                | NextState::OutputFull
+                | NextState::OutputFailed
+        );
+        if let Some(coverage) = &mut self.coverage {
+            if completed {
+                coverage.record_instruction(self.ip);
+            }
+        }
+        next_state
+    }
+
+    fn record_code(&mut self, instruction: &Instruction) {
+        if let Some(analysis) = &mut self.self_modification {
+            analysis.record_instruction(instruction);
+        }
+    }
+
+    fn execute(&mut self, instruction: &Instruction) -> NextState {
+        let opcode = instruction.opcode;
         let mut modes = [ParameterMode::Position; 3];
-        modes[0] = ParameterMode::from((instruction / 100) % 10);
-        modes[1] = ParameterMode::from((instruction / 1000) % 10);
-        modes[2] = ParameterMode::from((instruction / 10000) % 10);
+        for (mode, parameter) in modes.iter_mut().zip(instruction.parameters()) {
+            *mode = parameter.mode;
+        }
+
+        // Input and output instructions that suspend are executed again when resuming, so they're
+        // recorded as code once they can't suspend anymore. Every instruction is recorded before
+        // its store though, so that an instruction overwriting itself modifies code.
+        if opcode != Opcode::Input && opcode != Opcode::Output {
+            self.record_code(instruction);
+        }
 
         match opcode {
-            ADD | MULTIPLY | LESS_THAN | EQUALS => {
+            Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => {
                 let a = self.load_operand(self.ip + 1, modes[0]);
                 let b = self.load_operand(self.ip + 2, modes[1]);
                 self.store_operand(self.ip + 3, modes[2], Self::operation(a, b, opcode));
                 NextState::ContinueRelative(4)
             }
-            INPUT => {
-                let input_value = self.input.try_read();
-                let input_value = match input_value {
-                    Some(input_value) => input_value,
-                    None => return NextState::NeedInput,
+            Opcode::Input => {
+                let input_value = match self.input.try_read() {
+                    Ok(input_value) => input_value,
+                    Err(TryReadError::Empty) => return NextState::NeedInput,
+                    Err(TryReadError::Failed(e)) => {
+                        self.read_error = Some(e);
+                        return NextState::InputFailed;
+                    }
                 };
+                self.record_code(instruction);
+                self.inputs += 1;
                 self.store_operand(self.ip + 1, modes[0], input_value);
                 NextState::ContinueRelative(2)
             }
-            OUTPUT => {
+            Opcode::Output => {
                 let output_value = self.load_operand(self.ip + 1, modes[0]);
-                let _ = self.output.write(output_value);
+                match self.output.try_write(output_value) {
+                    Ok(()) => {}
+                    Err(TryWriteError::Full) => return NextState::OutputFull,
+                    Err(TryWriteError::Failed(e)) => {
+                        self.write_error = Some(e);
+                        return NextState::OutputFailed;
+                    }
+                }
+                self.record_code(instruction);
+                self.outputs += 1;
                 self.last_output = output_value;
                 NextState::ContinueRelative(2)
             }
-            JUMP_IF_TRUE | JUMP_IF_FALSE => {
+            Opcode::JumpIfTrue | Opcode::JumpIfFalse => {
                 let condition = self.load_operand(self.ip + 1, modes[0]);
-                if Self::should_jump(condition, opcode) {
+                let jump = Self::should_jump(condition, opcode);
+                if let Some(coverage) = &mut self.coverage {
+                    coverage.record_branch(self.ip, jump);
+                }
+                if jump {
                     let next_ip = self.load_operand(self.ip + 2, modes[1]) as usize;
                     NextState::ContinueAbsolute(next_ip)
                 } else {
                     NextState::ContinueRelative(3)
                 }
             }
-            RELATIVE_BASE_OFFSET => {
+            Opcode::RelativeBaseOffset => {
                 let adjustion = self.load_operand(self.ip + 1, modes[0]);
                 self.relative_base += adjustion;
                 NextState::ContinueRelative(2)
             }
-            HALT => NextState::Terminate,
-            _ => panic!(
-                "Invalid opcode ({}) at position {}!",
-                self.load(self.ip),
-                self.ip
-            ),
+            Opcode::Halt => NextState::Terminate,
         }
     }
 }
@@ -418,4 +783,206 @@ mod tests {
         computer.run_program();
         assert_eq!(vec![1125899906842624], computer.output);
     }
+
+    #[test]
+    fn breakpoint() {
+        let program = vec![104, 1, 104, 2, 104, 3, 99];
+        let mut computer = Computer::new(0, &program, VecDeque::new(), Vec::new());
+        computer.add_breakpoint(2);
+        computer.add_breakpoint(4);
+
+        let breakpoint = |ip| RunState::Breakpoint {
+            ip,
+            reason: BreakReason::Breakpoint,
+        };
+        assert_eq!(breakpoint(2), computer.run_program());
+        assert_eq!(vec![1], computer.output);
+        assert_eq!(breakpoint(4), computer.resume());
+        assert_eq!(vec![1, 2], computer.output);
+
+        computer.remove_breakpoint(4);
+        assert_eq!(RunState::Stopped(3), computer.resume());
+        assert_eq!(vec![1, 2, 3], computer.output);
+    }
+
+    #[test]
+    fn breakpoint_on_input() {
+        let program = vec![3, 7, 4, 7, 99];
+        let mut computer = Computer::new(0, &program, VecDeque::new(), Vec::new());
+        computer.add_breakpoint(0);
+
+        let breakpoint = RunState::Breakpoint {
+            ip: 0,
+            reason: BreakReason::Breakpoint,
+        };
+        assert_eq!(breakpoint, computer.run_program());
+        assert_eq!(RunState::NeedInput, computer.resume());
+        assert_eq!(RunState::NeedInput, computer.resume());
+        computer.input.push_back(5);
+        assert_eq!(RunState::Stopped(5), computer.resume());
+    }
+
+    #[test]
+    fn breakpoint_after_instruction_limit() {
+        // Loops forever, stopping at the breakpoint on every iteration
+        let program = vec![1105, 1, 0];
+        let mut computer = Computer::new(0, &program, VecDeque::new(), Vec::new());
+        computer.add_breakpoint(0);
+
+        let breakpoint = RunState::Breakpoint {
+            ip: 0,
+            reason: BreakReason::Breakpoint,
+        };
+        assert_eq!(breakpoint, computer.run_program());
+        computer.set_instruction_limit(Some(0));
+        assert_eq!(RunState::InstructionLimit { ip: 0 }, computer.resume());
+        computer.set_instruction_limit(None);
+        assert_eq!(breakpoint, computer.resume());
+        assert_eq!(1, computer.report().instructions);
+    }
+
+    #[test]
+    fn conditional_breakpoint() {
+        // Counts down from 3 to 0
+        let program = vec![4, 10, 1001, 10, -1, 10, 1005, 10, 0, 99, 3];
+        let mut computer = Computer::new(0, &program, VecDeque::new(), Vec::new());
+        computer.add_conditional_breakpoint(0, |computer| computer.peek(10) == 1);
+
+        assert_eq!(
+            RunState::Breakpoint {
+                ip: 0,
+                reason: BreakReason::Breakpoint
+            },
+            computer.run_program()
+        );
+        assert_eq!(vec![3, 2], computer.output);
+        assert_eq!(RunState::Stopped(1), computer.resume());
+    }
+
+    #[test]
+    fn watchpoints() {
+        let program = vec![1001, 9, 1, 10, 204, 1, 99, 0, 0, 41];
+        let mut computer = Computer::new(0, &program, VecDeque::new(), Vec::new());
+        computer.add_watchpoint(10, Access::Write);
+        computer.add_watchpoint(10, Access::Read);
+
+        assert_eq!(
+            RunState::Breakpoint {
+                ip: 4,
+                reason: BreakReason::Watchpoint {
+                    address: 10,
+                    access: Access::Write,
+                    instruction: 0
+                }
+            },
+            computer.run_program()
+        );
+        assert_eq!(42, computer.peek(10));
+
+        computer.remove_watchpoint(10, Access::Read);
+        computer.add_watchpoint(9, Access::Read);
+        computer.relative_base = 8;
+        assert_eq!(
+            RunState::Breakpoint {
+                ip: 6,
+                reason: BreakReason::Watchpoint {
+                    address: 9,
+                    access: Access::Read,
+                    instruction: 4
+                }
+            },
+            computer.resume()
+        );
+        assert_eq!(RunState::Stopped(41), computer.resume());
+    }
+
+    #[test]
+    fn bounded_output() {
+        let program = vec![104, 1, 104, 2, 104, 3, 99];
+        let mut computer = Computer::new(0, &program, VecDeque::new(), BoundedQueue::new(2));
+
+        assert_eq!(RunState::OutputFull { ip: 4 }, computer.run_program());
+        assert_eq!(vec![1, 2], computer.output.drain().collect::<Vec<_>>());
+        assert_eq!(RunState::Stopped(3), computer.resume());
+        assert_eq!(vec![3], computer.output.drain().collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn output_to_closed_channel() {
+        let program = vec![104, 1, 104, 2, 99];
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut computer = Computer::new(0, &program, VecDeque::new(), tx);
+        computer.add_breakpoint(2);
+
+        computer.run_program();
+        assert_eq!(Ok(1), rx.recv());
+        drop(rx);
+        assert_eq!(RunState::OutputFailed { ip: 2 }, computer.resume());
+        assert_eq!(Some(2), computer.take_write_error().map(|e| e.0));
+    }
+
+    // A machine whose upstream is gone fails instead of waiting for input forever.
+    #[cfg(feature = "std")]
+    #[test]
+    fn input_from_closed_channel() {
+        let program = vec![3, 0, 3, 0, 99];
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut computer = Computer::new(0, &program, rx, Vec::new());
+
+        tx.send(1).unwrap();
+        assert_eq!(RunState::NeedInput, computer.run_program());
+        drop(tx);
+        assert_eq!(RunState::InputFailed { ip: 2 }, computer.resume());
+        assert_eq!(Some(std::sync::mpsc::RecvError), computer.take_read_error());
+        assert_eq!(Termination::InputFailed, computer.report().termination);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn output_to_bounded_channel() {
+        let program = vec![104, 1, 104, 2, 99];
+        let (tx, rx) = std::sync::mpsc::sync_channel(1);
+        let mut computer = Computer::new(0, &program, VecDeque::new(), tx);
+
+        assert_eq!(RunState::OutputFull { ip: 2 }, computer.run_program());
+        assert_eq!(Ok(1), rx.recv());
+        assert_eq!(RunState::Stopped(2), computer.resume());
+        assert_eq!(Ok(2), rx.recv());
+    }
+
+    #[test]
+    fn run_report() {
+        let program = vec![3, 9, 4, 9, 109, 5, 4, 9, 99, 0];
+        let mut computer = Computer::new(0, &program, VecDeque::new(), Vec::new());
+        assert_eq!(Termination::Suspended, computer.report().termination);
+
+        computer.get_input().push_back(42);
+        assert_eq!(RunState::Stopped(42), computer.run_program());
+        let report = computer.report();
+        assert_eq!(Termination::Halted, report.termination);
+        assert_eq!(5, report.instructions);
+        assert_eq!(8, report.ip);
+        assert_eq!(5, report.relative_base);
+        assert_eq!(10, report.memory_size);
+        assert_eq!(1, report.inputs);
+        assert_eq!(2, report.outputs);
+    }
+
+    #[test]
+    fn instruction_limit() {
+        // Infinite loop
+        let program = vec![1105, 1, 0];
+        let mut computer = Computer::new(0, &program, VecDeque::new(), Vec::new());
+        computer.set_instruction_limit(Some(1000));
+
+        assert_eq!(RunState::InstructionLimit { ip: 0 }, computer.run_program());
+        let report = computer.report();
+        assert_eq!(Termination::InstructionLimit, report.termination);
+        assert_eq!(1000, report.instructions);
+
+        computer.set_instruction_limit(Some(1500));
+        assert_eq!(RunState::InstructionLimit { ip: 0 }, computer.resume());
+        assert_eq!(1500, computer.report().instructions);
+    }
 }
@@ -0,0 +1,66 @@
+// Programs in the puzzle input format: comma separated values, surrounding whitespace is
+// ignored.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::MemoryType;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseProgramError {
+    // Index of the invalid value in the program (not its position in the text).
+    pub index: usize,
+    pub value: String,
+}
+
+impl fmt::Display for ParseProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid value at index {}: {:?}", self.index, self.value)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseProgramError {}
+
+pub fn parse_program(text: &str) -> Result<Vec<MemoryType>, ParseProgramError> {
+    text.trim()
+        .split(',')
+        .enumerate()
+        .map(|(index, value)| {
+            let value = value.trim();
+            value.parse().map_err(|_| ParseProgramError {
+                index,
+                value: String::from(value),
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "std")]
+pub fn load_program<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Vec<MemoryType>> {
+    let text = std::fs::read_to_string(path)?;
+    parse_program(&text).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(Ok(vec![1, -2, 99]), parse_program("1,-2,99\n"));
+        assert_eq!(Ok(vec![1, 2]), parse_program(" 1, 2 "));
+        assert_eq!(
+            Err(ParseProgramError {
+                index: 1,
+                value: String::from("x")
+            }),
+            parse_program("1,x,3")
+        );
+        assert_eq!(
+            "Invalid value at index 0: \"\"",
+            parse_program("").unwrap_err().to_string()
+        );
+    }
+}
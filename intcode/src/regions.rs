@@ -0,0 +1,497 @@
+// Separation of code and data. Disassembling a program linearly is ambiguous, since data (like
+// the screen and score tables of the arcade game) is stored in between and after the code.
+//
+// The region map is computed by abstract interpretation: starting at address 0, all jumps with a
+// statically known target are followed. A cell has a known value if no reachable instruction
+// writes to it, so jumps through constant cells are resolved as well. Writes in relative mode
+// are assumed to go to the stack behind the program. Return addresses (values right behind an
+// unconditional jump that are stored by a reachable instruction) are additional entry points.
+// The opcodes and operands of all reached instructions are code, other cells that they read or
+// write are data, and everything else is unknown. The map can be refined with the coverage of a
+// real run, which also contains code that is only reached through computed jumps.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::ops::Range;
+
+use crate::analysis::Analysis;
+use crate::instruction::{Instruction, Opcode, Parameter, ParameterMode};
+use crate::{Coverage, MemoryType};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RegionKind {
+    // Opcodes and operands of reachable instructions
+    Code,
+    // Cells read or written by the code
+    Data,
+    // Neither reached nor referenced directly, e.g. tables that are indexed with computed
+    // addresses
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub range: Range<usize>,
+    pub kind: RegionKind,
+}
+
+#[derive(Debug, Clone)]
+pub struct RegionMap {
+    program: Vec<MemoryType>,
+    kinds: Vec<RegionKind>,
+    instructions: BTreeMap<usize, Instruction>,
+    // Statically known successors of each instruction. Jumps with computed targets (e.g.
+    // returns from functions) only have their fall through, if any.
+    successors: BTreeMap<usize, Vec<usize>>,
+    // Cells written by the reachable instructions, i.e. without a known value
+    written: BTreeSet<usize>,
+}
+
+impl RegionMap {
+    pub fn new(program: &[MemoryType]) -> Self {
+        let mut map = Self {
+            program: program.to_vec(),
+            kinds: Vec::new(),
+            instructions: BTreeMap::new(),
+            successors: BTreeMap::new(),
+            written: BTreeSet::new(),
+        };
+
+        // Cells found to be written lose their known value, which may change the reachable
+        // code. The set of written cells only grows, so this terminates.
+        loop {
+            map.explore();
+            let written: BTreeSet<usize> =
+                map.instructions.values().filter_map(destination).collect();
+            if written.is_subset(&map.written) {
+                break;
+            }
+            map.written.extend(written);
+        }
+
+        map.classify();
+        map
+    }
+
+    // Adds the instructions executed in a real run. Statically found instructions that overlap
+    // executed ones are dropped, since the trace shows that the analysis got them wrong.
+    pub fn refine(&mut self, coverage: &Coverage) {
+        let executed: Vec<usize> = coverage
+            .executed_addresses()
+            .filter(|&address| address < self.program.len())
+            .collect();
+        for address in executed {
+            if self.instructions.contains_key(&address) {
+                continue;
+            }
+            let instruction = match Instruction::decode(&self.program, address) {
+                Ok(instruction) => instruction,
+                Err(_) => continue,
+            };
+            let range = address..instruction.next_address();
+            let overlapping: Vec<usize> = self
+                .instructions
+                .range(..range.end)
+                .filter(|(_, other)| other.next_address() > range.start)
+                .map(|(&other, _)| other)
+                .filter(|&other| !coverage.is_executed(other))
+                .collect();
+            for other in overlapping {
+                self.instructions.remove(&other);
+                self.successors.remove(&other);
+            }
+            self.successors
+                .insert(address, self.static_successors(&instruction));
+            self.instructions.insert(address, instruction);
+        }
+        self.classify();
+    }
+
+    // Kind of the cell at `address`. Cells beyond the end of the program are unknown.
+    pub fn kind(&self, address: usize) -> RegionKind {
+        self.kinds
+            .get(address)
+            .copied()
+            .unwrap_or(RegionKind::Unknown)
+    }
+
+    // Consecutive cells of the same kind, covering the whole program.
+    pub fn regions(&self) -> Vec<Region> {
+        let mut regions: Vec<Region> = Vec::new();
+        for (address, &kind) in self.kinds.iter().enumerate() {
+            match regions.last_mut() {
+                Some(region) if region.kind == kind => region.range.end = address + 1,
+                _ => regions.push(Region {
+                    range: address..address + 1,
+                    kind,
+                }),
+            }
+        }
+        regions
+    }
+
+    pub fn instruction(&self, address: usize) -> Option<&Instruction> {
+        self.instructions.get(&address)
+    }
+
+    pub fn instructions(&self) -> impl Iterator<Item = &Instruction> + '_ {
+        self.instructions.values()
+    }
+
+    // Lists the instructions, and the data and unknown cells one per line.
+    pub fn disassemble(&self) -> String {
+        let mut result = String::new();
+        let mut address = 0;
+        while address < self.program.len() {
+            if let Some(instruction) = self.instructions.get(&address) {
+                let _ = writeln!(result, "{:>6}: {}", address, instruction);
+                address = instruction.next_address();
+                continue;
+            }
+            let kind = match self.kinds[address] {
+                // Operand of an instruction overlapping the previous one
+                RegionKind::Code => "code",
+                RegionKind::Data => "data",
+                RegionKind::Unknown => "unknown",
+            };
+            let _ = writeln!(result, "{:>6}: {} {}", address, kind, self.program[address]);
+            address += 1;
+        }
+        result
+    }
+
+    // Renders the control flow graph of the code in Graphviz' DOT format, with one node per
+    // basic block.
+    pub fn control_flow_graph(&self) -> String {
+        // Blocks start at jump targets, behind jumps and at instructions that aren't reached by
+        // falling through from another instruction (like return addresses).
+        let fall_through: BTreeSet<usize> = self
+            .instructions
+            .values()
+            .filter(|instruction| !ends_block(instruction))
+            .map(Instruction::next_address)
+            .collect();
+        let mut leaders: BTreeSet<usize> = self
+            .instructions
+            .keys()
+            .copied()
+            .filter(|address| !fall_through.contains(address))
+            .collect();
+        for instruction in self.instructions.values().filter(|i| ends_block(i)) {
+            leaders.extend(&self.successors[&instruction.address]);
+        }
+
+        let mut result = String::new();
+        let _ = writeln!(result, "digraph program {{");
+        let _ = writeln!(result, "    node [shape=box, fontname=\"monospace\"];");
+        for &leader in &leaders {
+            let mut instruction = match self.instructions.get(&leader) {
+                Some(instruction) => instruction,
+                None => continue,
+            };
+            let _ = write!(result, "    n{} [label=\"", leader);
+            loop {
+                let _ = write!(result, "{}: {}\\l", instruction.address, instruction);
+                let next = instruction.next_address();
+                match self.instructions.get(&next) {
+                    Some(next) if !ends_block(instruction) && !leaders.contains(&next.address) => {
+                        instruction = next
+                    }
+                    _ => break,
+                }
+            }
+            let _ = writeln!(result, "\"];");
+
+            let mut successors = self.successors[&instruction.address].clone();
+            successors.dedup();
+            for successor in successors {
+                if self.instructions.contains_key(&successor) {
+                    let _ = writeln!(result, "    n{} -> n{};", leader, successor);
+                }
+            }
+        }
+        let _ = writeln!(result, "}}");
+        result
+    }
+
+    // Value of a parameter, if it is known before the program runs.
+    fn constant(&self, parameter: &Parameter) -> Option<MemoryType> {
+        match parameter.mode {
+            ParameterMode::Immediate => Some(parameter.value),
+            ParameterMode::Position if parameter.value >= 0 => {
+                let address = parameter.value as usize;
+                if address < self.program.len() && !self.written.contains(&address) {
+                    Some(self.program[address])
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    // Whether a jump is taken, if it is known before the program runs.
+    fn jump_condition(&self, instruction: &Instruction) -> Option<bool> {
+        let condition = match instruction.opcode {
+            Opcode::JumpIfTrue | Opcode::JumpIfFalse => instruction.parameters()[0],
+            _ => return None,
+        };
+        let condition = self.constant(&condition)?;
+        if instruction.opcode == Opcode::JumpIfTrue {
+            Some(condition != 0)
+        } else {
+            Some(condition == 0)
+        }
+    }
+
+    fn static_successors(&self, instruction: &Instruction) -> Vec<usize> {
+        match instruction.opcode {
+            Opcode::JumpIfTrue | Opcode::JumpIfFalse => {
+                let condition = self.jump_condition(instruction);
+                let mut successors = Vec::new();
+                if condition != Some(false) {
+                    let target = self.constant(&instruction.parameters()[1]);
+                    if let Some(target) = target.filter(|&target| target >= 0) {
+                        successors.push(target as usize);
+                    }
+                }
+                if condition != Some(true) {
+                    successors.push(instruction.next_address());
+                }
+                successors
+            }
+            Opcode::Halt => Vec::new(),
+            _ => vec![instruction.next_address()],
+        }
+    }
+
+    // Finds the reachable instructions with the currently known cell values.
+    fn explore(&mut self) {
+        self.instructions.clear();
+        self.successors.clear();
+        let mut invalid = BTreeSet::new();
+        let mut entries = vec![0];
+
+        while !entries.is_empty() {
+            let mut worklist = entries;
+            while let Some(address) = worklist.pop() {
+                if address >= self.program.len()
+                    || self.instructions.contains_key(&address)
+                    || invalid.contains(&address)
+                {
+                    continue;
+                }
+                let instruction = match Instruction::decode(&self.program, address) {
+                    Ok(instruction) => instruction,
+                    Err(_) => {
+                        invalid.insert(address);
+                        continue;
+                    }
+                };
+                let successors = self.static_successors(&instruction);
+                worklist.extend(&successors);
+                self.successors.insert(address, successors);
+                self.instructions.insert(address, instruction);
+            }
+
+            let after_jumps: BTreeSet<usize> = self
+                .instructions
+                .values()
+                .filter(|instruction| self.jump_condition(instruction) == Some(true))
+                .map(Instruction::next_address)
+                .collect();
+            entries = self
+                .instructions
+                .values()
+                .filter_map(Analysis::constant_move)
+                .filter(|&value| value >= 0)
+                .map(|value| value as usize)
+                .filter(|address| {
+                    after_jumps.contains(address)
+                        && !self.instructions.contains_key(address)
+                        && !invalid.contains(address)
+                })
+                .collect();
+        }
+    }
+
+    fn classify(&mut self) {
+        self.kinds = vec![RegionKind::Unknown; self.program.len()];
+        for instruction in self.instructions.values() {
+            let end = instruction.next_address().min(self.program.len());
+            for kind in &mut self.kinds[instruction.address..end] {
+                *kind = RegionKind::Code;
+            }
+        }
+        for instruction in self.instructions.values() {
+            for parameter in instruction.parameters() {
+                if parameter.mode != ParameterMode::Position || parameter.value < 0 {
+                    continue;
+                }
+                match self.kinds.get_mut(parameter.value as usize) {
+                    Some(kind) if *kind == RegionKind::Unknown => *kind = RegionKind::Data,
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+// Jumps and halts end basic blocks.
+fn ends_block(instruction: &Instruction) -> bool {
+    matches!(
+        instruction.opcode,
+        Opcode::JumpIfTrue | Opcode::JumpIfFalse | Opcode::Halt
+    )
+}
+
+// Cell written by an instruction, if it is written in position mode.
+fn destination(instruction: &Instruction) -> Option<usize> {
+    let parameter = match instruction.opcode {
+        Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => {
+            instruction.parameters()[2]
+        }
+        Opcode::Input => instruction.parameters()[0],
+        _ => return None,
+    };
+    match parameter.mode {
+        ParameterMode::Position if parameter.value >= 0 => Some(parameter.value as usize),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computer, RunState};
+    use alloc::collections::VecDeque;
+
+    #[test]
+    fn data_behind_code() {
+        // add [7], [8], [9]; out [9]; hlt; followed by three data cells
+        let program = [1, 7, 8, 9, 4, 9, 99, 3, 4, 0];
+        let map = RegionMap::new(&program);
+        assert_eq!(
+            vec![
+                Region {
+                    range: 0..7,
+                    kind: RegionKind::Code
+                },
+                Region {
+                    range: 7..10,
+                    kind: RegionKind::Data
+                },
+            ],
+            map.regions()
+        );
+        assert_eq!(
+            "     0: add [7], [8], [9]\n\
+             \x20    4: out [9]\n\
+             \x20    6: hlt\n\
+             \x20    7: data 3\n\
+             \x20    8: data 4\n\
+             \x20    9: data 0\n",
+            map.disassemble()
+        );
+    }
+
+    #[test]
+    fn jump_through_constant_cell() {
+        let program = [
+            105, 1, 9, // 0: jnz 1, [9]
+            104, 7,  // 3: out 7 (unreachable)
+            99, // 5: hlt (unreachable)
+            104, 8,  // 6: out 8
+            99, // 8: hlt
+            6,  // 9: jump target
+        ];
+        let map = RegionMap::new(&program);
+        let kinds: Vec<_> = map
+            .regions()
+            .iter()
+            .map(|region| (region.range.clone(), region.kind))
+            .collect();
+        assert_eq!(
+            vec![
+                (0..3, RegionKind::Code),
+                (3..6, RegionKind::Unknown),
+                (6..9, RegionKind::Code),
+                (9..10, RegionKind::Data),
+            ],
+            kinds
+        );
+    }
+
+    #[test]
+    fn refine_with_trace() {
+        // Same as above, but the jump target is read from the input
+        let program = [
+            3, 11, // 0: in [11]
+            105, 1, 11, // 2: jnz 1, [11]
+            104, 7,  // 5: out 7
+            99, // 7: hlt
+            104, 8,  // 8: out 8
+            99, // 10: hlt
+            8,  // 11: jump target
+        ];
+        let mut map = RegionMap::new(&program);
+        assert_eq!(RegionKind::Unknown, map.kind(8));
+        assert_eq!(RegionKind::Data, map.kind(11));
+        assert_eq!(2, map.instructions().count());
+
+        let mut computer = Computer::new(0, &program, VecDeque::from(vec![8]), Vec::new());
+        computer.enable_coverage();
+        assert!(matches!(computer.run_program(), RunState::Stopped(_)));
+        map.refine(computer.coverage().unwrap());
+        assert_eq!(RegionKind::Code, map.kind(8));
+        assert_eq!(RegionKind::Code, map.kind(10));
+        assert_eq!(RegionKind::Unknown, map.kind(5));
+        assert_eq!(4, map.instructions().count());
+    }
+
+    #[test]
+    fn return_addresses() {
+        let program = [
+            21101, 7, 0, 0, // 0: add 7, 0, [rb]
+            1105, 1, 10, // 4: jnz 1, 10 (call)
+            104, 1,  // 7: out 1 (return address)
+            99, // 9: hlt
+            2106, 0, 0, // 10: jz 0, [rb] (return)
+        ];
+        let map = RegionMap::new(&program);
+        assert_eq!(
+            vec![Region {
+                range: 0..13,
+                kind: RegionKind::Code
+            }],
+            map.regions()
+        );
+    }
+
+    #[test]
+    fn control_flow_graph() {
+        let program = [
+            3, 12, // 0: in [12]
+            1001, 12, -1, 12, // 2: add [12], -1, [12]
+            1005, 12, 2, // 6: jnz [12], 2
+            104, 0,  // 9: out 0
+            99, // 11: hlt
+            0,  // 12: counter
+        ];
+        assert_eq!(
+            "digraph program {\n\
+             \x20   node [shape=box, fontname=\"monospace\"];\n\
+             \x20   n0 [label=\"0: in [12]\\l\"];\n\
+             \x20   n0 -> n2;\n\
+             \x20   n2 [label=\"2: add [12], -1, [12]\\l6: jnz [12], 2\\l\"];\n\
+             \x20   n2 -> n2;\n\
+             \x20   n2 -> n9;\n\
+             \x20   n9 [label=\"9: out 0\\l11: hlt\\l\"];\n\
+             }\n",
+            RegionMap::new(&program).control_flow_graph()
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use core::fmt;
+use core::time::Duration;
+
+use crate::MemoryType;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Termination {
+    // The program executed a halt instruction.
+    Halted,
+    // The program was interrupted after executing the configured number of instructions.
+    InstructionLimit,
+    // The program couldn't read an input.
+    InputFailed,
+    // The program couldn't write an output.
+    OutputFailed,
+    // The program hasn't finished yet: it waits for input, stopped at a breakpoint or has
+    // a full output (or was never started).
+    Suspended,
+}
+
+impl fmt::Display for Termination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let termination = match self {
+            Termination::Halted => "halted",
+            Termination::InstructionLimit => "instruction limit reached",
+            Termination::InputFailed => "input failed",
+            Termination::OutputFailed => "output failed",
+            Termination::Suspended => "suspended",
+        };
+        write!(f, "{}", termination)
+    }
+}
+
+// Statistics of a program run, see `Computer::report`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RunReport {
+    pub termination: Termination,
+    pub instructions: u64,
+    pub ip: usize,
+    pub relative_base: MemoryType,
+    // Number of memory cells in use at the end of the run. Memory never shrinks, so this is
+    // also the peak memory size.
+    pub memory_size: usize,
+    pub inputs: u64,
+    pub outputs: u64,
+    // Only measured with the `std` feature, zero otherwise.
+    pub wall_time: Duration,
+}
+
+impl fmt::Display for RunReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "termination:   {}", self.termination)?;
+        writeln!(f, "instructions:  {}", self.instructions)?;
+        writeln!(f, "ip:            {}", self.ip)?;
+        writeln!(f, "relative base: {}", self.relative_base)?;
+        writeln!(f, "memory size:   {}", self.memory_size)?;
+        writeln!(f, "inputs:        {}", self.inputs)?;
+        writeln!(f, "outputs:       {}", self.outputs)?;
+        write!(f, "wall time:     {:?}", self.wall_time)
+    }
+}
@@ -0,0 +1,251 @@
+// Runs several computers that feed each other's inputs on a single thread. The machines are
+// resumed round-robin in the order they were added, so execution is deterministic. The wiring
+// table sends all outputs of a machine to the input queue of another machine; outputs of
+// machines without a connection stay in their own output queue.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Computer, MemoryType, RunState};
+
+type Machine = Computer<VecDeque<MemoryType>, VecDeque<MemoryType>>;
+
+// A machine waiting for input that will never arrive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blocked {
+    pub machine: usize,
+    pub ip: usize,
+    // Machines whose outputs are connected to the input the machine waits on. Empty if it
+    // only gets input from outside the scheduler.
+    pub waiting_on: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchedulerError {
+    // All machines that haven't halted wait for input, and none is coming.
+    Deadlock(Vec<Blocked>),
+    // A machine stopped for another reason, e.g. its instruction limit.
+    Interrupted { machine: usize, state: RunState },
+}
+
+impl fmt::Display for SchedulerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchedulerError::Deadlock(blocked) => {
+                write!(f, "Deadlock:")?;
+                for blocked in blocked {
+                    write!(
+                        f,
+                        " machine {} waits for input at ip {}",
+                        blocked.machine, blocked.ip
+                    )?;
+                    match blocked.waiting_on.as_slice() {
+                        [] => write!(f, " (not connected);")?,
+                        machines => write!(f, " from {:?};", machines)?,
+                    }
+                }
+                Ok(())
+            }
+            SchedulerError::Interrupted { machine, state } => {
+                write!(f, "Machine {} was interrupted: {:?}", machine, state)
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Scheduler {
+    machines: Vec<Machine>,
+    states: Vec<RunState>,
+    // Machine that receives the outputs of each machine
+    wiring: Vec<Option<usize>>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Adds a machine running `program` and returns its id.
+    pub fn add(&mut self, program: &[MemoryType]) -> usize {
+        let id = self.machines.len();
+        self.machines
+            .push(Computer::new(id, program, VecDeque::new(), VecDeque::new()));
+        self.states.push(RunState::NotYetStarted);
+        self.wiring.push(None);
+        id
+    }
+
+    // Sends the outputs of `from` to the input of `to`, replacing any previous connection of
+    // `from`. Outputs that are already waiting in the output queue of `from` are moved as well.
+    pub fn connect(&mut self, from: usize, to: usize) {
+        assert!(from < self.machines.len(), "No machine {}", from);
+        assert!(to < self.machines.len(), "No machine {}", to);
+        self.wiring[from] = Some(to);
+        self.forward(from);
+    }
+
+    pub fn push_input(&mut self, machine: usize, value: MemoryType) {
+        self.machines[machine].get_input().push_back(value);
+    }
+
+    pub fn machine(&self, machine: usize) -> &Machine {
+        &self.machines[machine]
+    }
+
+    pub fn machine_mut(&mut self, machine: usize) -> &mut Machine {
+        &mut self.machines[machine]
+    }
+
+    // State of a machine after it was last resumed. The value of `Stopped` is the machine's
+    // last output.
+    pub fn state(&self, machine: usize) -> RunState {
+        self.states[machine]
+    }
+
+    // Outputs of a machine that isn't connected to another one.
+    pub fn output(&mut self, machine: usize) -> &mut VecDeque<MemoryType> {
+        self.machines[machine].get_output()
+    }
+
+    // Resumes the machines round-robin until all of them halted.
+    pub fn run(&mut self) -> Result<(), SchedulerError> {
+        loop {
+            let mut progress = false;
+            for machine in 0..self.machines.len() {
+                match self.states[machine] {
+                    RunState::Stopped(_) => continue,
+                    RunState::NeedInput if self.machines[machine].get_input().is_empty() => {
+                        continue
+                    }
+                    _ => {}
+                }
+
+                let state = self.machines[machine].resume();
+                self.states[machine] = state;
+                progress = true;
+                self.forward(machine);
+
+                if !matches!(state, RunState::Stopped(_) | RunState::NeedInput) {
+                    return Err(SchedulerError::Interrupted { machine, state });
+                }
+            }
+
+            if self
+                .states
+                .iter()
+                .all(|state| matches!(state, RunState::Stopped(_)))
+            {
+                return Ok(());
+            }
+            if !progress {
+                return Err(SchedulerError::Deadlock(self.blocked()));
+            }
+        }
+    }
+
+    // Moves the outputs of a machine to the input of the machine it is connected to.
+    fn forward(&mut self, machine: usize) {
+        if let Some(to) = self.wiring[machine] {
+            while let Some(value) = self.machines[machine].get_output().pop_front() {
+                self.machines[to].get_input().push_back(value);
+            }
+        }
+    }
+
+    fn blocked(&self) -> Vec<Blocked> {
+        (0..self.machines.len())
+            .filter(|&machine| self.states[machine] == RunState::NeedInput)
+            .map(|machine| Blocked {
+                machine,
+                ip: self.machines[machine].ip(),
+                waiting_on: (0..self.machines.len())
+                    .filter(|&from| self.wiring[from] == Some(machine))
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    // Reads two values and outputs their sum, forever
+    const ADDER: [MemoryType; 13] = [3, 100, 3, 101, 1, 100, 101, 102, 4, 102, 1105, 1, 0];
+
+    #[test]
+    fn pipeline() {
+        let mut scheduler = Scheduler::new();
+        let doubler = scheduler.add(&[3, 9, 102, 2, 9, 9, 4, 9, 99, 0]);
+        let incrementer = scheduler.add(&[3, 9, 101, 1, 9, 9, 4, 9, 99, 0]);
+        scheduler.connect(doubler, incrementer);
+        scheduler.push_input(doubler, 20);
+
+        assert_eq!(Ok(()), scheduler.run());
+        assert_eq!(RunState::Stopped(40), scheduler.state(doubler));
+        assert_eq!(RunState::Stopped(41), scheduler.state(incrementer));
+        assert_eq!(vec![41], Vec::from(scheduler.output(incrementer).clone()));
+        assert!(scheduler.output(doubler).is_empty());
+    }
+
+    #[test]
+    fn deadlock() {
+        let mut scheduler = Scheduler::new();
+        let a = scheduler.add(&ADDER);
+        let b = scheduler.add(&ADDER);
+        scheduler.connect(a, b);
+        scheduler.connect(b, a);
+        scheduler.push_input(a, 1);
+        scheduler.push_input(a, 2);
+
+        // a outputs 3 to b, which waits for a second value that never comes
+        let error = scheduler.run().unwrap_err();
+        assert_eq!(
+            SchedulerError::Deadlock(vec![
+                Blocked {
+                    machine: a,
+                    ip: 0,
+                    waiting_on: vec![b]
+                },
+                Blocked {
+                    machine: b,
+                    ip: 2,
+                    waiting_on: vec![a]
+                },
+            ]),
+            error
+        );
+        assert_eq!(
+            "Deadlock: machine 0 waits for input at ip 0 from [1]; \
+             machine 1 waits for input at ip 2 from [0];",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn interrupted() {
+        let mut scheduler = Scheduler::new();
+        let machine = scheduler.add(&[1105, 1, 0]);
+        scheduler
+            .machine_mut(machine)
+            .set_instruction_limit(Some(10));
+        assert_eq!(
+            Err(SchedulerError::Interrupted {
+                machine,
+                state: RunState::InstructionLimit { ip: 0 }
+            }),
+            scheduler.run()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "No machine 1")]
+    fn connect_unknown_machine() {
+        let mut scheduler = Scheduler::new();
+        let machine = scheduler.add(&[99]);
+        scheduler.connect(1, machine);
+    }
+}
@@ -0,0 +1,271 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::instruction::Instruction;
+use crate::MemoryType;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CodeCell {
+    // The cell holding the opcode and parameter modes of an instruction.
+    Opcode,
+    // A cell holding one of the parameters of an instruction.
+    Operand,
+}
+
+// A store into a memory cell that had already been executed as part of an instruction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CodeWrite {
+    pub ip: usize,
+    pub address: usize,
+    pub old_value: MemoryType,
+    pub new_value: MemoryType,
+    pub cell: CodeCell,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hotspot {
+    pub address: usize,
+    pub cell: CodeCell,
+    pub writes: usize,
+    // Addresses of the instructions that wrote to the cell.
+    pub writers: BTreeSet<usize>,
+}
+
+// Tracks which cells have been executed as code, and records every store into one of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelfModification {
+    code: BTreeMap<usize, CodeCell>,
+    writes: Vec<CodeWrite>,
+}
+
+impl SelfModification {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn record_instruction(&mut self, instruction: &Instruction) {
+        // A cell that was executed as an opcode once stays an opcode, even if a jump into the
+        // middle of an instruction later executes it as an operand as well.
+        self.code.insert(instruction.address, CodeCell::Opcode);
+        for address in instruction.address + 1..instruction.next_address() {
+            self.code.entry(address).or_insert(CodeCell::Operand);
+        }
+    }
+
+    pub(crate) fn record_store(
+        &mut self,
+        ip: usize,
+        address: usize,
+        old_value: MemoryType,
+        new_value: MemoryType,
+    ) {
+        if let Some(&cell) = self.code.get(&address) {
+            self.writes.push(CodeWrite {
+                ip,
+                address,
+                old_value,
+                new_value,
+                cell,
+            });
+        }
+    }
+
+    pub fn is_code(&self, address: usize) -> bool {
+        self.code.contains_key(&address)
+    }
+
+    pub fn writes(&self) -> &[CodeWrite] {
+        &self.writes
+    }
+
+    // True if a program ever changed an instruction itself (and not just its operands).
+    pub fn modifies_opcodes(&self) -> bool {
+        self.writes
+            .iter()
+            .any(|write| write.cell == CodeCell::Opcode)
+    }
+
+    // Modified code cells, most frequently written first.
+    pub fn hotspots(&self) -> Vec<Hotspot> {
+        let mut hotspots: BTreeMap<usize, Hotspot> = BTreeMap::new();
+        for write in &self.writes {
+            let hotspot = hotspots.entry(write.address).or_insert_with(|| Hotspot {
+                address: write.address,
+                cell: write.cell,
+                writes: 0,
+                writers: BTreeSet::new(),
+            });
+            hotspot.writes += 1;
+            hotspot.writers.insert(write.ip);
+        }
+
+        let mut hotspots: Vec<Hotspot> = hotspots.into_values().collect();
+        hotspots.sort_by(|a, b| b.writes.cmp(&a.writes).then(a.address.cmp(&b.address)));
+        hotspots
+    }
+
+    pub fn summary(&self) -> String {
+        let hotspots = self.hotspots();
+        let mut result = String::new();
+        let _ = writeln!(
+            result,
+            "{} writes to {} code cells",
+            self.writes.len(),
+            hotspots.len()
+        );
+        if hotspots.is_empty() {
+            return result;
+        }
+
+        let _ = writeln!(
+            result,
+            "{:>8} {:>8}  {:<8} written by",
+            "address", "writes", "cell"
+        );
+        for hotspot in hotspots {
+            let cell = match hotspot.cell {
+                CodeCell::Opcode => "opcode",
+                CodeCell::Operand => "operand",
+            };
+            let writers: Vec<String> = hotspot.writers.iter().map(|ip| ip.to_string()).collect();
+            let _ = writeln!(
+                result,
+                "{:>8} {:>8}  {:<8} {}",
+                hotspot.address,
+                hotspot.writes,
+                cell,
+                writers.join(", ")
+            );
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::VecDeque;
+
+    use super::*;
+    use crate::{Computer, RunState};
+
+    fn analyze(program: &[MemoryType]) -> SelfModification {
+        let mut computer = Computer::new(0, program, VecDeque::new(), Vec::new());
+        computer.enable_self_modification_analysis();
+        computer.run_program();
+        computer.take_self_modification().unwrap()
+    }
+
+    #[test]
+    fn no_self_modification() {
+        let analysis = analyze(&[1, 5, 6, 7, 99, 1, 2, 0]);
+        assert!(analysis.writes().is_empty());
+        assert_eq!("0 writes to 0 code cells\n", analysis.summary());
+    }
+
+    #[test]
+    fn overwrite_own_opcode() {
+        let analysis = analyze(&[1, 0, 0, 0, 99]);
+        assert!(analysis.modifies_opcodes());
+    }
+
+    #[test]
+    fn suspended_instruction() {
+        let mut computer = Computer::new(0, &[3, 5, 99], VecDeque::new(), Vec::new());
+        computer.enable_self_modification_analysis();
+        assert_eq!(RunState::NeedInput, computer.run_program());
+        assert!(!computer.self_modification().unwrap().is_code(0));
+
+        computer.get_input().push_back(1);
+        computer.resume();
+        let analysis = computer.self_modification().unwrap();
+        assert!(analysis.is_code(0) && analysis.is_code(1));
+        assert!(analysis.writes().is_empty());
+    }
+
+    #[test]
+    fn data_is_not_code() {
+        // The add stores into cell 5, which is never executed
+        let analysis = analyze(&[1101, 1, 2, 5, 99, 0]);
+        assert!(analysis.writes().is_empty());
+        assert!(analysis.is_code(3));
+        assert!(!analysis.is_code(5));
+    }
+
+    #[test]
+    fn patched_operand() {
+        // Loop that outputs 1, 2, 3 by incrementing the operand of the output instruction.
+        let program = vec![
+            104, 1, // 0: out 1
+            1001, 1, 1, 1, // 2: add [1], 1, [1]
+            1008, 1, 4, 12, // 6: eq [1], 4, [12]
+            1006, 12, 0, // 10: jz [12], 0
+            99,
+        ];
+        let analysis = analyze(&program);
+
+        // The jz at 10 only becomes code after the first iteration
+        assert_eq!(
+            &[
+                CodeWrite {
+                    ip: 2,
+                    address: 1,
+                    old_value: 1,
+                    new_value: 2,
+                    cell: CodeCell::Operand
+                },
+                CodeWrite {
+                    ip: 2,
+                    address: 1,
+                    old_value: 2,
+                    new_value: 3,
+                    cell: CodeCell::Operand
+                },
+                CodeWrite {
+                    ip: 6,
+                    address: 12,
+                    old_value: 0,
+                    new_value: 0,
+                    cell: CodeCell::Operand
+                },
+            ],
+            &analysis.writes()[..3]
+        );
+        assert_eq!(5, analysis.writes().len());
+        assert!(!analysis.modifies_opcodes());
+
+        let hotspots = analysis.hotspots();
+        assert_eq!(2, hotspots.len());
+        assert_eq!(1, hotspots[0].address);
+        assert_eq!(3, hotspots[0].writes);
+        assert_eq!(12, hotspots[1].address);
+        assert_eq!(2, hotspots[1].writes);
+
+        assert_eq!(
+            "5 writes to 2 code cells\n\
+             \x20address   writes  cell     written by\n\
+             \x20      1        3  operand  2\n\
+             \x20     12        2  operand  6\n",
+            analysis.summary()
+        );
+    }
+
+    #[test]
+    fn patched_opcode() {
+        // Turns the instruction at 0 into a halt after it was executed once
+        let program = vec![1101, 1, 1, 20, 1101, 0, 99, 0, 1105, 1, 0];
+        let analysis = analyze(&program);
+        assert!(analysis.modifies_opcodes());
+        assert_eq!(
+            &[CodeWrite {
+                ip: 4,
+                address: 0,
+                old_value: 1101,
+                new_value: 99,
+                cell: CodeCell::Opcode
+            }],
+            analysis.writes()
+        );
+    }
+}
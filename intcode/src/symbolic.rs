@@ -0,0 +1,812 @@
+// Symbolic execution of intcode programs: selected memory cells and inputs hold symbols instead
+// of numbers, and every value computed from them is tracked as a polynomial over those symbols.
+// Control flow has to stay concrete, so execution fails as soon as a jump, an address or an
+// instruction depends on a symbol.
+
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::RangeInclusive;
+
+use crate::instruction::{decode_opcode, DecodeError, Opcode, ParameterMode};
+use crate::{Computer, MemoryType, RunState, MAX_MEMORY};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(usize);
+
+impl Symbol {
+    // Symbols are numbered in the order they were created, starting at 0.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "x{}", self.0)
+    }
+}
+
+// A polynomial with integer coefficients. Each monomial is stored as the sorted list of its
+// symbols (so x0^2*x1 is [x0, x0, x1]); terms with a coefficient of 0 are never stored. All
+// arithmetic is checked, and returns `None` if a coefficient overflows.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Polynomial {
+    terms: BTreeMap<Vec<Symbol>, MemoryType>,
+}
+
+impl Polynomial {
+    pub fn constant(value: MemoryType) -> Self {
+        let mut polynomial = Self::default();
+        if value != 0 {
+            polynomial.terms.insert(Vec::new(), value);
+        }
+        polynomial
+    }
+
+    pub fn symbol(symbol: Symbol) -> Self {
+        let mut polynomial = Self::default();
+        polynomial.terms.insert(vec![symbol], 1);
+        polynomial
+    }
+
+    fn add_term(&mut self, monomial: Vec<Symbol>, coefficient: MemoryType) -> Option<()> {
+        let entry = self.terms.entry(monomial).or_insert(0);
+        *entry = entry.checked_add(coefficient)?;
+        if *entry == 0 {
+            self.terms.retain(|_, &mut coefficient| coefficient != 0);
+        }
+        Some(())
+    }
+
+    pub fn checked_add(&self, other: &Polynomial) -> Option<Polynomial> {
+        let mut result = self.clone();
+        for (monomial, &coefficient) in &other.terms {
+            result.add_term(monomial.clone(), coefficient)?;
+        }
+        Some(result)
+    }
+
+    pub fn checked_sub(&self, other: &Polynomial) -> Option<Polynomial> {
+        self.checked_add(&other.checked_mul(&Polynomial::constant(-1))?)
+    }
+
+    pub fn checked_mul(&self, other: &Polynomial) -> Option<Polynomial> {
+        let mut result = Polynomial::default();
+        for (a, &coefficient_a) in &self.terms {
+            for (b, &coefficient_b) in &other.terms {
+                let mut monomial: Vec<Symbol> = a.iter().chain(b.iter()).copied().collect();
+                monomial.sort();
+                result.add_term(monomial, coefficient_a.checked_mul(coefficient_b)?)?;
+            }
+        }
+        Some(result)
+    }
+
+    // The value of the polynomial, if it doesn't depend on any symbol.
+    pub fn as_constant(&self) -> Option<MemoryType> {
+        match self.terms.len() {
+            0 => Some(0),
+            1 => self.terms.get(&Vec::new()).copied(),
+            _ => None,
+        }
+    }
+
+    pub fn degree(&self) -> usize {
+        self.terms.keys().map(Vec::len).max().unwrap_or(0)
+    }
+
+    // Replaces `symbol` by `value`.
+    pub fn substitute(&self, symbol: Symbol, value: MemoryType) -> Option<Polynomial> {
+        let mut result = Polynomial::default();
+        for (monomial, &coefficient) in &self.terms {
+            let mut remaining = monomial.clone();
+            remaining.retain(|&s| s != symbol);
+            let power = (monomial.len() - remaining.len()) as u32;
+            result.add_term(
+                remaining,
+                coefficient.checked_mul(value.checked_pow(power)?)?,
+            )?;
+        }
+        Some(result)
+    }
+
+    // Evaluates the polynomial with `values[i]` as the value of the i-th symbol.
+    pub fn evaluate(&self, values: &[MemoryType]) -> Option<MemoryType> {
+        self.terms
+            .iter()
+            .try_fold(0 as MemoryType, |sum, (monomial, &coefficient)| {
+                let term = monomial.iter().try_fold(coefficient, |product, symbol| {
+                    product.checked_mul(values[symbol.0])
+                })?;
+                sum.checked_add(term)
+            })
+    }
+
+    // Finds values for the symbols, with the i-th symbol taken from `ranges[i]`, for which the
+    // polynomial evaluates to `target`. All symbols but the last one are enumerated; if the
+    // polynomial is linear in the last symbol, it is solved for directly. Fails if the
+    // polynomial overflows for some of the values, which then have to be tried concretely.
+    pub fn solve(
+        &self,
+        target: MemoryType,
+        ranges: &[RangeInclusive<MemoryType>],
+    ) -> Result<Option<Vec<MemoryType>>, Overflow> {
+        let mut values = Vec::with_capacity(ranges.len());
+        if self.solve_from(target, ranges, &mut values)? {
+            Ok(Some(values))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn solve_from(
+        &self,
+        target: MemoryType,
+        ranges: &[RangeInclusive<MemoryType>],
+        values: &mut Vec<MemoryType>,
+    ) -> Result<bool, Overflow> {
+        let symbol = Symbol(values.len());
+        let range = match ranges.get(symbol.0) {
+            Some(range) => range.clone(),
+            None => return Ok(self.as_constant() == Some(target)),
+        };
+
+        if symbol.0 + 1 < ranges.len() {
+            for value in range {
+                values.push(value);
+                if self
+                    .substitute(symbol, value)
+                    .ok_or(Overflow)?
+                    .solve_from(target, ranges, values)?
+                {
+                    return Ok(true);
+                }
+                values.pop();
+            }
+            return Ok(false);
+        }
+
+        if self.terms.keys().flatten().any(|&s| s != symbol) {
+            return Ok(false);
+        }
+        if self.degree() > 1 {
+            for value in range {
+                let result = self.substitute(symbol, value).ok_or(Overflow)?;
+                if result.as_constant() == Some(target) {
+                    values.push(value);
+                    return Ok(true);
+                }
+            }
+            return Ok(false);
+        }
+
+        // a * x + b == target
+        let a = self.terms.get(&vec![symbol]).copied().unwrap_or(0);
+        let b = self.terms.get(&Vec::new()).copied().unwrap_or(0);
+        let value = if a == 0 {
+            if b != target {
+                return Ok(false);
+            }
+            *range.start()
+        } else {
+            let difference = target.checked_sub(b).ok_or(Overflow)?;
+            if difference.checked_rem(a).ok_or(Overflow)? != 0 {
+                return Ok(false);
+            }
+            difference.checked_div(a).ok_or(Overflow)?
+        };
+        if range.contains(&value) {
+            values.push(value);
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+}
+
+// A coefficient of a polynomial doesn't fit into a `MemoryType`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+
+        // Highest degree first
+        let mut terms: Vec<(&Vec<Symbol>, MemoryType)> = self
+            .terms
+            .iter()
+            .map(|(monomial, &coefficient)| (monomial, coefficient))
+            .collect();
+        terms.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then(a.cmp(b)));
+
+        for (i, (monomial, coefficient)) in terms.into_iter().enumerate() {
+            let magnitude = coefficient.abs();
+            match (i, coefficient < 0) {
+                (0, false) => {}
+                (0, true) => write!(f, "-")?,
+                (_, false) => write!(f, " + ")?,
+                (_, true) => write!(f, " - ")?,
+            }
+
+            let mut factors = Vec::new();
+            if magnitude != 1 || monomial.is_empty() {
+                factors.push(magnitude.to_string());
+            }
+            let mut symbols = monomial.iter().peekable();
+            while let Some(&symbol) = symbols.next() {
+                let mut power = 1;
+                while symbols.peek() == Some(&&symbol) {
+                    symbols.next();
+                    power += 1;
+                }
+                if power == 1 {
+                    factors.push(symbol.to_string());
+                } else {
+                    factors.push(format!("{}^{}", symbol, power));
+                }
+            }
+            write!(f, "{}", factors.join("*"))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Expression(Polynomial),
+    // Result of a comparison that depends on a symbol, or of a load from an address that
+    // depends on a symbol. Such values can be moved around, but not used for control flow.
+    Unknown,
+}
+
+impl Value {
+    fn constant(value: MemoryType) -> Self {
+        Value::Expression(Polynomial::constant(value))
+    }
+
+    pub fn as_constant(&self) -> Option<MemoryType> {
+        match self {
+            Value::Expression(polynomial) => polynomial.as_constant(),
+            Value::Unknown => None,
+        }
+    }
+
+    fn operation(a: &Value, b: &Value, opcode: Opcode) -> Value {
+        let (a, b) = match (a, b) {
+            (Value::Expression(a), Value::Expression(b)) => (a, b),
+            _ => return Value::Unknown,
+        };
+        // Results that overflow are unknown, which leaves them to the interpreter.
+        match opcode {
+            Opcode::Add => a.checked_add(b).map_or(Value::Unknown, Value::Expression),
+            Opcode::Multiply => a.checked_mul(b).map_or(Value::Unknown, Value::Expression),
+            // Comparisons are only known if both sides differ by a constant
+            Opcode::LessThan | Opcode::Equals => {
+                match a
+                    .checked_sub(b)
+                    .and_then(|difference| difference.as_constant())
+                {
+                    Some(d) if opcode == Opcode::LessThan => Value::constant((d < 0) as MemoryType),
+                    Some(d) => Value::constant((d == 0) as MemoryType),
+                    None => Value::Unknown,
+                }
+            }
+            _ => panic!("Unexpected opcode: {}", opcode.value()),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Expression(polynomial) => write!(f, "{}", polynomial),
+            Value::Unknown => write!(f, "?"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SymbolicError {
+    // The opcode cell at `ip` depends on a symbol.
+    SymbolicInstruction { ip: usize },
+    // The instruction at `ip` stores to an address or adjusts the relative base by a value
+    // that depends on a symbol.
+    SymbolicAddress { ip: usize },
+    // The jump at `ip` has a condition or target that depends on a symbol.
+    SymbolicBranch { ip: usize },
+    // The instruction at `ip` accesses an address outside of the available memory.
+    InvalidAddress { ip: usize, address: MemoryType },
+    InvalidInstruction(DecodeError),
+    // The input instruction at `ip` was executed without any inputs left.
+    NeedInput { ip: usize },
+    // Computing an address or the relative base at `ip` overflowed.
+    Overflow { ip: usize },
+    // The program didn't halt within the step limit (see `SymbolicComputer::set_step_limit`);
+    // `ip` is the next instruction.
+    StepLimit { ip: usize },
+}
+
+impl fmt::Display for SymbolicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SymbolicError::SymbolicInstruction { ip } => {
+                write!(f, "Instruction at position {} depends on a symbol", ip)
+            }
+            SymbolicError::SymbolicAddress { ip } => {
+                write!(f, "Address used at position {} depends on a symbol", ip)
+            }
+            SymbolicError::SymbolicBranch { ip } => {
+                write!(f, "Jump at position {} depends on a symbol", ip)
+            }
+            SymbolicError::InvalidAddress { ip, address } => {
+                write!(f, "Invalid address ({}) at position {}", address, ip)
+            }
+            SymbolicError::InvalidInstruction(e) => write!(f, "{}", e),
+            SymbolicError::NeedInput { ip } => write!(f, "No input left at position {}", ip),
+            SymbolicError::Overflow { ip } => write!(f, "Address overflow at position {}", ip),
+            SymbolicError::StepLimit { ip } => write!(f, "Step limit reached at position {}", ip),
+        }
+    }
+}
+
+// Default number of instructions after which a program is assumed not to halt, both for the
+// symbolic execution and for each run of the concrete fallback.
+pub const STEP_LIMIT: u64 = 10_000_000;
+
+pub struct SymbolicComputer {
+    memory: Vec<Value>,
+    ip: usize,
+    relative_base: MemoryType,
+    input: VecDeque<Value>,
+    output: Vec<Value>,
+    symbol_count: usize,
+    step_limit: u64,
+}
+
+impl SymbolicComputer {
+    pub fn new(program: &[MemoryType]) -> Self {
+        Self {
+            memory: program
+                .iter()
+                .map(|&value| Value::constant(value))
+                .collect(),
+            ip: 0,
+            relative_base: 0,
+            input: VecDeque::new(),
+            output: Vec::new(),
+            symbol_count: 0,
+            step_limit: STEP_LIMIT,
+        }
+    }
+
+    pub fn new_symbol(&mut self) -> Symbol {
+        self.symbol_count += 1;
+        Symbol(self.symbol_count - 1)
+    }
+
+    // Replaces the content of the cell at `address` by a new symbol.
+    pub fn symbolize(&mut self, address: usize) -> Symbol {
+        let symbol = self.new_symbol();
+        self.store(address, Value::Expression(Polynomial::symbol(symbol)));
+        symbol
+    }
+
+    pub fn push_input(&mut self, value: Value) {
+        self.input.push_back(value);
+    }
+
+    // Adds a new symbol as the next input.
+    pub fn push_symbolic_input(&mut self) -> Symbol {
+        let symbol = self.new_symbol();
+        self.push_input(Value::Expression(Polynomial::symbol(symbol)));
+        symbol
+    }
+
+    pub fn value(&self, address: usize) -> Value {
+        match self.memory.get(address) {
+            Some(value) => value.clone(),
+            None => Value::constant(0),
+        }
+    }
+
+    pub fn output(&self) -> &[Value] {
+        &self.output
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    // Stops `run` with `SymbolicError::StepLimit` after `limit` instructions.
+    pub fn set_step_limit(&mut self, limit: u64) {
+        self.step_limit = limit;
+    }
+
+    // Runs the program until it halts or reaches the step limit.
+    pub fn run(&mut self) -> Result<(), SymbolicError> {
+        for _ in 0..self.step_limit {
+            if !self.execute_instruction()? {
+                return Ok(());
+            }
+        }
+        Err(SymbolicError::StepLimit { ip: self.ip })
+    }
+
+    fn store(&mut self, address: usize, value: Value) {
+        if address >= self.memory.len() {
+            if address < MAX_MEMORY {
+                self.memory.resize(address + 1, Value::constant(0));
+            } else {
+                panic!(
+                    "Attempt to resize beyond memory limit [request: {}, limit: {}]",
+                    address, MAX_MEMORY
+                );
+            }
+        }
+        self.memory[address] = value;
+    }
+
+    // The address given by the parameter at `offset`, or None if it depends on a symbol.
+    fn address(&self, offset: usize, mode: ParameterMode) -> Result<Option<usize>, SymbolicError> {
+        let value = match self.value(offset).as_constant() {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let address = match mode {
+            ParameterMode::Position => Some(value),
+            ParameterMode::Relative => value.checked_add(self.relative_base),
+            ParameterMode::Immediate => return Ok(None),
+        };
+        match address {
+            Some(address) => self.checked_address(address).map(Some),
+            None => Err(SymbolicError::Overflow { ip: self.ip }),
+        }
+    }
+
+    fn checked_address(&self, address: MemoryType) -> Result<usize, SymbolicError> {
+        if address < 0 || address as usize >= MAX_MEMORY {
+            Err(SymbolicError::InvalidAddress {
+                ip: self.ip,
+                address,
+            })
+        } else {
+            Ok(address as usize)
+        }
+    }
+
+    fn load_operand(&self, offset: usize, mode: ParameterMode) -> Result<Value, SymbolicError> {
+        if mode == ParameterMode::Immediate {
+            return Ok(self.value(offset));
+        }
+        match self.address(offset, mode)? {
+            Some(address) => Ok(self.value(address)),
+            None => Ok(Value::Unknown),
+        }
+    }
+
+    fn store_operand(
+        &mut self,
+        offset: usize,
+        mode: ParameterMode,
+        value: Value,
+    ) -> Result<(), SymbolicError> {
+        if mode == ParameterMode::Immediate {
+            panic!("Write to immediate not allowed!");
+        }
+        let address = match self.address(offset, mode)? {
+            Some(address) => address,
+            None => return Err(SymbolicError::SymbolicAddress { ip: self.ip }),
+        };
+        self.store(address, value);
+        Ok(())
+    }
+
+    // Returns false once the program halted.
+    fn execute_instruction(&mut self) -> Result<bool, SymbolicError> {
+        let ip = self.ip;
+        let value = match self.value(ip).as_constant() {
+            Some(value) => value,
+            None => return Err(SymbolicError::SymbolicInstruction { ip }),
+        };
+        let (opcode, modes) =
+            decode_opcode(value, ip).map_err(SymbolicError::InvalidInstruction)?;
+
+        match opcode {
+            Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => {
+                let a = self.load_operand(ip + 1, modes[0])?;
+                let b = self.load_operand(ip + 2, modes[1])?;
+                self.store_operand(ip + 3, modes[2], Value::operation(&a, &b, opcode))?;
+            }
+            Opcode::Input => {
+                let input_value = match self.input.pop_front() {
+                    Some(input_value) => input_value,
+                    None => return Err(SymbolicError::NeedInput { ip }),
+                };
+                self.store_operand(ip + 1, modes[0], input_value)?;
+            }
+            Opcode::Output => {
+                let output_value = self.load_operand(ip + 1, modes[0])?;
+                self.output.push(output_value);
+            }
+            Opcode::JumpIfTrue | Opcode::JumpIfFalse => {
+                let condition = match self.load_operand(ip + 1, modes[0])?.as_constant() {
+                    Some(condition) => condition,
+                    None => return Err(SymbolicError::SymbolicBranch { ip }),
+                };
+                let jump = match opcode {
+                    Opcode::JumpIfTrue => condition != 0,
+                    _ => condition == 0,
+                };
+                if jump {
+                    match self.load_operand(ip + 2, modes[1])?.as_constant() {
+                        Some(target) => self.ip = self.checked_address(target)?,
+                        None => return Err(SymbolicError::SymbolicBranch { ip }),
+                    }
+                    return Ok(true);
+                }
+            }
+            Opcode::RelativeBaseOffset => {
+                let adjustment = match self.load_operand(ip + 1, modes[0])?.as_constant() {
+                    Some(adjustment) => adjustment,
+                    None => return Err(SymbolicError::SymbolicAddress { ip }),
+                };
+                self.relative_base = match self.relative_base.checked_add(adjustment) {
+                    Some(relative_base) => relative_base,
+                    None => return Err(SymbolicError::Overflow { ip }),
+                };
+            }
+            Opcode::Halt => return Ok(false),
+        }
+
+        self.ip += 1 + opcode.parameter_count();
+        Ok(true)
+    }
+}
+
+// Finds values for the memory cells in `cells`, each taken from its range, for which the program
+// halts with `target` at `address`. The program is executed symbolically first and the
+// resulting expression solved; if that isn't possible (e.g. because the control flow depends on
+// one of the cells, or the expression overflows), all combinations are run with the interpreter
+// instead. Runs that don't halt within `STEP_LIMIT` instructions don't match.
+pub fn find_cell_values(
+    program: &[MemoryType],
+    cells: &[(usize, RangeInclusive<MemoryType>)],
+    address: usize,
+    target: MemoryType,
+) -> Option<Vec<MemoryType>> {
+    find_cell_values_within(program, cells, address, target, STEP_LIMIT)
+}
+
+fn find_cell_values_within(
+    program: &[MemoryType],
+    cells: &[(usize, RangeInclusive<MemoryType>)],
+    address: usize,
+    target: MemoryType,
+    step_limit: u64,
+) -> Option<Vec<MemoryType>> {
+    let mut computer = SymbolicComputer::new(program);
+    computer.set_step_limit(step_limit);
+    for &(cell, _) in cells {
+        computer.symbolize(cell);
+    }
+    if computer.run().is_ok() {
+        if let Value::Expression(result) = computer.value(address) {
+            let ranges: Vec<RangeInclusive<MemoryType>> =
+                cells.iter().map(|(_, range)| range.clone()).collect();
+            if let Ok(values) = result.solve(target, &ranges) {
+                return values;
+            }
+        }
+    }
+
+    let mut values = Vec::with_capacity(cells.len());
+    if search_concrete(program, cells, address, target, step_limit, &mut values) {
+        Some(values)
+    } else {
+        None
+    }
+}
+
+fn search_concrete(
+    program: &[MemoryType],
+    cells: &[(usize, RangeInclusive<MemoryType>)],
+    address: usize,
+    target: MemoryType,
+    step_limit: u64,
+    values: &mut Vec<MemoryType>,
+) -> bool {
+    if values.len() == cells.len() {
+        let mut program = program.to_vec();
+        for (&(cell, _), &value) in cells.iter().zip(values.iter()) {
+            if cell >= program.len() {
+                program.resize(cell + 1, 0);
+            }
+            program[cell] = value;
+        }
+        let mut computer = Computer::new(0, &program, VecDeque::new(), Vec::new());
+        computer.set_instruction_limit(Some(step_limit));
+        // Programs that hit the instruction limit or fail otherwise don't match
+        return match computer.run_program() {
+            RunState::Stopped(_) => computer.peek(address) == target,
+            _ => false,
+        };
+    }
+
+    for value in cells[values.len()].1.clone() {
+        values.push(value);
+        if search_concrete(program, cells, address, target, step_limit, values) {
+            return true;
+        }
+        values.pop();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polynomial_arithmetic() {
+        let x = Polynomial::symbol(Symbol(0));
+        let y = Polynomial::symbol(Symbol(1));
+        let sum = x
+            .checked_add(&y)
+            .and_then(|sum| sum.checked_add(&Polynomial::constant(-2)))
+            .unwrap();
+        let product = sum.checked_mul(&x).unwrap();
+
+        assert_eq!("x0 + x1 - 2", sum.to_string());
+        assert_eq!("x0^2 + x0*x1 - 2*x0", product.to_string());
+        assert_eq!(2, product.degree());
+        assert_eq!(Some(3 * 3 + 3 * 4 - 2 * 3), product.evaluate(&[3, 4]));
+        assert_eq!("x1 + 1", sum.substitute(Symbol(0), 3).unwrap().to_string());
+        assert_eq!(Some(0), sum.checked_sub(&sum).unwrap().as_constant());
+    }
+
+    #[test]
+    fn overflow() {
+        let x = Polynomial::symbol(Symbol(0));
+        let large = Polynomial::constant(MemoryType::MAX);
+        assert_eq!(None, large.checked_add(&Polynomial::constant(1)));
+        assert_eq!(None, large.checked_mul(&Polynomial::constant(2)));
+
+        let polynomial = x.checked_mul(&large).unwrap();
+        assert_eq!(None, polynomial.evaluate(&[2]));
+        assert_eq!(None, polynomial.substitute(Symbol(0), 2));
+        let square = x.checked_mul(&x).unwrap();
+        assert_eq!(None, square.substitute(Symbol(0), MemoryType::MAX));
+        assert_eq!(Err(Overflow), polynomial.solve(0, &[2..=3, 0..=1]));
+        let linear = x.checked_add(&Polynomial::constant(-1)).unwrap();
+        assert_eq!(Err(Overflow), linear.solve(MemoryType::MAX, &[0..=1]));
+    }
+
+    #[test]
+    fn solve() {
+        // 100 * x0 + x1 == 1234
+        let polynomial = Polynomial::symbol(Symbol(0))
+            .checked_mul(&Polynomial::constant(100))
+            .and_then(|product| product.checked_add(&Polynomial::symbol(Symbol(1))))
+            .unwrap();
+        assert_eq!(
+            Ok(Some(vec![12, 34])),
+            polynomial.solve(1234, &[0..=99, 0..=99])
+        );
+        assert_eq!(Ok(None), polynomial.solve(1234, &[0..=9, 0..=99]));
+
+        // x0^2 == 49
+        let x = Polynomial::symbol(Symbol(0));
+        let square = x.checked_mul(&x).unwrap();
+        assert_eq!(Ok(Some(vec![-7])), square.solve(49, &[-10..=10]));
+    }
+
+    #[test]
+    fn symbolic_memory() {
+        // Like day 2: the first instruction reads from the symbolic addresses, but its result
+        // is overwritten right away.
+        let program = vec![1, 0, 0, 3, 1, 1, 2, 3, 2, 3, 13, 0, 99, 7];
+        let mut computer = SymbolicComputer::new(&program);
+        computer.symbolize(1);
+        computer.symbolize(2);
+        computer.run().unwrap();
+
+        assert_eq!("7*x0 + 7*x1", computer.value(0).to_string());
+        assert_eq!(
+            Some(vec![1, 9]),
+            find_cell_values(&program, &[(1, 0..=9), (2, 0..=9)], 0, 70)
+        );
+    }
+
+    #[test]
+    fn symbolic_io() {
+        // Outputs 3 * input and whether that is less than 5
+        let program = vec![3, 20, 102, 3, 20, 20, 4, 20, 1007, 20, 5, 21, 4, 21, 99];
+        let mut computer = SymbolicComputer::new(&program);
+        computer.push_symbolic_input();
+        computer.run().unwrap();
+        assert_eq!(
+            vec![
+                Value::Expression(
+                    Polynomial::symbol(Symbol(0))
+                        .checked_mul(&Polynomial::constant(3))
+                        .unwrap()
+                ),
+                Value::Unknown
+            ],
+            computer.output()
+        );
+    }
+
+    #[test]
+    fn overflow_fallback() {
+        // Computes x0 * x1 * max + x0 * x1 * max, whose coefficient overflows even though the
+        // result doesn't if one of the cells is 0
+        let max = MemoryType::MAX;
+        let mut program = vec![2, 20, 21, 22, 1002, 22, max, 22, 1, 22, 22, 0, 99];
+        program.resize(23, 0);
+        let mut computer = SymbolicComputer::new(&program);
+        computer.symbolize(20);
+        computer.symbolize(21);
+        computer.run().unwrap();
+        assert_eq!(Value::Unknown, computer.value(0));
+
+        assert_eq!(
+            Some(vec![0, 0]),
+            find_cell_values(&program, &[(20, 0..=0), (21, 0..=3)], 0, 0)
+        );
+    }
+
+    #[test]
+    fn concrete_fallback() {
+        // Stores 2 at address 0 unless the cell at 9 is 0
+        let program = vec![1006, 9, 7, 1101, 1, 1, 0, 99, 0, 0];
+        let mut computer = SymbolicComputer::new(&program);
+        computer.symbolize(9);
+        assert_eq!(Err(SymbolicError::SymbolicBranch { ip: 0 }), computer.run());
+
+        assert_eq!(
+            Some(vec![1]),
+            find_cell_values(&program, &[(9, 0..=5)], 0, 2)
+        );
+        assert_eq!(None, find_cell_values(&program, &[(9, 0..=5)], 0, 3));
+    }
+
+    #[test]
+    fn non_halting_programs() {
+        // Loops forever unless the cell at 12 is non-zero, then stores 2 at address 0
+        let program = vec![1005, 12, 7, 1105, 1, 3, 0, 1101, 1, 1, 0, 99, 0];
+        let mut computer = SymbolicComputer::new(&program);
+        computer.set_step_limit(1000);
+        assert_eq!(Err(SymbolicError::StepLimit { ip: 3 }), computer.run());
+
+        assert_eq!(
+            Some(vec![1]),
+            find_cell_values_within(&program, &[(12, 0..=5)], 0, 2, 1000)
+        );
+    }
+
+    #[test]
+    fn address_overflow() {
+        // Adjusts the relative base by the maximum twice
+        let max = MemoryType::MAX;
+        let program = vec![109, max, 109, max, 99];
+        assert_eq!(
+            Err(SymbolicError::Overflow { ip: 2 }),
+            SymbolicComputer::new(&program).run()
+        );
+
+        // Reads from [rb + max] with rb = 1
+        let program = vec![109, 1, 204, max, 99];
+        assert_eq!(
+            Err(SymbolicError::Overflow { ip: 2 }),
+            SymbolicComputer::new(&program).run()
+        );
+    }
+}
@@ -0,0 +1,622 @@
+// Ahead-of-time translation of a fixed intcode image into Rust source.
+//
+// The generated code dispatches over the basic blocks that can be found statically (starting at
+// address 0 and following jumps with immediate targets). Whenever the program leaves the known
+// code (indirect jump to an unknown address) or writes into one of its own instructions, the
+// compiled code bails out and execution continues in the interpreter (`Computer`).
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::analysis::Analysis;
+use crate::instruction::{Instruction, Opcode, ParameterMode};
+use crate::{Computer, Input, MemoryType, Output, RunState, MAX_MEMORY};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Exit {
+    NeedInput,
+    // The output is full; resuming retries the output at `Machine::ip`.
+    OutputFull,
+    Halt,
+    // Either the program wrote into its own code, jumped to an address that isn't the start
+    // of a compiled block, failed to read an input or write an output or executed an invalid
+    // instruction.
+    // `Machine::ip` points to the next instruction to execute.
+    Fallback,
+}
+
+// State of a running compiled program. The fields are public so that the generated code
+// can access them directly.
+pub struct Machine<I, O> {
+    pub memory: Vec<MemoryType>,
+    pub ip: usize,
+    pub relative_base: MemoryType,
+    pub last_output: MemoryType,
+    pub input: I,
+    pub output: O,
+    code: Vec<bool>,
+}
+
+impl<I, O> Machine<I, O> {
+    fn new(program: &[MemoryType], code: &[(usize, usize)], input: I, output: O) -> Self {
+        let mut code_cells = vec![false; program.len()];
+        for &(start, end) in code {
+            for cell in &mut code_cells[start..end] {
+                *cell = true;
+            }
+        }
+
+        Self {
+            memory: program.to_vec(),
+            ip: 0,
+            relative_base: 0,
+            last_output: 0,
+            input,
+            output,
+            code: code_cells,
+        }
+    }
+
+    pub fn load(&self, address: usize) -> MemoryType {
+        if address < self.memory.len() {
+            self.memory[address]
+        } else {
+            0
+        }
+    }
+
+    // Returns true if the store modified a cell that belongs to the compiled code.
+    pub fn store(&mut self, address: usize, value: MemoryType) -> bool {
+        if address >= self.memory.len() {
+            if address < MAX_MEMORY {
+                self.memory.resize(address + 1, 0);
+            } else {
+                panic!(
+                    "Attempt to resize beyond memory limit [request: {}, limit: {}]",
+                    address, MAX_MEMORY
+                );
+            }
+        }
+        self.memory[address] = value;
+        address < self.code.len() && self.code[address]
+    }
+}
+
+enum Engine<I: Input<MemoryType>, O: Output<MemoryType>> {
+    Compiled(Machine<I, O>, fn(&mut Machine<I, O>) -> Exit),
+    Interpreted(Box<Computer<I, O>>),
+    // Only used temporarily while switching from compiled to interpreted execution.
+    Switching,
+}
+
+// Drop-in replacement for `Computer` that runs a program generated by `transpile`.
+pub struct CompiledComputer<I: Input<MemoryType>, O: Output<MemoryType>> {
+    engine: Engine<I, O>,
+    run_state: RunState,
+}
+
+impl<I: Input<MemoryType>, O: Output<MemoryType>> CompiledComputer<I, O>
+where
+    I::ReadError: core::fmt::Debug,
+{
+    pub fn new(
+        program: &[MemoryType],
+        code: &[(usize, usize)],
+        input: I,
+        output: O,
+        run: fn(&mut Machine<I, O>) -> Exit,
+    ) -> Self {
+        Self {
+            engine: Engine::Compiled(Machine::new(program, code, input, output), run),
+            run_state: RunState::NotYetStarted,
+        }
+    }
+
+    pub fn get_input(&mut self) -> &mut I {
+        match &mut self.engine {
+            Engine::Compiled(machine, _) => &mut machine.input,
+            Engine::Interpreted(computer) => computer.get_input(),
+            Engine::Switching => unreachable!(),
+        }
+    }
+
+    pub fn get_output(&mut self) -> &mut O {
+        match &mut self.engine {
+            Engine::Compiled(machine, _) => &mut machine.output,
+            Engine::Interpreted(computer) => computer.get_output(),
+            Engine::Switching => unreachable!(),
+        }
+    }
+
+    // Returns true as long as the program hasn't fallen back to the interpreter.
+    pub fn is_compiled(&self) -> bool {
+        matches!(self.engine, Engine::Compiled(_, _))
+    }
+
+    pub fn run_program(&mut self) -> RunState {
+        self.resume()
+    }
+
+    pub fn resume(&mut self) -> RunState {
+        if let RunState::Stopped(_) = self.run_state {
+            return self.run_state;
+        }
+
+        if let Engine::Compiled(machine, run) = &mut self.engine {
+            match run(machine) {
+                Exit::NeedInput => {
+                    self.run_state = RunState::NeedInput;
+                    return self.run_state;
+                }
+                Exit::OutputFull => {
+                    self.run_state = RunState::OutputFull { ip: machine.ip };
+                    return self.run_state;
+                }
+                Exit::Halt => {
+                    self.run_state = RunState::Stopped(machine.last_output);
+                    return self.run_state;
+                }
+                Exit::Fallback => {
+                    let machine = match core::mem::replace(&mut self.engine, Engine::Switching) {
+                        Engine::Compiled(machine, _) => machine,
+                        _ => unreachable!(),
+                    };
+                    self.engine = Engine::Interpreted(Box::new(Computer::from_state(
+                        machine.memory,
+                        machine.ip,
+                        machine.relative_base,
+                        machine.last_output,
+                        machine.input,
+                        machine.output,
+                    )));
+                }
+            }
+        }
+
+        self.run_state = match &mut self.engine {
+            Engine::Interpreted(computer) => computer.resume(),
+            _ => unreachable!(),
+        };
+        self.run_state
+    }
+}
+
+fn load_expression(instruction: &Instruction, index: usize) -> String {
+    let cell = instruction.address + 1 + index;
+    match instruction.parameters()[index].mode {
+        ParameterMode::Position => format!("m.load(m.load({}) as usize)", cell),
+        ParameterMode::Immediate => format!("m.load({})", cell),
+        ParameterMode::Relative => format!("m.load((m.relative_base + m.load({})) as usize)", cell),
+    }
+}
+
+// Stores `v` at the address given by the instruction's last parameter, which must not be an
+// immediate (see `writes_to_immediate`).
+fn emit_store(code: &mut String, instruction: &Instruction) {
+    let index = instruction.parameters().len() - 1;
+    let cell = instruction.address + 1 + index;
+    let address = match instruction.parameters()[index].mode {
+        ParameterMode::Relative => format!("(m.relative_base + m.load({})) as usize", cell),
+        _ => format!("m.load({}) as usize", cell),
+    };
+    let _ = writeln!(
+        code,
+        "                if m.store({}, v) {{ m.ip = {}; return Exit::Fallback; }}",
+        address,
+        instruction.next_address()
+    );
+}
+
+// Instructions that store to an immediate parameter are invalid.
+fn writes_to_immediate(instruction: &Instruction) -> bool {
+    match instruction.opcode {
+        Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals | Opcode::Input => {
+            instruction
+                .parameters()
+                .last()
+                .map(|parameter| parameter.mode)
+                == Some(ParameterMode::Immediate)
+        }
+        _ => false,
+    }
+}
+
+// Emits the code of a single instruction. Returns false if the instruction ends the block.
+fn emit_instruction(
+    code: &mut String,
+    instruction: &Instruction,
+    leaders: &BTreeSet<usize>,
+) -> bool {
+    let parameters = instruction.parameters();
+    let _ = writeln!(
+        code,
+        "                // {:>6}: {}",
+        instruction.address, instruction
+    );
+
+    // Leave the error to the interpreter, like for any other invalid instruction
+    if writes_to_immediate(instruction) {
+        let _ = writeln!(
+            code,
+            "                m.ip = {}; return Exit::Fallback;",
+            instruction.address
+        );
+        return false;
+    }
+
+    match instruction.opcode {
+        Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => {
+            let a = load_expression(instruction, 0);
+            let b = load_expression(instruction, 1);
+            let value = match instruction.opcode {
+                Opcode::Add => format!("{} + {}", a, b),
+                Opcode::Multiply => format!("{} * {}", a, b),
+                Opcode::LessThan => format!("({} < {}) as MemoryType", a, b),
+                _ => format!("({} == {}) as MemoryType", a, b),
+            };
+            let _ = writeln!(code, "                let v = {};", value);
+            emit_store(code, instruction);
+            true
+        }
+        Opcode::Input => {
+            let _ = writeln!(
+                code,
+                "                let v = match m.input.try_read() {{ Ok(v) => v, Err(TryReadError::Empty) => {{ m.ip = {0}; return Exit::NeedInput; }} Err(_) => {{ m.ip = {0}; return Exit::Fallback; }} }};",
+                instruction.address
+            );
+            emit_store(code, instruction);
+            true
+        }
+        Opcode::Output => {
+            let _ = writeln!(
+                code,
+                "                let v = {}; match m.output.try_write(v) {{ Ok(()) => {{}} Err(TryWriteError::Full) => {{ m.ip = {1}; return Exit::OutputFull; }} Err(_) => {{ m.ip = {1}; return Exit::Fallback; }} }} m.last_output = v;",
+                load_expression(instruction, 0),
+                instruction.address
+            );
+            true
+        }
+        Opcode::JumpIfTrue | Opcode::JumpIfFalse => {
+            let jump = match (parameters[1].mode, parameters[1].value) {
+                (ParameterMode::Immediate, target) if leaders.contains(&(target as usize)) => {
+                    format!("pc = {}; continue;", target as usize)
+                }
+                _ => format!(
+                    "pc = {} as usize; continue;",
+                    load_expression(instruction, 1)
+                ),
+            };
+            match Analysis::jump_condition(instruction) {
+                Some(true) => {
+                    let _ = writeln!(code, "                {}", jump);
+                    false
+                }
+                Some(false) => true,
+                None => {
+                    let comparison = if instruction.opcode == Opcode::JumpIfTrue {
+                        "!="
+                    } else {
+                        "=="
+                    };
+                    let _ = writeln!(
+                        code,
+                        "                if {} {} 0 {{ {} }}",
+                        load_expression(instruction, 0),
+                        comparison,
+                        jump
+                    );
+                    true
+                }
+            }
+        }
+        Opcode::RelativeBaseOffset => {
+            let _ = writeln!(
+                code,
+                "                m.relative_base += {};",
+                load_expression(instruction, 0)
+            );
+            true
+        }
+        Opcode::Halt => {
+            let _ = writeln!(
+                code,
+                "                m.ip = {}; return Exit::Halt;",
+                instruction.address
+            );
+            false
+        }
+    }
+}
+
+// Translates `program` into Rust source defining a module `name` with
+// - `PROGRAM`: the original image,
+// - `run`: the compiled code, and
+// - `new(input, output)`: a constructor for a `CompiledComputer` running it.
+//
+// The output is meant to be written from a build script and pulled in with `include!`.
+pub fn transpile(program: &[MemoryType], name: &str) -> String {
+    let analysis = Analysis::new(program);
+    // Execution also resumes at output instructions after the output was full
+    let mut leaders = analysis.leaders.clone();
+    leaders.extend(
+        analysis
+            .instructions
+            .values()
+            .filter(|instruction| instruction.opcode == Opcode::Output)
+            .map(|instruction| instruction.address),
+    );
+    let mut code = String::new();
+
+    let _ = writeln!(
+        code,
+        "// Generated by intcode::transpiler::transpile. Do not edit."
+    );
+    let _ = writeln!(code, "#[allow(dead_code, clippy::all)]");
+    let _ = writeln!(code, "pub mod {} {{", name);
+    let _ = writeln!(
+        code,
+        "    use intcode::transpiler::{{CompiledComputer, Exit, Machine}};"
+    );
+    let _ = writeln!(
+        code,
+        "    use intcode::{{Input, MemoryType, Output, TryReadError, TryWriteError}};"
+    );
+    let _ = writeln!(code);
+
+    let _ = write!(code, "    pub const PROGRAM: &[MemoryType] = &[");
+    for (i, value) in program.iter().enumerate() {
+        if i % 16 == 0 {
+            let _ = write!(code, "\n        ");
+        }
+        let _ = write!(code, "{}, ", value);
+    }
+    let _ = writeln!(code, "\n    ];");
+    let _ = writeln!(code);
+
+    let _ = write!(code, "    pub const CODE: &[(usize, usize)] = &[");
+    for (start, end) in analysis.code_ranges() {
+        let _ = write!(code, "({}, {}), ", start, end);
+    }
+    let _ = writeln!(code, "];");
+    let _ = writeln!(code);
+
+    let _ = writeln!(
+        code,
+        "    pub fn new<I: Input<MemoryType>, O: Output<MemoryType>>(input: I, output: O) -> CompiledComputer<I, O>"
+    );
+    let _ = writeln!(code, "    where");
+    let _ = writeln!(code, "        I::ReadError: core::fmt::Debug,");
+    let _ = writeln!(code, "    {{");
+    let _ = writeln!(
+        code,
+        "        CompiledComputer::new(PROGRAM, CODE, input, output, run::<I, O>)"
+    );
+    let _ = writeln!(code, "    }}");
+    let _ = writeln!(code);
+
+    let _ = writeln!(
+        code,
+        "    pub fn run<I: Input<MemoryType>, O: Output<MemoryType>>(m: &mut Machine<I, O>) -> Exit {{"
+    );
+    let _ = writeln!(code, "        let mut pc = m.ip;");
+    let _ = writeln!(code, "        loop {{");
+    let _ = writeln!(code, "            match pc {{");
+
+    for &leader in &leaders {
+        let _ = writeln!(code, "            {} => {{", leader);
+        let mut address = leader;
+        loop {
+            if analysis.invalid.contains(&address) {
+                let _ = writeln!(
+                    code,
+                    "                m.ip = {}; return Exit::Fallback;",
+                    address
+                );
+                break;
+            }
+            let instruction = &analysis.instructions[&address];
+            if !emit_instruction(&mut code, instruction, &leaders) {
+                break;
+            }
+            address = instruction.next_address();
+            if leaders.contains(&address) {
+                let _ = writeln!(code, "                pc = {};", address);
+                break;
+            }
+        }
+        let _ = writeln!(code, "            }}");
+    }
+
+    let _ = writeln!(
+        code,
+        "            _ => {{ m.ip = pc; return Exit::Fallback; }}"
+    );
+    let _ = writeln!(code, "            }}");
+    let _ = writeln!(code, "        }}");
+    let _ = writeln!(code, "    }}");
+    let _ = writeln!(code, "}}");
+
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::VecDeque;
+
+    use super::*;
+    use crate::{BoundedQueue, TryReadError, TryWriteError};
+
+    // The generated code can't be compiled from within a unit test, but the analysis that
+    // decides what gets compiled can be checked.
+    #[test]
+    fn code_discovery() {
+        // Writes a return address, "calls" the function at 10, which outputs its argument and
+        // returns by jumping back through the saved address.
+        let program = vec![
+            21101, 7, 0, 1, // 0: add 7, 0, [rb+1]
+            1105, 1, 10, // 4: jnz 1, 10
+            99, // 7: hlt
+            0, 0, // 8: data
+            104, 42, // 10: out 42
+            2105, 1, 1, // 12: jnz 1, [rb+1]
+        ];
+        let analysis = Analysis::new(&program);
+
+        assert_eq!(
+            vec![0, 7, 10],
+            analysis.leaders.iter().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0, 1), (4, 8), (10, 11), (12, 14)],
+            analysis.code_ranges()
+        );
+        assert!(analysis.invalid.is_empty());
+    }
+
+    #[test]
+    fn invalid_instructions_are_not_code() {
+        let program = vec![1106, 1, 10, 42, 99];
+        let analysis = Analysis::new(&program);
+        assert_eq!(vec![(0, 2)], analysis.code_ranges());
+        assert!(analysis.invalid.contains(&3));
+    }
+
+    #[test]
+    fn generated_code() {
+        let program = vec![3, 9, 1001, 9, 1, 9, 4, 9, 99, 0];
+        let code = transpile(&program, "increment");
+
+        assert!(code.contains("pub mod increment {"));
+        assert!(code
+            .contains("pub const CODE: &[(usize, usize)] = &[(0, 1), (2, 3), (6, 7), (8, 9), ];"));
+        assert!(code.contains("            0 => {"));
+        assert!(code.contains("m.ip = 0; return Exit::NeedInput;"));
+        assert!(code.contains("let v = m.load(m.load(3) as usize) + m.load(4);"));
+        assert!(
+            code.contains("if m.store(m.load(5) as usize, v) { m.ip = 6; return Exit::Fallback; }")
+        );
+        assert!(code.contains("m.ip = 8; return Exit::Halt;"));
+        // Execution resumes at the output if it was full
+        assert!(code.contains("            6 => {"));
+        assert!(code.contains("Err(TryWriteError::Full) => { m.ip = 6; return Exit::OutputFull; }"));
+    }
+
+    #[test]
+    fn store_to_immediate() {
+        let program = vec![11101, 1, 2, 3, 99];
+        let code = transpile(&program, "invalid");
+        assert!(code.contains("            0 => {\n                //      0: "));
+        assert!(code.contains("m.ip = 0; return Exit::Fallback;"));
+        assert!(!code.contains("panic!"));
+    }
+
+    // Hand-written equivalent of what `transpile` generates for [3, 5, 4, 5, 99, 0]
+    fn echo<I: Input<MemoryType>, O: Output<MemoryType>>(m: &mut Machine<I, O>) -> Exit {
+        if m.ip != 0 {
+            return Exit::Fallback;
+        }
+        let v = match m.input.try_read() {
+            Ok(v) => v,
+            Err(TryReadError::Empty) => return Exit::NeedInput,
+            Err(_) => return Exit::Fallback,
+        };
+        if m.store(5, v) {
+            m.ip = 2;
+            return Exit::Fallback;
+        }
+        let v = m.load(5);
+        let _ = m.output.write(v);
+        m.last_output = v;
+        m.ip = 4;
+        Exit::Halt
+    }
+
+    #[test]
+    fn compiled_computer() {
+        let program = vec![3, 5, 4, 5, 99, 0];
+        let mut computer =
+            CompiledComputer::new(&program, &[(0, 5)], VecDeque::new(), Vec::new(), echo);
+        assert_eq!(RunState::NeedInput, computer.run_program());
+        computer.get_input().push_back(42);
+        assert_eq!(RunState::Stopped(42), computer.resume());
+        assert_eq!(&vec![42], computer.get_output());
+        assert!(computer.is_compiled());
+    }
+
+    // Hand-written equivalent of what `transpile` generates for [104, 1, 104, 2, 99]
+    fn two_outputs<I: Input<MemoryType>, O: Output<MemoryType>>(m: &mut Machine<I, O>) -> Exit {
+        let mut pc = m.ip;
+        loop {
+            match pc {
+                0 | 2 => {
+                    let v = m.load(pc + 1);
+                    match m.output.try_write(v) {
+                        Ok(()) => {}
+                        Err(TryWriteError::Full) => {
+                            m.ip = pc;
+                            return Exit::OutputFull;
+                        }
+                        Err(_) => {
+                            m.ip = pc;
+                            return Exit::Fallback;
+                        }
+                    }
+                    m.last_output = v;
+                    pc += 2;
+                }
+                4 => {
+                    m.ip = 4;
+                    return Exit::Halt;
+                }
+                _ => {
+                    m.ip = pc;
+                    return Exit::Fallback;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn output_full() {
+        let program = vec![104, 1, 104, 2, 99];
+        let mut computer = CompiledComputer::new(
+            &program,
+            &[(0, 1), (2, 3), (4, 5)],
+            VecDeque::new(),
+            BoundedQueue::new(1),
+            two_outputs,
+        );
+        assert_eq!(RunState::OutputFull { ip: 2 }, computer.run_program());
+        assert_eq!(Some(1), computer.get_output().pop_front());
+        assert_eq!(RunState::Stopped(2), computer.resume());
+        assert_eq!(Some(2), computer.get_output().pop_front());
+        assert!(computer.is_compiled());
+    }
+
+    #[test]
+    fn fallback_on_self_modification() {
+        // Same as above, but the input is stored into the output instruction's operand
+        let program = vec![3, 3, 104, 0, 99];
+        let mut computer =
+            CompiledComputer::new(&program, &[(0, 5)], VecDeque::new(), Vec::new(), |m| {
+                let v = match m.input.try_read() {
+                    Ok(v) => v,
+                    Err(TryReadError::Empty) => return Exit::NeedInput,
+                    Err(_) => return Exit::Fallback,
+                };
+                if m.store(3, v) {
+                    m.ip = 2;
+                    return Exit::Fallback;
+                }
+                unreachable!()
+            });
+        computer.get_input().push_back(42);
+        assert_eq!(RunState::Stopped(42), computer.run_program());
+        assert!(!computer.is_compiled());
+    }
+}
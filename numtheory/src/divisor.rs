@@ -0,0 +1,141 @@
+// Greatest common divisor and least common multiple
+
+use crate::{Integer, Signed};
+
+// The greatest common divisor is never negative, and gcd(0, 0) = 0. The only result that doesn't
+// fit into a signed type is 2^(bits - 1), e.g. for gcd(i64::MIN, 0).
+pub fn checked_gcd<T: Integer>(mut a: T, mut b: T) -> Option<T> {
+    while b != T::ZERO {
+        // The only remainder that overflows is MIN % -1, which is 0
+        let remainder = a.checked_rem(b).unwrap_or(T::ZERO);
+        a = b;
+        b = remainder;
+    }
+    a.checked_abs()
+}
+
+// Panics like an arithmetic operator if the result overflows.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    checked_gcd(a, b).expect("attempt to calculate the gcd with overflow")
+}
+
+// The least common multiple is never negative, and 0 if a or b is 0. Divides before multiplying,
+// so that it only overflows if the result doesn't fit.
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    let gcd = checked_gcd(a, b)?;
+    a.checked_div(gcd)?.checked_mul(b)?.checked_abs()
+}
+
+// Extended Euclidean algorithm: the gcd g of a and b, and x and y with a * x + b * y = g.
+// Returns `None` if a or b is the minimum value of the type.
+pub fn egcd<T: Signed>(a: T, b: T) -> Option<(T, T, T)> {
+    let (mut old_r, mut r) = (a.checked_abs()?, b.checked_abs()?);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+
+    // The coefficients alternate in sign and stay below |a| and |b| in magnitude, so they don't
+    // overflow.
+    while r != T::ZERO {
+        let quotient = old_r.checked_div(r)?;
+        let next = |old: T, current: T| old.checked_sub(quotient.checked_mul(current)?);
+
+        let next_r = next(old_r, r)?;
+        old_r = r;
+        r = next_r;
+        let next_x = next(old_x, x)?;
+        old_x = x;
+        x = next_x;
+        let next_y = next(old_y, y)?;
+        old_y = y;
+        y = next_y;
+    }
+
+    let sign = |value: T, coefficient: T| {
+        if value < T::ZERO {
+            coefficient.checked_neg()
+        } else {
+            Some(coefficient)
+        }
+    };
+    Some((old_r, sign(a, old_x)?, sign(b, old_y)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn examples() {
+        assert_eq!(252, gcd(3528, 3780));
+        assert_eq!(6, gcd(-12i32, 18));
+        assert_eq!(7, gcd(0u8, 7));
+        assert_eq!(0, gcd(0, 0));
+        assert_eq!(None, checked_gcd(i64::MIN, 0));
+        assert_eq!(Some(1), checked_gcd(i64::MIN, -1));
+
+        assert_eq!(Some(36), lcm(12, -18));
+        assert_eq!(Some(0), lcm(0, 5));
+        assert_eq!(Some(3 << 40), lcm(1u64 << 40, 3 << 40));
+        assert_eq!(None, lcm(u64::MAX, u64::MAX - 1));
+        assert_eq!(None, lcm(i8::MIN, 3));
+
+        assert_eq!(Some((2, -9, 47)), egcd(240, 46));
+        assert_eq!(Some((2, 9, 47)), egcd(-240, 46));
+        assert_eq!(Some((5, 0, -1)), egcd(0, -5));
+        assert_eq!(None, egcd(i32::MIN, 3));
+    }
+
+    proptest! {
+        #[test]
+        fn gcd_divides_both(a: i64, b: i64) {
+            match checked_gcd(a, b) {
+                Some(0) => prop_assert!(a == 0 && b == 0),
+                Some(g) => {
+                    prop_assert!(g > 0);
+                    prop_assert_eq!(0, a % g);
+                    prop_assert_eq!(0, b % g);
+                }
+                // Only multiples of 2^63 have a gcd that doesn't fit
+                None => prop_assert!((a == 0 || a == i64::MIN) && (b == 0 || b == i64::MIN)),
+            }
+        }
+
+        #[test]
+        fn gcd_is_symmetric(a: u32, b: u32) {
+            prop_assert_eq!(gcd(a, b), gcd(b, a));
+        }
+
+        // Any common divisor of a and b divides a * x + b * y, so g is the greatest one.
+        #[test]
+        fn bezout_identity(a in (i64::MIN + 1).., b in (i64::MIN + 1)..) {
+            let (g, x, y) = egcd(a, b).unwrap();
+            prop_assert_eq!(gcd(a, b), g);
+            prop_assert_eq!(g as i128, a as i128 * x as i128 + b as i128 * y as i128);
+        }
+
+        #[test]
+        fn lcm_without_overflow(a: u64, b: u64) {
+            let expected = if a == 0 || b == 0 {
+                0
+            } else {
+                a as u128 / gcd(a, b) as u128 * b as u128
+            };
+            if expected <= u64::MAX as u128 {
+                prop_assert_eq!(Some(expected as u64), lcm(a, b));
+            } else {
+                prop_assert_eq!(None, lcm(a, b));
+            }
+        }
+
+        #[test]
+        fn lcm_of_signed_values(a: i16, b: i16) {
+            let expected = lcm(a as i64, b as i64).unwrap();
+            prop_assert_eq!(i16::try_from(expected).ok(), lcm(a, b));
+        }
+    }
+}
@@ -0,0 +1,87 @@
+// The operations of the primitive integers that the algorithms are generic over.
+
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+
+pub trait Integer: Copy + Ord + Hash + Debug + Display {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn checked_div(self, other: Self) -> Option<Self>;
+    fn checked_rem(self, other: Self) -> Option<Self>;
+
+    fn checked_abs(self) -> Option<Self> {
+        if self < Self::ZERO {
+            Self::ZERO.checked_sub(self)
+        } else {
+            Some(self)
+        }
+    }
+
+    // Quotient and remainder rounded towards negative infinity for positive divisors, so that
+    // the remainder is never negative
+    fn div_euclid(self, other: Self) -> Self;
+    fn rem_euclid(self, other: Self) -> Self;
+}
+
+// Integers with negative values, needed for Bézout coefficients and fractions
+pub trait Signed: Integer {
+    fn checked_neg(self) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn checked_div(self, other: Self) -> Option<Self> {
+                    <$t>::checked_div(self, other)
+                }
+
+                fn checked_rem(self, other: Self) -> Option<Self> {
+                    <$t>::checked_rem(self, other)
+                }
+
+                fn div_euclid(self, other: Self) -> Self {
+                    <$t>::div_euclid(self, other)
+                }
+
+                fn rem_euclid(self, other: Self) -> Self {
+                    <$t>::rem_euclid(self, other)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! signed {
+    ($($t:ty),*) => {
+        $(
+            impl Signed for $t {
+                fn checked_neg(self) -> Option<Self> {
+                    <$t>::checked_neg(self)
+                }
+            }
+        )*
+    };
+}
+
+integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+signed!(i8, i16, i32, i64, i128, isize);
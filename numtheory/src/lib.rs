@@ -0,0 +1,14 @@
+// Number theory on the primitive integer types: greatest common divisors, modular arithmetic and
+// exact fractions. Results that don't fit into the integer type are reported as `None` instead of
+// wrapping around; only `gcd` and the operators of `Ratio` panic on overflow, like the operators
+// of the integers.
+
+mod divisor;
+mod integer;
+mod modular;
+mod ratio;
+
+pub use divisor::{checked_gcd, egcd, gcd, lcm};
+pub use integer::{Integer, Signed};
+pub use modular::{crt, modinv};
+pub use ratio::Ratio;
@@ -0,0 +1,152 @@
+// Modular inverse and the Chinese remainder theorem
+
+use crate::{egcd, Integer, Signed};
+
+// The x in 0..modulus with a * x ≡ 1 (mod modulus), if a and the modulus are coprime. The modulus
+// must be positive.
+pub fn modinv<T: Signed>(a: T, modulus: T) -> Option<T> {
+    if modulus <= T::ZERO {
+        return None;
+    }
+    let (gcd, x, _) = egcd(a.rem_euclid(modulus), modulus)?;
+    if gcd != T::ONE {
+        return None;
+    }
+    Some(x.rem_euclid(modulus))
+}
+
+// Solves x ≡ residue (mod modulus) for all pairs of (residue, modulus). The moduli don't need to be
+// coprime: the solution is unique modulo their lcm. Returns the smallest non-negative solution and
+// the lcm, or `None` if a modulus isn't positive, the congruences contradict each other or the
+// lcm doesn't fit.
+pub fn crt<T: Signed>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let mut solution = (T::ZERO, T::ONE);
+    for &(residue, modulus) in congruences {
+        if modulus <= T::ZERO {
+            return None;
+        }
+        solution = merge(solution, (residue.rem_euclid(modulus), modulus))?;
+    }
+    Some(solution)
+}
+
+// Combines x ≡ r1 (mod m1) and x ≡ r2 (mod m2), where 0 <= r < m.
+fn merge<T: Signed>((r1, m1): (T, T), (r2, m2): (T, T)) -> Option<(T, T)> {
+    // m1 * p ≡ g (mod m2)
+    let (g, p, _) = egcd(m1, m2)?;
+    let difference = r2.checked_sub(r1)?;
+    if difference.checked_rem(g)? != T::ZERO {
+        return None;
+    }
+
+    // x = r1 + m1 * k, where m1 * k ≡ difference (mod m2), i.e. k ≡ difference / g * p
+    // (mod m2 / g). Then x < m1 + m1 * (m2 / g - 1), which is the lcm.
+    let reduced = m2.checked_div(g)?;
+    let k = mul_mod(
+        difference.checked_div(g)?.rem_euclid(reduced),
+        p.rem_euclid(reduced),
+        reduced,
+    );
+    let lcm = m1.checked_div(g)?.checked_mul(m2)?;
+    let x = r1.checked_add(m1.checked_mul(k)?)?;
+    Some((x, lcm))
+}
+
+// (a * b) mod m for 0 <= a, b < m, without overflowing if a * b doesn't fit.
+fn mul_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    if let Some(product) = a.checked_mul(b) {
+        return product.rem_euclid(modulus);
+    }
+
+    // Double and add, with sums that stay below the modulus
+    let add = |x: T, y: T| {
+        let rest = modulus.checked_sub(y).unwrap();
+        if x >= rest {
+            x.checked_sub(rest).unwrap()
+        } else {
+            x.checked_add(y).unwrap()
+        }
+    };
+    let two = T::ONE.checked_add(T::ONE).unwrap();
+    let (mut a, mut b) = (a, b);
+    let mut product = T::ZERO;
+    while b > T::ZERO {
+        if b.rem_euclid(two) == T::ONE {
+            product = add(product, a);
+        }
+        a = add(a, a);
+        b = b.div_euclid(two);
+    }
+    product
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcm;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    #[test]
+    fn examples() {
+        assert_eq!(Some(4), modinv(3, 11));
+        assert_eq!(Some(7), modinv(-3, 11));
+        assert_eq!(Some(0), modinv(5, 1));
+        assert_eq!(None, modinv(6, 9));
+        assert_eq!(None, modinv(3, 0));
+
+        assert_eq!(Some((23, 105)), crt(&[(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Some((10, 12)), crt(&[(2, 4), (4, 6)]));
+        assert_eq!(None, crt(&[(0, 2), (1, 4)]));
+        assert_eq!(Some((0, 1)), crt::<i32>(&[]));
+        assert_eq!(None, crt(&[(1, -3)]));
+        assert_eq!(None, crt(&[(1, i64::MAX), (2, i64::MAX - 1)]));
+    }
+
+    #[test]
+    fn products_larger_than_the_type() {
+        let modulus = i64::MAX - 24;
+        assert_eq!(
+            ((modulus - 1) as i128 * (modulus - 2) as i128 % modulus as i128) as i64,
+            mul_mod(modulus - 1, modulus - 2, modulus)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn inverse(a: i64, modulus in 1i64..) {
+            match modinv(a, modulus) {
+                Some(x) => {
+                    prop_assert!((0..modulus).contains(&x));
+                    prop_assert_eq!(
+                        1 % modulus as i128,
+                        (a as i128 * x as i128).rem_euclid(modulus as i128)
+                    );
+                }
+                None => prop_assert_ne!(1, crate::gcd(a as i128, modulus as i128)),
+            }
+        }
+
+        #[test]
+        fn multiplication(a: u64, b: u64, modulus in 1u64..) {
+            let (a, b) = (a % modulus, b % modulus);
+            let expected = a as u128 * b as u128 % modulus as u128;
+            prop_assert_eq!(expected as u64, mul_mod(a, b, modulus));
+        }
+
+        // The congruences are taken from a known solution, so they don't contradict each other.
+        #[test]
+        fn chinese_remainder(x in 0i64..1 << 40, moduli in vec(1i64..1 << 20, 0..4)) {
+            let congruences: Vec<_> = moduli.iter().map(|&m| (x % m, m)).collect();
+            let expected = moduli.iter().try_fold(1, |l, &m| lcm(l, m)).unwrap();
+            prop_assert_eq!(Some((x % expected, expected)), crt(&congruences));
+        }
+
+        #[test]
+        fn large_moduli(x: i64, a in 1i64..1 << 31, b in 1i64..1 << 31) {
+            let (solution, modulus) = crt(&[(x, a), (x, b)]).unwrap();
+            prop_assert_eq!(Some(modulus), lcm(a, b));
+            prop_assert_eq!(x.rem_euclid(modulus), solution);
+        }
+    }
+}
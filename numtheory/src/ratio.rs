@@ -0,0 +1,321 @@
+// Exact fractions of signed integers
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::{checked_gcd, Signed};
+
+// Always in lowest terms with a positive denominator, so that equal fractions have equal fields.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Ratio<T> {
+    numer: T,
+    denom: T,
+}
+
+impl<T: Signed> Ratio<T> {
+    // Panics if the denominator is 0, or if the fraction doesn't fit after reducing it.
+    pub fn new(numer: T, denom: T) -> Self {
+        assert!(denom != T::ZERO, "denominator of a fraction is zero");
+        Self::checked_new(numer, denom).expect("attempt to reduce a fraction with overflow")
+    }
+
+    pub fn checked_new(numer: T, denom: T) -> Option<Self> {
+        if denom == T::ZERO {
+            return None;
+        }
+        let gcd = checked_gcd(numer, denom)?;
+        let (numer, denom) = (numer.checked_div(gcd)?, denom.checked_div(gcd)?);
+        if denom < T::ZERO {
+            Some(Self {
+                numer: numer.checked_neg()?,
+                denom: denom.checked_neg()?,
+            })
+        } else {
+            Some(Self { numer, denom })
+        }
+    }
+
+    pub fn from_integer(value: T) -> Self {
+        Self {
+            numer: value,
+            denom: T::ONE,
+        }
+    }
+
+    pub fn numer(&self) -> T {
+        self.numer
+    }
+
+    pub fn denom(&self) -> T {
+        self.denom
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == T::ONE
+    }
+
+    // Largest integer that isn't greater than the fraction
+    pub fn floor(&self) -> T {
+        self.numer.div_euclid(self.denom)
+    }
+
+    // Can overflow before reducing the sum, even if the result would fit.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let gcd = checked_gcd(self.denom, other.denom)?;
+        let (left, right) = (self.denom.checked_div(gcd)?, other.denom.checked_div(gcd)?);
+        let numer = self
+            .numer
+            .checked_mul(right)?
+            .checked_add(other.numer.checked_mul(left)?)?;
+        Self::checked_new(numer, left.checked_mul(other.denom)?)
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self {
+            numer: self.numer.checked_neg()?,
+            denom: self.denom,
+        })
+    }
+
+    // Cancels the common factors first, so that the product is already in lowest terms.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        let a = checked_gcd(self.numer, other.denom)?;
+        let b = checked_gcd(other.numer, self.denom)?;
+        Some(Self {
+            numer: self
+                .numer
+                .checked_div(a)?
+                .checked_mul(other.numer.checked_div(b)?)?,
+            denom: self
+                .denom
+                .checked_div(b)?
+                .checked_mul(other.denom.checked_div(a)?)?,
+        })
+    }
+
+    // `None` for 0
+    pub fn checked_recip(self) -> Option<Self> {
+        Self::checked_new(self.denom, self.numer)
+    }
+
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        self.checked_mul(other.checked_recip()?)
+    }
+}
+
+impl<T: Signed> From<T> for Ratio<T> {
+    fn from(value: T) -> Self {
+        Self::from_integer(value)
+    }
+}
+
+// The operators panic on overflow, like those of the integers.
+impl<T: Signed> Add for Ratio<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other)
+            .expect("attempt to add fractions with overflow")
+    }
+}
+
+impl<T: Signed> Sub for Ratio<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other)
+            .expect("attempt to subtract fractions with overflow")
+    }
+}
+
+impl<T: Signed> Mul for Ratio<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other)
+            .expect("attempt to multiply fractions with overflow")
+    }
+}
+
+impl<T: Signed> Div for Ratio<T> {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        assert!(other.numer != T::ZERO, "attempt to divide by zero");
+        self.checked_div(other)
+            .expect("attempt to divide fractions with overflow")
+    }
+}
+
+impl<T: Signed> Neg for Ratio<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.checked_neg()
+            .expect("attempt to negate a fraction with overflow")
+    }
+}
+
+impl<T: Signed> PartialOrd for Ratio<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Compares the integer parts, then the reciprocals of the remainders in reverse order, like the
+// terms of continued fractions. Unlike cross-multiplying, this can't overflow.
+impl<T: Signed> Ord for Ratio<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (mut a, mut b) = (self.numer, self.denom);
+        let (mut c, mut d) = (other.numer, other.denom);
+        let mut reversed = false;
+        loop {
+            let ordering = match a.div_euclid(b).cmp(&c.div_euclid(d)) {
+                Ordering::Equal => {
+                    let (r, s) = (a.rem_euclid(b), c.rem_euclid(d));
+                    if r != T::ZERO && s != T::ZERO {
+                        // r / b < s / d exactly if b / r > d / s
+                        a = b;
+                        b = r;
+                        c = d;
+                        d = s;
+                        reversed = !reversed;
+                        continue;
+                    }
+                    r.cmp(&s)
+                }
+                ordering => ordering,
+            };
+            return if reversed {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+        }
+    }
+}
+
+impl<T: Signed> fmt::Display for Ratio<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::convert::TryFrom;
+
+    fn ratio(numer: i64, denom: i64) -> Ratio<i64> {
+        Ratio::new(numer, denom)
+    }
+
+    #[test]
+    fn examples() {
+        assert_eq!((-2, 3), (ratio(4, -6).numer(), ratio(4, -6).denom()));
+        assert_eq!(ratio(5, 6), ratio(1, 2) + ratio(1, 3));
+        assert_eq!(ratio(1, 6), ratio(1, 2) - ratio(1, 3));
+        assert_eq!(ratio(1, 6), ratio(1, 2) * ratio(1, 3));
+        assert_eq!(ratio(3, 2), ratio(1, 2) / ratio(1, 3));
+        assert_eq!(ratio(-1, 2), -ratio(1, 2));
+        assert_eq!(Ratio::from(4), ratio(8, 2));
+
+        assert_eq!(-2, ratio(-3, 2).floor());
+        assert!(ratio(6, 3).is_integer());
+        assert_eq!("-3/2", ratio(3, -2).to_string());
+        assert_eq!("2", ratio(6, 3).to_string());
+
+        assert!(ratio(1, 3) < ratio(1, 2));
+        assert!(ratio(-1, 2) < ratio(-1, 3));
+        assert!(ratio(22, 7) > ratio(355, 113));
+
+        assert_eq!(None, Ratio::checked_new(1, 0));
+        assert_eq!(None, Ratio::checked_new(1, i8::MIN));
+        assert_eq!(None, Ratio::new(1i8, 100).checked_add(Ratio::new(1, 99)));
+        assert_eq!(None, Ratio::new(1i8, 2).checked_div(Ratio::from(0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn division_by_zero() {
+        let _ = ratio(1, 2) / ratio(0, 1);
+    }
+
+    fn widen(ratio: Ratio<i8>) -> Ratio<i64> {
+        Ratio::new(ratio.numer() as i64, ratio.denom() as i64)
+    }
+
+    fn narrow(ratio: Ratio<i64>) -> Option<Ratio<i8>> {
+        Some(Ratio {
+            numer: i8::try_from(ratio.numer()).ok()?,
+            denom: i8::try_from(ratio.denom()).ok()?,
+        })
+    }
+
+    fn small() -> impl Strategy<Value = Ratio<i8>> {
+        (any::<i8>(), any::<i8>())
+            .prop_filter_map("invalid fraction", |(n, d)| Ratio::checked_new(n, d))
+    }
+
+    fn large() -> impl Strategy<Value = Ratio<i64>> {
+        (any::<i64>(), any::<i64>())
+            .prop_filter_map("invalid fraction", |(n, d)| Ratio::checked_new(n, d))
+    }
+
+    proptest! {
+        #[test]
+        fn lowest_terms(numer: i64, denom in any::<i64>().prop_filter("zero", |&d| d != 0)) {
+            if let Some(ratio) = Ratio::checked_new(numer, denom) {
+                prop_assert!(ratio.denom() > 0);
+                prop_assert_eq!(1, crate::gcd(ratio.numer(), ratio.denom()));
+                prop_assert_eq!(
+                    numer as i128 * ratio.denom() as i128,
+                    ratio.numer() as i128 * denom as i128
+                );
+            }
+        }
+
+        #[test]
+        fn ordering_without_overflow(x in large(), y in large()) {
+            let cross = (x.numer() as i128 * y.denom() as i128)
+                .cmp(&(y.numer() as i128 * x.denom() as i128));
+            prop_assert_eq!(cross, x.cmp(&y));
+        }
+
+        // Products and quotients fail exactly if the result doesn't fit, sums only might fail
+        // otherwise.
+        #[test]
+        fn arithmetic_with_overflow(x in small(), y in small()) {
+            let (a, b) = (widen(x), widen(y));
+            for (sum, checked) in [(a + b, x.checked_add(y)), (a - b, x.checked_sub(y))] {
+                match narrow(sum) {
+                    Some(sum) => prop_assert!(checked.is_none() || checked == Some(sum)),
+                    None => prop_assert_eq!(None, checked),
+                }
+            }
+            prop_assert_eq!(narrow(a * b), x.checked_mul(y));
+            prop_assert_eq!(b.checked_recip().and_then(|b| narrow(a * b)), x.checked_div(y));
+        }
+
+        #[test]
+        fn inverse_operations(x in small(), y in small()) {
+            let (a, b) = (widen(x), widen(y));
+            prop_assert_eq!(a, a + b - b);
+            prop_assert_eq!(a + b, b + a);
+            if b != Ratio::from(0) {
+                prop_assert_eq!(a, a * b / b);
+            }
+            prop_assert_eq!(a < b, a - b < Ratio::from(0));
+        }
+    }
+}
@@ -0,0 +1,139 @@
+// Expected answers of the days, read from `answers.toml` at the root of the workspace. Used by
+// `aoc verify` and by the tests of the days.
+
+use std::fs;
+
+use toml::Value;
+
+pub const FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.toml");
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expected {
+    pub day: u32,
+    // Name of the input file in the day's crate, without `.txt`
+    pub input: String,
+    pub parts: [Option<String>; 2],
+}
+
+// Parses the registry. The entries are sorted by day, each starting with its puzzle input.
+pub fn parse(text: &str) -> Result<Vec<Expected>, String> {
+    let value: Value = text.parse().map_err(|e| format!("Invalid TOML: {}", e))?;
+    let days = value
+        .as_table()
+        .ok_or_else(|| String::from("Expected a table of days"))?;
+
+    let mut entries = Vec::new();
+    for (key, inputs) in days {
+        let day = key
+            .strip_prefix("day")
+            .and_then(|number| number.parse().ok())
+            .ok_or_else(|| format!("Invalid day: {}", key))?;
+        let inputs = inputs
+            .as_table()
+            .ok_or_else(|| format!("Expected a table of inputs for {}", key))?;
+
+        for (input, parts) in inputs {
+            let location = format!("{}.{}", key, input);
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| format!("Expected a table of parts for {}", location))?;
+            let mut expected = [None, None];
+            for (part, answer) in parts {
+                let index = match part.as_str() {
+                    "part1" => 0,
+                    "part2" => 1,
+                    _ => return Err(format!("Unknown part {} of {}", part, location)),
+                };
+                expected[index] = Some(match answer {
+                    Value::String(answer) => answer.clone(),
+                    Value::Integer(answer) => answer.to_string(),
+                    _ => return Err(format!("Invalid answer for {}.{}", location, part)),
+                });
+            }
+            entries.push(Expected {
+                day,
+                input: input.clone(),
+                parts: expected,
+            });
+        }
+    }
+
+    entries.sort_by(|a, b| {
+        (a.day, a.input != "input", &a.input).cmp(&(b.day, b.input != "input", &b.input))
+    });
+    Ok(entries)
+}
+
+// The expected answer of `part` (1 or 2) for the puzzle input of `day`. Panics if the registry
+// doesn't have it.
+pub fn expected(day: u32, part: usize) -> String {
+    let text = fs::read_to_string(FILE).unwrap_or_else(|e| panic!("Error reading {}: {}", FILE, e));
+    let entries = parse(&text).unwrap_or_else(|e| panic!("{}: {}", FILE, e));
+    entries
+        .into_iter()
+        .find(|entry| entry.day == day && entry.input == "input")
+        .and_then(|entry| entry.parts[part - 1].clone())
+        .unwrap_or_else(|| panic!("No answer for part {} of day {} in {}", part, day, FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expected(day: u32, input: &str, part1: Option<&str>, part2: Option<&str>) -> Expected {
+        Expected {
+            day,
+            input: String::from(input),
+            parts: [part1.map(String::from), part2.map(String::from)],
+        }
+    }
+
+    #[test]
+    fn parse_registry() {
+        let entries = parse(
+            r#"
+            [day12]
+            example = { part2 = 2772 }
+            input = { part1 = 12466, part2 = 360689156787864 }
+
+            [day08]
+            input = { part1 = 1340, part2 = "LEJKC" }
+            "#,
+        );
+        assert_eq!(
+            Ok(vec![
+                expected(8, "input", Some("1340"), Some("LEJKC")),
+                expected(12, "input", Some("12466"), Some("360689156787864")),
+                expected(12, "example", None, Some("2772")),
+            ]),
+            entries
+        );
+    }
+
+    #[test]
+    fn expected_answers() {
+        assert_eq!("3399394", super::expected(1, 1));
+        assert_eq!("LEJKC", super::expected(8, 2));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(parse("[day01").unwrap_err().starts_with("Invalid TOML: "));
+        assert_eq!(
+            Err(String::from("Invalid day: first")),
+            parse("[first]\ninput = { part1 = 1 }")
+        );
+        assert_eq!(
+            Err(String::from("Expected a table of parts for day01.input")),
+            parse("[day01]\ninput = 1")
+        );
+        assert_eq!(
+            Err(String::from("Unknown part part3 of day01.input")),
+            parse("[day01]\ninput = { part3 = 1 }")
+        );
+        assert_eq!(
+            Err(String::from("Invalid answer for day01.input.part1")),
+            parse("[day01]\ninput = { part1 = 1.5 }")
+        );
+    }
+}
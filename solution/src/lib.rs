@@ -0,0 +1,359 @@
+// Common interface of the days' solutions, used by the `aoc` runner and by the binaries of the
+// individual days.
+
+use std::env;
+use std::fmt::{self, Debug};
+use std::fs;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use animation::Animation;
+use benchmark::Bencher;
+
+pub mod answers;
+
+// Answers are shown with `Display`. Puzzles that might not have a solution for an input
+// return an `Option`, those that can fail while solving it (e.g. on unexpected output of an
+// intcode program) a `Result`.
+pub trait Answer {
+    fn to_answer(&self) -> String;
+}
+
+macro_rules! display_answer {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                fn to_answer(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+display_answer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, String);
+
+impl<T: Answer> Answer for Option<T> {
+    fn to_answer(&self) -> String {
+        match self {
+            Some(answer) => answer.to_answer(),
+            None => String::from("No solution found"),
+        }
+    }
+}
+
+impl<T: Answer, E: fmt::Display> Answer for Result<T, E> {
+    fn to_answer(&self) -> String {
+        match self {
+            Ok(answer) => answer.to_answer(),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+}
+
+pub trait Solution {
+    type Input;
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    // Parses the contents of the input file.
+    fn parse(input: &str) -> Result<Self::Input, String>;
+
+    fn part1(input: &Self::Input) -> Self::Answer1;
+
+    fn part2(input: &Self::Input) -> Self::Answer2;
+
+    // Additional information about solving the puzzle (e.g. statistics of the intcode
+    // computer), shown with `--stats`.
+    fn statistics(_input: &Self::Input) -> Option<String> {
+        None
+    }
+
+    // Shows how the puzzle is solved, for the days that can be watched, with `--animate`.
+    fn animate(_input: &Self::Input, _animation: &mut Animation) -> Result<(), String> {
+        Err(String::from("There is no animation for this day"))
+    }
+
+    // Measures the functions that the parts spend most of their time in (e.g. a single step of a
+    // simulation), in addition to parsing and the parts themselves.
+    fn benchmarks(_input: &Self::Input, _bencher: &mut Bencher) {}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    pub answer: String,
+    pub time: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub parse_time: Duration,
+    pub parts: [Part; 2],
+    pub statistics: Option<String>,
+}
+
+impl Report {
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.parts[0].time + self.parts[1].time
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Parse:  {}", format_duration(self.parse_time))?;
+        for (i, part) in self.parts.iter().enumerate() {
+            // Answers drawn as pictures start on a new line
+            if part.answer.contains('\n') {
+                writeln!(f, "Part {} ({}):", i + 1, format_duration(part.time))?;
+                writeln!(f, "{}", part.answer.trim_end())?;
+            } else {
+                writeln!(
+                    f,
+                    "Part {}: {} ({})",
+                    i + 1,
+                    part.answer,
+                    format_duration(part.time)
+                )?;
+            }
+        }
+        if let Some(statistics) = &self.statistics {
+            writeln!(f, "{}", statistics.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_secs_f64() * 1e6;
+    if micros < 1000.0 {
+        format!("{:.0} µs", micros)
+    } else if micros < 1e6 {
+        format!("{:.1} ms", micros / 1e3)
+    } else {
+        format!("{:.2} s", micros / 1e6)
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+// Parses the input and solves both parts, measuring the time of each step.
+pub fn run<S: Solution>(input: &str, statistics: bool) -> Result<Report, String> {
+    let (input, parse_time) = timed(|| S::parse(input));
+    let input = input?;
+    let (answer1, time1) = timed(|| S::part1(&input));
+    let (answer2, time2) = timed(|| S::part2(&input));
+    Ok(Report {
+        parse_time,
+        parts: [
+            Part {
+                answer: answer1.to_answer(),
+                time: time1,
+            },
+            Part {
+                answer: answer2.to_answer(),
+                time: time2,
+            },
+        ],
+        statistics: if statistics {
+            S::statistics(&input)
+        } else {
+            None
+        },
+    })
+}
+
+// Parses the input and animates the solution.
+pub fn animate<S: Solution>(input: &str, animation: &mut Animation) -> Result<(), String> {
+    S::animate(&S::parse(input)?, animation)
+}
+
+// Measures parsing, both parts and the benchmarks of the solution.
+pub fn bench<S: Solution>(input: &str, bencher: &mut Bencher) -> Result<(), String> {
+    let parsed = S::parse(input)?;
+    bencher.bench("parse", || S::parse(input));
+    bencher.bench("part1", || S::part1(&parsed));
+    bencher.bench("part2", || S::part2(&parsed));
+    S::benchmarks(&parsed, bencher);
+    Ok(())
+}
+
+// Entry point of the binaries of the days: `<day> <input file> [--stats] [--animate] [--bench]`
+pub fn main<S: Solution>() {
+    let statistics = env::args().any(|arg| arg == "--stats");
+    let animate = env::args().any(|arg| arg == "--animate");
+    let benchmark = env::args().any(|arg| arg == "--bench");
+    let input_file = match env::args().skip(1).find(|arg| !arg.starts_with("--")) {
+        Some(input_file) => input_file,
+        None => {
+            println!("Please supply input file!");
+            std::process::exit(1);
+        }
+    };
+
+    let input = match fs::read_to_string(input_file) {
+        Ok(input) => input,
+        Err(e) => {
+            println!("Error reading input: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if animate {
+        let mut animation = Animation::terminal(animation::DEFAULT_FPS);
+        let result = self::animate::<S>(&input, &mut animation);
+        drop(animation);
+        if let Err(e) = result {
+            println!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if benchmark {
+        println!("{}", benchmark::HEADER);
+        let mut bencher = Bencher::new()
+            .with_observer(|measurement| println!("{}", benchmark::row(measurement, None)));
+        if let Err(e) = bench::<S>(&input, &mut bencher) {
+            println!("Error parsing input: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    match run::<S>(&input, statistics) {
+        Ok(report) => print!("{}", report),
+        Err(e) => {
+            println!("Error parsing input: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+// Parses the values of an input separated by `separator`, ignoring surrounding whitespace.
+pub fn parse_separated<T>(input: &str, separator: char) -> Result<Vec<T>, String>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    input
+        .trim()
+        .split(separator)
+        .map(str::trim)
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|e| format!("Invalid value \"{}\": {:?}", value, e))
+        })
+        .collect()
+}
+
+// Parses each line of an input.
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, String>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.trim()
+                .parse()
+                .map_err(|e| format!("Line {}: {:?}", i + 1, e))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+        type Answer1 = Option<u32>;
+        type Answer2 = String;
+
+        fn parse(input: &str) -> Result<Self::Input, String> {
+            parse_lines(input)
+        }
+
+        fn part1(input: &Self::Input) -> Option<u32> {
+            input.iter().copied().reduce(|a, b| a + b)
+        }
+
+        fn part2(input: &Self::Input) -> String {
+            input
+                .iter()
+                .map(|value| "#".repeat(*value as usize))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+
+    #[test]
+    fn run_solution() {
+        let report = run::<Sum>("1\n2\n3\n", false).unwrap();
+        assert_eq!("6", report.parts[0].answer);
+        assert_eq!("#\n##\n###", report.parts[1].answer);
+        assert_eq!(None, report.statistics);
+
+        let text = report.to_string();
+        assert!(text.starts_with("Parse:  "));
+        assert!(text.contains("Part 1: 6 ("));
+        assert!(text.contains("):\n#\n##\n###\n"));
+
+        assert_eq!(
+            "No solution found",
+            run::<Sum>("", false).unwrap().parts[0].answer
+        );
+        assert_eq!(
+            Err(String::from("Line 2: ParseIntError { kind: InvalidDigit }")),
+            run::<Sum>("1\nx\n", false)
+        );
+        assert!(animate::<Sum>("1\n", &mut Animation::headless()).is_err());
+    }
+
+    #[test]
+    fn bench_solution() {
+        let mut bencher = Bencher::new().with_target(Duration::from_millis(5));
+        bench::<Sum>("1\n2\n3\n", &mut bencher).unwrap();
+        let names: Vec<&str> = bencher
+            .measurements()
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(vec!["parse", "part1", "part2"], names);
+
+        assert!(bench::<Sum>("x", &mut bencher).is_err());
+        assert_eq!(3, bencher.measurements().len());
+    }
+
+    #[test]
+    fn failed_answers() {
+        assert_eq!("42", Ok::<u32, String>(42).to_answer());
+        assert_eq!(
+            "Error: Invalid tile",
+            Err::<u32, _>("Invalid tile").to_answer()
+        );
+    }
+
+    #[test]
+    fn parse_values() {
+        assert_eq!(Ok(vec![1, -2, 3]), parse_separated::<i32>("1, -2,3\n", ','));
+        assert!(parse_separated::<i32>("1,,3", ',').is_err());
+        assert_eq!(Ok(vec![1, 2]), parse_lines::<u8>("1\n2\n"));
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!("250 µs", format_duration(Duration::from_micros(250)));
+        assert_eq!("12.5 ms", format_duration(Duration::from_micros(12_500)));
+        assert_eq!("3.25 s", format_duration(Duration::from_millis(3250)));
+    }
+}